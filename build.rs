@@ -0,0 +1,10 @@
+fn main() {
+    // `js` is set when the module runs in a JS host, such as a browser, Node, Deno or Bun, where the
+    // imports wasm-bindgen makes work. WASI runtimes and native builds get the fallbacks instead.
+    println!("cargo::rustc-check-cfg=cfg(js)");
+    let arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    if arch == "wasm32" && os != "wasi" {
+        println!("cargo::rustc-cfg=js");
+    }
+}
@@ -0,0 +1,288 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::occurrence::{next_checked_occurrence, next_occurrence, wall_time_rule};
+use crate::validation::{check_date_ms, ValidationError};
+use crate::Unitime;
+
+/// Most calls a `"fire-all"` alarm makes at once to catch up.
+const MAX_CATCH_UP_CALLS: u32 = 1000;
+
+/// What a recurring alarm does when several of its times passed at once, because the page was suspended by
+/// laptop sleep or throttled in a background tab.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Call back once for every time that passed, up to 1000 times.
+    FireAll = "fire-all",
+    /// Call back once for all of them.
+    FireOnce = "fire-once",
+    /// Call back only if a single time passed, and otherwise wait for the next one.
+    Skip = "skip",
+}
+
+// `#[default]` does not survive `#[wasm_bindgen]` on string enums.
+#[allow(clippy::derivable_impls)]
+impl Default for CatchUp {
+    fn default() -> Self {
+        CatchUp::FireOnce
+    }
+}
+
+enum Repeat {
+    Once,
+    /// A fixed interval in milliseconds.
+    Every(i64),
+    /// A wall-clock time in milliseconds after midnight on the weekdays of a bit set, in UTC or local time.
+    Daily { time: i64, mask: u8, utc: bool },
+}
+
+struct Alarm {
+    id: u32,
+    at: i64,
+    repeat: Repeat,
+    callback: Function,
+}
+
+impl Alarm {
+    /// Move a recurring alarm to its first time after `now`, returning how many of its times have passed.
+    /// One-shot alarms return `None`.
+    fn advance(&mut self, now: i64) -> Option<u32> {
+        match self.repeat {
+            Repeat::Once => None,
+            Repeat::Every(interval) => {
+                let count = (now.saturating_sub(self.at) / interval).saturating_add(1);
+                self.at = self.at.saturating_add(count.saturating_mul(interval));
+                Some(count.try_into().unwrap_or(u32::MAX))
+            }
+            Repeat::Daily { time, mask, utc } => {
+                let mut count = 0;
+                while self.at <= now {
+                    self.at = next_occurrence(self.at, time, mask, utc)?;
+                    count += 1;
+                }
+                Some(count)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    alarms: Vec<Alarm>,
+    next_id: u32,
+    catch_up: CatchUp,
+    /// Handle of the pending `setTimeout`, if one is armed.
+    timer: Option<JsValue>,
+    #[cfg(js)]
+    tick: Option<Closure<dyn FnMut()>>,
+}
+
+/// Run the callbacks of every alarm that is due, following the catch-up policy, then arm the timer for
+/// the next one. Callbacks run with no borrow held, so they may add and cancel alarms.
+fn fire_due(state: &Rc<RefCell<State>>) {
+    let now = crate::now_ms();
+    let mut due = Vec::new();
+    let catch_up = {
+        let mut s = state.borrow_mut();
+        s.alarms.retain_mut(|a| {
+            if a.at > now {
+                return true;
+            }
+            let count = a.advance(now);
+            due.push((a.id, a.callback.clone(), count.unwrap_or(1)));
+            count.is_some()
+        });
+        s.catch_up
+    };
+    for (id, callback, count) in due {
+        let missed = count - 1;
+        let calls = match catch_up {
+            CatchUp::FireAll => count.min(MAX_CATCH_UP_CALLS),
+            CatchUp::Skip if missed > 0 => 0,
+            _ => 1,
+        };
+        for _ in 0..calls {
+            // An exception in one callback must not keep the others from running.
+            let _ = callback.call2(&JsValue::NULL, &JsValue::from(id), &JsValue::from(missed));
+        }
+    }
+    arm(state);
+}
+
+/// Replace the pending timeout with one for the earliest alarm.
+#[cfg(js)]
+fn arm(state: &Rc<RefCell<State>>) {
+    let mut s = state.borrow_mut();
+    if let Some(timer) = s.timer.take() {
+        crate::timer::clear_timeout(&timer);
+    }
+    let Some(next) = s.alarms.iter().map(|a| a.at).min() else {
+        return;
+    };
+    let weak = Rc::downgrade(state);
+    let tick = s.tick.get_or_insert_with(|| {
+        Closure::new(move || {
+            if let Some(state) = weak.upgrade() {
+                state.borrow_mut().timer = None;
+                fire_due(&state);
+            }
+        })
+    });
+    s.timer = crate::timer::set_timeout(tick, next - crate::now_ms());
+}
+
+/// There are no timers outside a JS host; `check` still fires due alarms.
+#[cfg(not(js))]
+fn arm(state: &Rc<RefCell<State>>) {
+    state.borrow_mut().timer = None;
+}
+
+/// Many alarms driven by a single `setTimeout`, each calling back with its id when it goes off. Alarms
+/// can fire once, repeat at an interval, or repeat at a wall-clock time. The clock re-checks the time
+/// at least once a minute, so alarms go off soon after the page wakes from sleep, and very long delays
+/// need no care. Call `check()` on `visibilitychange` to fire overdue alarms at once.
+///
+/// Callbacks get the alarm id and the number of times of a recurring alarm that were missed, which is 0
+/// unless the page was suspended past more than one of them. `catchUp` decides how those are handled.
+#[wasm_bindgen]
+pub struct AlarmClock {
+    state: Rc<RefCell<State>>,
+}
+
+impl Default for AlarmClock {
+    fn default() -> Self {
+        AlarmClock::new()
+    }
+}
+
+impl AlarmClock {
+    fn add(&self, at: i64, repeat: Repeat, callback: Function) -> u32 {
+        let id = {
+            let mut s = self.state.borrow_mut();
+            s.next_id += 1;
+            let id = s.next_id;
+            s.alarms.push(Alarm { id, at, repeat, callback });
+            id
+        };
+        arm(&self.state);
+        id
+    }
+}
+
+#[wasm_bindgen]
+impl AlarmClock {
+    /// Creates a new `AlarmClock` with no alarms.
+    /// # Examples
+    /// ```
+    /// const alarms = new AlarmClock();
+    /// document.addEventListener("visibilitychange", () => alarms.check());
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> AlarmClock {
+        AlarmClock { state: Rc::default() }
+    }
+
+    /// Add an alarm that goes off once at `time`, right away if it has passed. Returns its id.
+    /// # Examples
+    /// ```
+    /// const id = alarms.at(meeting.start, id => notify("Meeting starts now"));
+    /// ```
+    pub fn at(&self, time: &Unitime, callback: Function) -> u32 {
+        self.add(time.epoch_ms(), Repeat::Once, callback)
+    }
+
+    /// Add an alarm that goes off every `interval`, first at `first` in epoch milliseconds, within the
+    /// range of a JavaScript `Date`, or one interval from now. Returns its id.
+    /// # Examples
+    /// ```
+    /// alarms.every(UniDuration.fromSeconds(900), id => saveDraft());
+    /// ```
+    pub fn every(&self, interval: &UniDuration, callback: Function, first: Option<f64>) -> Result<u32, ValidationError> {
+        let interval_ms = interval.nanos() / NANOS_PER_MILLI;
+        if interval_ms < 1 {
+            return Err(ValidationError::new("alarm interval must be at least 1 ms"));
+        }
+        let at = match first {
+            Some(ms) => check_date_ms("first", ms)?,
+            None => crate::now_ms() + interval_ms,
+        };
+        Ok(self.add(at, Repeat::Every(interval_ms), callback))
+    }
+
+    /// Add an alarm that goes off whenever the clock reads `hour`:`minute`, in local time unless `utc`
+    /// is `true`, on every day or only on `weekdays` from 0 for Sunday. Returns its id.
+    /// # Examples
+    /// ```
+    /// alarms.daily(7, 30, id => ring(), [1, 2, 3, 4, 5]);
+    /// ```
+    pub fn daily(
+        &self,
+        hour: u32,
+        minute: u32,
+        callback: Function,
+        weekdays: Option<Vec<u32>>,
+        utc: Option<bool>,
+    ) -> Result<u32, ValidationError> {
+        let (time, mask) = wall_time_rule(hour, minute, weekdays)?;
+        let utc = utc.unwrap_or(false);
+        let at = next_checked_occurrence(crate::now_ms(), time, mask, utc)?;
+        Ok(self.add(at, Repeat::Daily { time, mask, utc }, callback))
+    }
+
+    /// Get what recurring alarms do after the page resumes with several of their times passed. The
+    /// default, `"fire-once"`, calls back once per alarm. One-shot alarms always fire, however late.
+    /// # Examples
+    /// ```
+    /// alarms.catchUp = "skip";
+    /// alarms.every(UniDuration.fromSeconds(60), (id, missed) => poll());
+    /// ```
+    #[wasm_bindgen(getter, js_name = "catchUp")]
+    pub fn catch_up(&self) -> CatchUp {
+        self.state.borrow().catch_up
+    }
+
+    #[wasm_bindgen(setter, js_name = "catchUp")]
+    pub fn set_catch_up(&self, catch_up: CatchUp) {
+        self.state.borrow_mut().catch_up = catch_up;
+    }
+
+    /// Remove the alarm with id `id`, returning whether there was one.
+    pub fn cancel(&self, id: u32) -> bool {
+        let removed = {
+            let mut s = self.state.borrow_mut();
+            let before = s.alarms.len();
+            s.alarms.retain(|a| a.id != id);
+            s.alarms.len() != before
+        };
+        arm(&self.state);
+        removed
+    }
+
+    /// Remove every alarm.
+    pub fn clear(&self) {
+        self.state.borrow_mut().alarms.clear();
+        arm(&self.state);
+    }
+
+    /// Fire every alarm that is due now and re-arm the timer, e.g. when the page becomes visible again.
+    pub fn check(&self) {
+        fire_due(&self.state);
+    }
+
+    /// Get the time the next alarm goes off in epoch milliseconds, or `undefined` if there are none.
+    #[wasm_bindgen(getter)]
+    pub fn next(&self) -> Option<f64> {
+        self.state.borrow().alarms.iter().map(|a| a.at as f64).reduce(f64::min)
+    }
+
+    /// Get the number of alarms.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.state.borrow().alarms.len()
+    }
+}
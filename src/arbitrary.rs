@@ -0,0 +1,70 @@
+//! Proptest strategies for instants, durations and zones, for Rust crates that build on Unitime to
+//! property-test their own time logic against it. They shrink toward 1970 and toward zero.
+//!
+//! ```ignore
+//! use proptest::prelude::*;
+//! use unitime::arbitrary;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn my_format_round_trips(t in arbitrary::instant_between(0, 4_102_444_800_000), zone in arbitrary::zone()) {
+//!         let ms = t.get_epoch_mil().floor();
+//!         prop_assert_eq!(my_parse(&my_format(ms, zone), zone), ms);
+//!     }
+//! }
+//! ```
+
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::duration::UniDuration;
+use crate::{Unitime, MAX_NANOS, MIN_NANOS, NANOS_PER_MILLI};
+
+/// IANA zones that `zone` picks from: UTC, everyday zones on both sides of it and in both hemispheres,
+/// and zones known for tripping up time math, such as half-hour and 45-minute offsets, a half-hour
+/// daylight saving shift, negative daylight saving time, offsets past 12 hours and a skipped day.
+pub const ZONES: &[&str] = &[
+    "UTC",
+    "Europe/London",
+    "Europe/Berlin",
+    "America/New_York",
+    "America/Los_Angeles",
+    "America/Sao_Paulo",
+    "Asia/Tokyo",
+    "Australia/Sydney",
+    "Asia/Kolkata",
+    "Asia/Kathmandu",
+    "America/St_Johns",
+    "Australia/Lord_Howe",
+    "Europe/Dublin",
+    "Africa/Casablanca",
+    "Pacific/Kiritimati",
+    "Pacific/Apia",
+    "Pacific/Chatham",
+];
+
+/// Any instant a `Unitime` can hold, to the nanosecond.
+pub fn instant() -> impl Strategy<Value = Unitime> {
+    (MIN_NANOS..=MAX_NANOS).prop_map(Unitime::from_nanos)
+}
+
+/// Instants from epoch millisecond `from` to `to`, both included, to the nanosecond.
+pub fn instant_between(from: i64, to: i64) -> impl Strategy<Value = Unitime> {
+    (from as i128 * NANOS_PER_MILLI..=to as i128 * NANOS_PER_MILLI).prop_map(Unitime::from_nanos)
+}
+
+/// Any `UniDuration`, negative or positive, to the nanosecond.
+pub fn duration() -> impl Strategy<Value = UniDuration> {
+    any::<i64>().prop_map(UniDuration::from_nanos)
+}
+
+/// Durations from `min` to `max` milliseconds, both included, to the nanosecond.
+pub fn duration_between(min: i64, max: i64) -> impl Strategy<Value = UniDuration> {
+    let nanos = NANOS_PER_MILLI as i64;
+    (min.saturating_mul(nanos)..=max.saturating_mul(nanos)).prop_map(UniDuration::from_nanos)
+}
+
+/// One of `ZONES`.
+pub fn zone() -> impl Strategy<Value = &'static str> {
+    select(ZONES)
+}
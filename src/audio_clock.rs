@@ -0,0 +1,59 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Check the `AudioContext` readings, giving the base latency in seconds, 0 if omitted.
+fn check(current_time: f64, base_latency: Option<f64>) -> Result<f64, ValidationError> {
+    if !current_time.is_finite() || current_time < 0.0 {
+        return Err(ValidationError::new("audio context time must be a non-negative number of seconds"));
+    }
+    match base_latency.unwrap_or(0.0) {
+        latency if latency.is_finite() && latency >= 0.0 => Ok(latency),
+        _ => Err(ValidationError::new("audio context base latency must be a non-negative number of seconds")),
+    }
+}
+
+/// Get the `AudioContext` time in seconds at which to schedule a sound so that it is heard at `timeMs` on
+/// the `performance.now()` clock, the clock of `Metronome`. `audioCtxCurrentTime` and
+/// `audioCtxBaseLatency` are the context's `currentTime` and `baseLatency` read together with `now`
+/// on the same clock, or just before this call if `now` is omitted. The base latency is what the context
+/// adds between a scheduled time and the sound leaving it; without it every sound is that much late.
+/// Times already passed give context times before `currentTime`, which Web Audio plays at once.
+/// # Examples
+/// ```
+/// for (const t of metronome.schedule()) {
+///     click.start(toAudioContextTime(t, ctx.currentTime, ctx.baseLatency));
+/// }
+/// ```
+#[wasm_bindgen(js_name = "toAudioContextTime")]
+pub fn to_audio_context_time(
+    time_ms: f64,
+    audio_ctx_current_time: f64,
+    audio_ctx_base_latency: Option<f64>,
+    now: Option<f64>,
+) -> Result<f64, ValidationError> {
+    let latency = check(audio_ctx_current_time, audio_ctx_base_latency)?;
+    let now = now.unwrap_or_else(crate::monotonic_ms);
+    Ok(audio_ctx_current_time + (time_ms - now) / 1000.0 - latency)
+}
+
+/// Get the time on the `performance.now()` clock at which a sound scheduled at `audioContextTime`
+/// seconds is heard, the reverse of `toAudioContextTime`, with the same readings of the context.
+/// # Examples
+/// ```
+/// const heardAt = fromAudioContextTime(source.startTime, ctx.currentTime, ctx.baseLatency);
+/// requestAnimationFrame(function flash() {
+///     if (performance.now() < heardAt) requestAnimationFrame(flash); else light();
+/// });
+/// ```
+#[wasm_bindgen(js_name = "fromAudioContextTime")]
+pub fn from_audio_context_time(
+    audio_context_time: f64,
+    audio_ctx_current_time: f64,
+    audio_ctx_base_latency: Option<f64>,
+    now: Option<f64>,
+) -> Result<f64, ValidationError> {
+    let latency = check(audio_ctx_current_time, audio_ctx_base_latency)?;
+    let now = now.unwrap_or_else(crate::monotonic_ms);
+    Ok(now + (audio_context_time - audio_ctx_current_time + latency) * 1000.0)
+}
@@ -0,0 +1,80 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::UniDuration;
+use crate::validation::ValidationError;
+use crate::{Unitime, MAX_NANOS, MIN_NANOS};
+
+/// Bytes in the encoding of a `Unitime`, enough for its nanoseconds as a 96-bit integer.
+const INSTANT_BYTES: usize = 12;
+/// Bytes in the encoding of a `UniDuration`.
+const DURATION_BYTES: usize = 8;
+
+/// Write `value` as the `N` low bytes of a big-endian integer with the sign bit flipped, so that
+/// comparing encodings byte by byte, as IndexedDB does with binary keys, orders them like the values.
+fn encode<const N: usize>(value: i128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut out = bytes[16 - N..].to_vec();
+    out[0] ^= 0x80;
+    out
+}
+
+fn decode(bytes: &[u8], len: usize, what: &str) -> Result<i128, ValidationError> {
+    if bytes.len() != len {
+        return Err(ValidationError::new(format!("{what} bytes must be {len} long, not {}", bytes.len())));
+    }
+    let mut wide = [0u8; 16];
+    wide[16 - len..].copy_from_slice(bytes);
+    wide[16 - len] ^= 0x80;
+    // Shift the sign up to bit 127 and back down to extend it.
+    let shift = 8 * (16 - len) as u32;
+    Ok((i128::from_be_bytes(wide) << shift) >> shift)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Encode as 12 bytes holding the exact nanoseconds since 1970, for `postMessage`, IndexedDB keys and
+    /// binary WebSocket frames. Encodings sort byte by byte in time order.
+    /// # Examples
+    /// ```
+    /// socket.send(new Unitime().toBytes());
+    /// ```
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode::<INSTANT_BYTES>(self.nanos)
+    }
+
+    /// Creates a `Unitime` from bytes written by `toBytes`.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromBytes(new Uint8Array(event.data));
+    /// ```
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Unitime, ValidationError> {
+        let nanos = decode(bytes, INSTANT_BYTES, "Unitime")?;
+        if !(MIN_NANOS..=MAX_NANOS).contains(&nanos) {
+            return Err(ValidationError::new("encoded time is out of range"));
+        }
+        Ok(Unitime::from_nanos(nanos))
+    }
+}
+
+#[wasm_bindgen]
+impl UniDuration {
+    /// Encode as 8 bytes holding the exact nanoseconds. Encodings sort byte by byte in length order.
+    /// # Examples
+    /// ```
+    /// store.put({ id, took: duration.toBytes() });
+    /// ```
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode::<DURATION_BYTES>(self.nanos() as i128)
+    }
+
+    /// Creates a `UniDuration` from bytes written by `toBytes`.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<UniDuration, ValidationError> {
+        let nanos = decode(bytes, DURATION_BYTES, "UniDuration")?;
+        // Eight bytes always hold an `i64`.
+        Ok(UniDuration::from_nanos(nanos as i64))
+    }
+}
@@ -0,0 +1,317 @@
+//! The Chinese lunisolar calendar, computed from the moon and sun rather than read from a table, following
+//! Reingold and Dershowitz, *Calendrical Calculations*. New moons use the series from Meeus' *Astronomical
+//! Algorithms*, ch. 49, and solar terms the NOAA solar longitude, both to within a few minutes.
+
+use std::ops::RangeInclusive;
+
+use super::{Calendar, Fields};
+use crate::civil::{civil_from_days, days_from_civil, MS_PER_DAY, MS_PER_SEC};
+use crate::sun::{julian_century, SolarPosition};
+
+const SYNODIC_MONTH: f64 = 29.530588861;
+/// Julian ephemeris day of the first new moon of 2000, lunation 0 in Meeus' numbering.
+const NEW_MOON_0: f64 = 2_451_550.097_66;
+const JD_UNIX_EPOCH: f64 = 2_440_587.5;
+/// Years in which the series for new moons and solar terms stay close enough to place months on the
+/// right day. Beyond them the error in the difference between terrestrial and universal time alone
+/// grows to hours.
+const YEARS: RangeInclusive<i64> = -1000..=3000;
+
+const MONTH_NAMES: [&str; 12] = ["正月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "十一月", "十二月"];
+const LEAP_MONTH_NAMES: [&str; 12] = [
+    "闰正月", "闰二月", "闰三月", "闰四月", "闰五月", "闰六月", "闰七月", "闰八月", "闰九月", "闰十月", "闰十一月", "闰十二月",
+];
+const STEMS: [&str; 10] = ["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
+const BRANCHES: [&str; 12] = ["子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥"];
+pub(crate) const ANIMALS: [&str; 12] = [
+    "Rat", "Ox", "Tiger", "Rabbit", "Dragon", "Snake", "Horse", "Goat", "Monkey", "Rooster", "Dog", "Pig",
+];
+
+/// Name of day `day` of a month, e.g. 初一, 十五 or 廿三.
+fn day_name(day: u32) -> String {
+    const DIGITS: [&str; 10] = ["", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    match day {
+        1..=9 => format!("初{}", DIGITS[day as usize]),
+        10 => "初十".to_string(),
+        20 => "二十".to_string(),
+        30 => "三十".to_string(),
+        _ => format!("{}{}", ["十", "廿"][day as usize / 10 - 1], DIGITS[day as usize % 10]),
+    }
+}
+
+/// Offset of Chinese civil time from UTC in milliseconds: Beijing mean solar time before 1929, UTC+8 after.
+fn china_offset_ms(ms: f64) -> f64 {
+    if ms < (days_from_civil(1929, 1, 1) * MS_PER_DAY) as f64 {
+        27_940_000.0
+    } else {
+        28_800_000.0
+    }
+}
+
+/// Day, counted from 1970-01-01, that the moment `ms` falls on in China.
+fn china_day(ms: f64) -> i64 {
+    ((ms + china_offset_ms(ms)) / MS_PER_DAY as f64).floor() as i64
+}
+
+/// Moment of midnight starting `day` in China.
+fn midnight(day: i64) -> f64 {
+    let ms = (day * MS_PER_DAY) as f64;
+    ms - china_offset_ms(ms)
+}
+
+fn solar_longitude(ms: f64) -> f64 {
+    SolarPosition::at(julian_century(ms)).longitude
+}
+
+/// Rough difference between terrestrial and universal time in seconds.
+fn delta_t(year: f64) -> f64 {
+    if (1986.0..2050.0).contains(&year) {
+        let t = year - 2000.0;
+        62.92 + 0.32217 * t + 0.005589 * t * t
+    } else {
+        let u = (year - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    }
+}
+
+/// Moment of new moon number `k`, counted from the first one of 2000, in epoch milliseconds.
+fn new_moon(k: i64) -> f64 {
+    let k = k as f64;
+    let t = k / 1236.85;
+    let (t2, t3, t4) = (t * t, t * t * t, t * t * t * t);
+    let jde = NEW_MOON_0 + SYNODIC_MONTH * k + 0.00015437 * t2 - 0.000000150 * t3 + 0.00000000073 * t4;
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let m = (2.5534 + 29.10535670 * k - 0.0000014 * t2 - 0.00000011 * t3).to_radians();
+    let mp = (201.5643 + 385.81693528 * k + 0.0107582 * t2 + 0.00001238 * t3 - 0.000000058 * t4).to_radians();
+    let f = (160.7108 + 390.67050284 * k - 0.0016118 * t2 - 0.00000227 * t3 + 0.000000011 * t4).to_radians();
+    let omega = (124.7746 - 1.56375588 * k + 0.0020672 * t2 + 0.00000215 * t3).to_radians();
+    let lunar = -0.40720 * mp.sin() + 0.17241 * e * m.sin() + 0.01608 * (2.0 * mp).sin()
+        + 0.01039 * (2.0 * f).sin()
+        + 0.00739 * e * (mp - m).sin()
+        - 0.00514 * e * (mp + m).sin()
+        + 0.00208 * e * e * (2.0 * m).sin()
+        - 0.00111 * (mp - 2.0 * f).sin()
+        - 0.00057 * (mp + 2.0 * f).sin()
+        + 0.00056 * e * (2.0 * mp + m).sin()
+        - 0.00042 * (3.0 * mp).sin()
+        + 0.00042 * e * (m + 2.0 * f).sin()
+        + 0.00038 * e * (m - 2.0 * f).sin()
+        - 0.00024 * e * (2.0 * mp - m).sin()
+        - 0.00017 * omega.sin()
+        - 0.00007 * (mp + 2.0 * m).sin()
+        + 0.00004 * (2.0 * mp - 2.0 * f).sin()
+        + 0.00004 * (3.0 * m).sin()
+        + 0.00003 * (mp + m - 2.0 * f).sin()
+        + 0.00003 * (2.0 * mp + 2.0 * f).sin()
+        - 0.00003 * (mp + m + 2.0 * f).sin()
+        + 0.00003 * (mp - m + 2.0 * f).sin()
+        - 0.00002 * (mp - m - 2.0 * f).sin()
+        - 0.00002 * (3.0 * mp + m).sin()
+        + 0.00002 * (4.0 * mp).sin();
+    // Perturbations by the planets: (coefficient, argument at k = 0, change per lunation) in degrees.
+    const PLANETARY: [(f64, f64, f64); 14] = [
+        (0.000325, 299.77, 0.107408),
+        (0.000165, 251.88, 0.016321),
+        (0.000164, 251.83, 26.651886),
+        (0.000126, 349.42, 36.412478),
+        (0.000110, 84.66, 18.206239),
+        (0.000062, 141.74, 53.303771),
+        (0.000060, 207.14, 2.453732),
+        (0.000056, 154.84, 7.306860),
+        (0.000047, 34.52, 27.261239),
+        (0.000042, 207.19, 0.121824),
+        (0.000040, 291.34, 1.844379),
+        (0.000037, 161.72, 24.198154),
+        (0.000035, 239.56, 25.513099),
+        (0.000023, 331.55, 3.592518),
+    ];
+    let planetary: f64 = PLANETARY
+        .iter()
+        .enumerate()
+        .map(|(i, &(coeff, a0, rate))| {
+            let a = a0 + rate * k - if i == 0 { 0.009173 * t2 } else { 0.0 };
+            coeff * a.to_radians().sin()
+        })
+        .sum();
+    let ms = (jde + lunar + planetary - JD_UNIX_EPOCH) * MS_PER_DAY as f64;
+    ms - delta_t(2000.0 + k / 12.3685) * MS_PER_SEC as f64
+}
+
+/// Number of the new moon nearest to the moment `ms`, give or take one.
+fn lunation_near(ms: f64) -> i64 {
+    ((ms / MS_PER_DAY as f64 + JD_UNIX_EPOCH - NEW_MOON_0) / SYNODIC_MONTH).round() as i64
+}
+
+/// Day in China of the first new moon at or after midnight starting `day`.
+fn new_moon_on_or_after(day: i64) -> i64 {
+    let t = midnight(day);
+    let k = lunation_near(t) - 1;
+    let k = (k..k + 4).find(|&k| new_moon(k) >= t).unwrap_or(k + 3);
+    china_day(new_moon(k))
+}
+
+/// Day in China of the last new moon before midnight starting `day`.
+fn new_moon_before(day: i64) -> i64 {
+    let t = midnight(day);
+    let k = lunation_near(t) + 1;
+    let k = (k - 3..=k).rev().find(|&k| new_moon(k) < t).unwrap_or(k - 3);
+    china_day(new_moon(k))
+}
+
+/// Moment shortly before `ms` at which the sun's longitude was last `lambda` degrees.
+fn estimate_prior_solar_longitude(lambda: f64, ms: f64) -> f64 {
+    let rate = 365.2422 / 360.0 * MS_PER_DAY as f64;
+    let tau = ms - rate * (solar_longitude(ms) - lambda).rem_euclid(360.0);
+    let delta = (solar_longitude(tau) - lambda + 180.0).rem_euclid(360.0) - 180.0;
+    ms.min(tau - rate * delta)
+}
+
+/// Day in China of the last winter solstice on or before `day`.
+fn winter_solstice_on_or_before(day: i64) -> i64 {
+    let approx = estimate_prior_solar_longitude(270.0, midnight(day + 1));
+    let d = china_day(approx) - 1;
+    // The estimate is within a day of the solstice.
+    (d..d + 10).find(|&d| solar_longitude(midnight(d + 1)) > 270.0).unwrap_or(d + 9)
+}
+
+/// Number, from 1 to 12, of the last major solar term to begin before `day`.
+fn major_solar_term(day: i64) -> i64 {
+    (2 + (solar_longitude(midnight(day)) / 30.0).floor() as i64 - 1).rem_euclid(12) + 1
+}
+
+/// Whether the month starting on `month_start` has no major solar term in it.
+fn no_major_solar_term(month_start: i64) -> bool {
+    major_solar_term(month_start) == major_solar_term(new_moon_on_or_after(month_start + 1))
+}
+
+/// Whether some month from `earlier` to `month_start`, both month starts, lacks a major solar term.
+fn prior_leap_month(earlier: i64, mut month_start: i64) -> bool {
+    // The months checked are within one year, so there are at most 13.
+    for _ in 0..14 {
+        if month_start < earlier {
+            break;
+        }
+        if no_major_solar_term(month_start) {
+            return true;
+        }
+        month_start = new_moon_before(month_start);
+    }
+    false
+}
+
+/// Number of the month starting on `month_start`, from 1 to 12, and whether it is a leap month.
+fn month_label(month_start: i64) -> (u32, bool) {
+    let s1 = winter_solstice_on_or_before(month_start);
+    let s2 = winter_solstice_on_or_before(s1 + 370);
+    let m12 = new_moon_on_or_after(s1 + 1);
+    let next_m11 = new_moon_before(s2 + 1);
+    let leap_year = ((next_m11 - m12) as f64 / SYNODIC_MONTH).round() as i64 == 12;
+    let months = ((month_start - m12) as f64 / SYNODIC_MONTH).round() as i64;
+    let number = (months - (leap_year && prior_leap_month(m12, month_start)) as i64 - 1).rem_euclid(12) + 1;
+    let leap = leap_year && no_major_solar_term(month_start) && !prior_leap_month(m12, new_moon_before(month_start));
+    (number as u32, leap)
+}
+
+/// Chinese New Year in the solstice-to-solstice year holding `day`.
+fn new_year_in_sui(day: i64) -> i64 {
+    let s1 = winter_solstice_on_or_before(day);
+    let s2 = winter_solstice_on_or_before(s1 + 370);
+    let next_m11 = new_moon_before(s2 + 1);
+    let m12 = new_moon_on_or_after(s1 + 1);
+    let m13 = new_moon_on_or_after(m12 + 1);
+    let leap_year = ((next_m11 - m12) as f64 / SYNODIC_MONTH).round() as i64 == 12;
+    if leap_year && (no_major_solar_term(m12) || no_major_solar_term(m13)) {
+        new_moon_on_or_after(m13 + 1)
+    } else {
+        m13
+    }
+}
+
+fn new_year_on_or_before(day: i64) -> i64 {
+    let new_year = new_year_in_sui(day);
+    if day >= new_year {
+        new_year
+    } else {
+        new_year_in_sui(day - 180)
+    }
+}
+
+/// The Chinese lunisolar calendar. Years are numbered by the Gregorian year their New Year falls in and
+/// months in the order they occur, so a year with a leap month has 13; `month_label` gives the traditional
+/// number of each. Days are reckoned in Beijing time, and only the years -1000 to 3000 are worked out.
+pub(crate) struct Chinese;
+
+impl Chinese {
+    /// Start days of the months of `year`, followed by the next New Year.
+    fn month_starts(year: i64) -> Vec<i64> {
+        let new_year = new_year_on_or_before(days_from_civil(year, 7, 1));
+        let next = new_year_on_or_before(days_from_civil(year + 1, 7, 1));
+        let mut starts = vec![new_year];
+        // A year has 12 or 13 months.
+        while starts.len() < 15 {
+            let last = starts[starts.len() - 1];
+            if last >= next {
+                break;
+            }
+            starts.push(new_moon_on_or_after(last + 1));
+        }
+        starts
+    }
+
+    fn label(date: Fields) -> (u32, bool) {
+        let starts = Chinese::month_starts(date.year);
+        month_label(starts[date.month as usize - 1])
+    }
+}
+
+impl Calendar for Chinese {
+    fn years(&self) -> Option<RangeInclusive<i64>> {
+        Some(YEARS)
+    }
+
+    fn date_of(&self, days: i64) -> Fields {
+        let year = civil_from_days(new_year_on_or_before(days)).0;
+        let starts = Chinese::month_starts(year);
+        let month = starts.iter().rposition(|&start| start <= days).unwrap_or(0);
+        Fields { year, month: month as u32 + 1, day: (days - starts[month] + 1) as u32 }
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        let starts = Chinese::month_starts(date.year);
+        let index = (date.month as usize).checked_sub(1)?;
+        let (start, next) = (*starts.get(index)?, *starts.get(index + 1)?);
+        (date.day >= 1 && (date.day as i64) <= next - start).then(|| start + date.day as i64 - 1)
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        let (number, leap) = Chinese::label(date);
+        if leap {
+            LEAP_MONTH_NAMES[number as usize - 1]
+        } else {
+            MONTH_NAMES[number as usize - 1]
+        }
+    }
+
+    fn month_code(&self, date: Fields) -> String {
+        let (number, leap) = Chinese::label(date);
+        format!("M{number:02}{}", if leap { "L" } else { "" })
+    }
+
+    fn is_leap_month(&self, date: Fields) -> bool {
+        Chinese::label(date).1
+    }
+
+    fn sexagenary_year(&self, date: Fields) -> Option<u32> {
+        Some((date.year - 4).rem_euclid(60) as u32)
+    }
+
+    fn format(&self, date: Fields) -> String {
+        let cycle = self.sexagenary_year(date).unwrap_or(0);
+        format!("{}年{}{}", stem_branch(cycle), self.month_name(date), day_name(date.day))
+    }
+}
+
+/// The stem and branch characters of position `cycle`, from 0 to 59, in the sexagenary cycle.
+pub(crate) fn stem_branch(cycle: u32) -> String {
+    format!("{}{}", STEMS[cycle as usize % 10], BRANCHES[cycle as usize % 12])
+}
@@ -0,0 +1,26 @@
+use super::{Calendar, Fields};
+use crate::civil::{civil_from_days, days_from_civil, days_in_month};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+    "December",
+];
+
+/// The proleptic Gregorian calendar, so other calendars can be converted to and from it the same way.
+pub(crate) struct Gregorian;
+
+impl Calendar for Gregorian {
+    fn date_of(&self, days: i64) -> Fields {
+        let (year, month, day) = civil_from_days(days);
+        Fields { year, month, day }
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        let valid = (1..=12).contains(&date.month) && date.day >= 1 && date.day <= days_in_month(date.year, date.month);
+        valid.then(|| days_from_civil(date.year, date.month, date.day))
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        MONTH_NAMES[date.month as usize - 1]
+    }
+}
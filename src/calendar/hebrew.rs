@@ -0,0 +1,122 @@
+use super::{Calendar, Fields};
+
+/// Days after 1970-01-01 of 1 Tishri AM 1.
+const EPOCH: i64 = -2_092_590;
+/// An hour has 1080 parts (halakim), so a day has 25920.
+const PARTS_PER_DAY: i64 = 25_920;
+
+/// The arithmetic Hebrew calendar. Months are numbered from Tishri, so in a leap year the 6th month is
+/// Adar I, the inserted one, and the 7th is Adar II; in a common year the 6th is Adar and Nisan is the 7th.
+pub(crate) struct Hebrew;
+
+impl Hebrew {
+    fn is_leap_year(year: i64) -> bool {
+        (7 * year + 1).rem_euclid(19) < 7
+    }
+
+    /// Days from the epoch to the molad of Tishri, moved off Sunday, Wednesday and Friday.
+    fn elapsed_days(year: i64) -> i64 {
+        let months = (235 * year - 234).div_euclid(19);
+        let parts = 12_084 + 13_753 * months;
+        let days = 29 * months + parts.div_euclid(PARTS_PER_DAY);
+        if (3 * (days + 1)).rem_euclid(7) < 3 {
+            days + 1
+        } else {
+            days
+        }
+    }
+
+    /// Days after 1970-01-01 of 1 Tishri of `year`, after the postponements that keep year lengths legal.
+    fn new_year(year: i64) -> i64 {
+        let (before, this, after) = (
+            Hebrew::elapsed_days(year - 1),
+            Hebrew::elapsed_days(year),
+            Hebrew::elapsed_days(year + 1),
+        );
+        let correction = if after - this == 356 {
+            2
+        } else if this - before == 382 {
+            1
+        } else {
+            0
+        };
+        EPOCH + this + correction
+    }
+
+    /// Lengths of the months of `year` in order from Tishri.
+    fn month_lengths(year: i64) -> Vec<u32> {
+        let year_length = Hebrew::new_year(year + 1) - Hebrew::new_year(year);
+        // Heshvan gains a day in "complete" years of 355 or 385 days, Kislev loses one in "deficient" years of 353 or 383.
+        let heshvan = if year_length % 10 == 5 { 30 } else { 29 };
+        let kislev = if year_length % 10 == 3 { 29 } else { 30 };
+        let mut lengths = vec![30, heshvan, kislev, 29, 30];
+        if Hebrew::is_leap_year(year) {
+            lengths.extend([30, 29]);
+        } else {
+            lengths.push(29);
+        }
+        lengths.extend([30, 29, 30, 29, 30, 29]);
+        lengths
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "Tishri", "Heshvan", "Kislev", "Tevet", "Shevat", "Adar", "Nisan", "Iyar", "Sivan", "Tammuz", "Av", "Elul",
+];
+
+impl Calendar for Hebrew {
+    fn date_of(&self, days: i64) -> Fields {
+        let mut year = ((days - EPOCH) as f64 / 365.2468).floor() as i64 + 1;
+        while Hebrew::new_year(year) > days {
+            year -= 1;
+        }
+        while Hebrew::new_year(year + 1) <= days {
+            year += 1;
+        }
+        let mut rest = (days - Hebrew::new_year(year)) as u32;
+        let mut month = 1;
+        for length in Hebrew::month_lengths(year) {
+            if rest < length {
+                break;
+            }
+            rest -= length;
+            month += 1;
+        }
+        Fields { year, month, day: rest + 1 }
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        let lengths = Hebrew::month_lengths(date.year);
+        let length = *lengths.get((date.month as usize).checked_sub(1)?)?;
+        if date.day == 0 || date.day > length {
+            return None;
+        }
+        let before: u32 = lengths[..date.month as usize - 1].iter().sum();
+        Some(Hebrew::new_year(date.year) + before as i64 + date.day as i64 - 1)
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        match (Hebrew::is_leap_year(date.year), date.month) {
+            (true, 6) => "Adar I",
+            (true, 7) => "Adar II",
+            (true, m) if m > 7 => MONTH_NAMES[m as usize - 2],
+            (_, m) => MONTH_NAMES[m as usize - 1],
+        }
+    }
+
+    fn month_code(&self, date: Fields) -> String {
+        match (Hebrew::is_leap_year(date.year), date.month) {
+            (true, 6) => "M05L".to_string(),
+            (true, m) if m > 6 => format!("M{:02}", m - 1),
+            (_, m) => format!("M{m:02}"),
+        }
+    }
+
+    fn is_leap_month(&self, date: Fields) -> bool {
+        Hebrew::is_leap_year(date.year) && date.month == 6
+    }
+
+    fn format(&self, date: Fields) -> String {
+        format!("{} {} {} AM", date.day, self.month_name(date), date.year)
+    }
+}
@@ -0,0 +1,72 @@
+use super::{Calendar, Fields};
+
+const MONTH_NAMES: [&str; 12] = [
+    "Muharram",
+    "Safar",
+    "Rabi' al-awwal",
+    "Rabi' al-thani",
+    "Jumada al-awwal",
+    "Jumada al-thani",
+    "Rajab",
+    "Sha'ban",
+    "Ramadan",
+    "Shawwal",
+    "Dhu al-Qa'dah",
+    "Dhu al-Hijjah",
+];
+
+/// The tabular Islamic calendar: 30-year cycles of 354- and 355-day years, with months alternating
+/// between 30 and 29 days. Dates can differ by a day or two from calendars based on moon sighting
+/// or on astronomical tables such as Umm al-Qura.
+pub(crate) struct Islamic {
+    /// Days after 1970-01-01 of 1 Muharram 1 AH.
+    epoch: i64,
+}
+
+impl Islamic {
+    pub const CIVIL: Islamic = Islamic { epoch: -492_148 };
+    pub const ASTRONOMICAL: Islamic = Islamic { epoch: -492_149 };
+
+    fn is_leap_year(year: i64) -> bool {
+        (14 + 11 * year).rem_euclid(30) < 11
+    }
+
+    fn month_length(year: i64, month: u32) -> u32 {
+        if month % 2 == 1 || (month == 12 && Islamic::is_leap_year(year)) {
+            30
+        } else {
+            29
+        }
+    }
+
+    fn start_of(&self, year: i64, month: u32) -> i64 {
+        let months = month as i64 - 1;
+        self.epoch + (59 * months + 1) / 2 + (year - 1) * 354 + (3 + 11 * year).div_euclid(30)
+    }
+}
+
+impl Calendar for Islamic {
+    fn date_of(&self, days: i64) -> Fields {
+        let year = (30 * (days - self.epoch) + 10_646).div_euclid(10_631);
+        let into_year = days - self.start_of(year, 1);
+        // Months start every 29.5 days, rounded up, so this finds the last month starting on or before `days`.
+        let month = ((2 * into_year).div_euclid(59) + 1).clamp(1, 12) as u32;
+        let day = (days - self.start_of(year, month) + 1) as u32;
+        Fields { year, month, day }
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        let valid = (1..=12).contains(&date.month)
+            && date.day >= 1
+            && date.day <= Islamic::month_length(date.year, date.month);
+        valid.then(|| self.start_of(date.year, date.month) + date.day as i64 - 1)
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        MONTH_NAMES[date.month as usize - 1]
+    }
+
+    fn format(&self, date: Fields) -> String {
+        format!("{} {} {} AH", date.day, self.month_name(date), date.year)
+    }
+}
@@ -0,0 +1,159 @@
+use wasm_bindgen::prelude::*;
+
+use super::{gregorian::Gregorian, Calendar, Fields, NUMBERED_MONTH_NAMES};
+use crate::civil::{self, days_from_civil, MS_PER_DAY};
+use crate::parse::Cursor;
+use crate::validation::{char_offset, check_date, ValidationError};
+use crate::Unitime;
+
+/// A modern Japanese era: its name in kanji, its one-letter abbreviation and the Gregorian date it began.
+struct Era {
+    name: &'static str,
+    letter: u8,
+    start: (i64, u32, u32),
+}
+
+const ERAS: [Era; 5] = [
+    Era { name: "明治", letter: b'M', start: (1868, 10, 23) },
+    Era { name: "大正", letter: b'T', start: (1912, 7, 30) },
+    Era { name: "昭和", letter: b'S', start: (1926, 12, 25) },
+    Era { name: "平成", letter: b'H', start: (1989, 1, 8) },
+    Era { name: "令和", letter: b'R', start: (2019, 5, 1) },
+];
+
+impl Era {
+    /// The era named `name` in kanji or by its letter.
+    fn named(name: &str) -> Result<&'static Era, ValidationError> {
+        let era = ERAS.iter().find(|era| era.name == name || name.as_bytes() == [era.letter]);
+        era.ok_or_else(|| ValidationError::new(format!("no Japanese era {name}")))
+    }
+
+    fn start_days(&self) -> i64 {
+        let (year, month, day) = self.start;
+        days_from_civil(year, month, day)
+    }
+
+    /// Check that day `days` after 1970-01-01 is not before the era began. Dates past the end of an
+    /// era, as printed before a change of era, are accepted.
+    fn check_start(&self, days: i64) -> Result<(), ValidationError> {
+        if days < self.start_days() {
+            let (y, m, d) = self.start;
+            return Err(ValidationError::new(format!("the {} era began on {y}-{m:02}-{d:02}", self.name)));
+        }
+        Ok(())
+    }
+}
+
+/// The Gregorian calendar with years counted in Japanese eras from Meiji on. Earlier dates keep their
+/// Gregorian year and have no era, and no conversion to the pre-1873 lunisolar calendar is made.
+pub(crate) struct Japanese;
+
+impl Calendar for Japanese {
+    fn date_of(&self, days: i64) -> Fields {
+        Gregorian.date_of(days)
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        Gregorian.days_of(date)
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        NUMBERED_MONTH_NAMES[date.month as usize - 1]
+    }
+
+    fn era(&self, date: Fields) -> Option<(&'static str, i64)> {
+        let days = days_from_civil(date.year, date.month, date.day);
+        let era = ERAS.iter().rev().find(|era| era.start_days() <= days)?;
+        Some((era.name, date.year - era.start.0 + 1))
+    }
+
+    fn year_from_era(&self, era: Option<&str>, year: i64) -> Result<i64, ValidationError> {
+        let Some(name) = era else {
+            return Ok(year);
+        };
+        let era = Era::named(name)?;
+        if year < 1 {
+            return Err(ValidationError::range("year", year, 1, 999));
+        }
+        Ok(era.start.0 + year - 1)
+    }
+
+    fn check_era_start(&self, era: Option<&str>, days: i64) -> Result<(), ValidationError> {
+        era.map_or(Ok(()), |name| Era::named(name)?.check_start(days))
+    }
+
+    fn format(&self, date: Fields) -> String {
+        match self.era(date) {
+            Some((name, 1)) => format!("{name}元年{}月{}日", date.month, date.day),
+            Some((name, year)) => format!("{name}{year}年{}月{}日", date.month, date.day),
+            None => format!("{}年{}月{}日", date.year, date.month, date.day),
+        }
+    }
+}
+
+/// Read the era, era year, month and day of `令和6年5月1日` or `R6.5.1` style dates.
+fn era_date(c: &mut Cursor) -> Option<(&'static Era, u32, u32, u32)> {
+    if let Some(era) = ERAS.iter().find(|era| c.eat_str(era.name)) {
+        let year = if c.eat_str("元") { 1 } else { c.digits(1, 3)?.0 };
+        c.eat_str("年").then_some(())?;
+        let month = c.digits(1, 2)?.0;
+        c.eat_str("月").then_some(())?;
+        let day = c.digits(1, 2)?.0;
+        c.eat_str("日").then_some(())?;
+        Some((era, year, month, day))
+    } else {
+        let letter = c.peek()?;
+        let era = ERAS.iter().find(|era| era.letter == letter.to_ascii_uppercase())?;
+        c.eat(letter);
+        let year = c.digits(1, 3)?.0;
+        let sep = c.peek().filter(|b| matches!(b, b'.' | b'/' | b'-'))?;
+        c.eat(sep);
+        let month = c.digits(1, 2)?.0;
+        c.eat(sep).then_some(())?;
+        let day = c.digits(1, 2)?.0;
+        Some((era, year, month, day))
+    }
+}
+
+/// Parse `令和6年5月1日` or `R6.5.1` style dates into days after 1970-01-01.
+fn parse_japanese(s: &str) -> Result<i64, ValidationError> {
+    // Forms are often filled in with full-width digits, which keeps character offsets the same.
+    let lead = s.chars().count() - s.trim_start().chars().count();
+    let s: String = s
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+            c => c,
+        })
+        .collect();
+    let mut c = Cursor::new(&s);
+    let syntax = |c: &Cursor| ValidationError::syntax("invalid japanese era date", lead + char_offset(&s, c.pos()));
+    let (era, year, month, day) = era_date(&mut c).ok_or_else(|| syntax(&c))?;
+    if !c.is_end() {
+        return Err(syntax(&c));
+    }
+    if year == 0 {
+        return Err(ValidationError::range("year", 0, 1, 999));
+    }
+    let year = era.start.0 + year as i64 - 1;
+    check_date(year, month, day)?;
+    let days = days_from_civil(year, month, day);
+    era.check_start(days)?;
+    Ok(days)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Creates a new `Unitime` at midnight starting a Japanese era date written like `令和6年5月1日`,
+    /// `令和元年5月1日` or `R6.5.1`, in local time unless `utc` is `true`. Full-width digits are accepted.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromJapaneseDate("令和6年5月1日");
+    /// ```
+    #[wasm_bindgen(js_name = "fromJapaneseDate")]
+    pub fn from_japanese_date(s: &str, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        let days = parse_japanese(s)?;
+        Ok(Unitime::from_epoch_ms(civil::wall_to_instant(days * MS_PER_DAY, utc.unwrap_or(false))))
+    }
+}
@@ -0,0 +1,283 @@
+//! Non-Gregorian calendars. Each calendar converts between a day count since 1970-01-01 and its own
+//! year, month and day, so adding one only takes a new [`Calendar`] implementation and a `CalendarKind`.
+//!
+//! Only the tabular Islamic calendars are here. Umm al-Qura months follow the sighting tables published
+//! by Saudi Arabia rather than a rule, so they are deliberately left out; `Intl.DateTimeFormat` with
+//! `calendar: "islamic-umalqura"` gives them where the host has the tables.
+
+use std::ops::RangeInclusive;
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, MS_PER_DAY};
+use crate::validation::{check_year, ValidationError};
+use crate::Unitime;
+
+mod chinese;
+mod gregorian;
+mod hebrew;
+mod islamic;
+mod japanese;
+mod offset;
+
+/// Calendar systems that `Unitime.toCalendar` and `Unitime.fromCalendar` understand, named as in `Intl`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarKind {
+    /// Thai solar calendar, with years of the Buddhist Era.
+    Buddhist = "buddhist",
+    /// Chinese lunisolar calendar, worked out for the years -1000 to 3000.
+    Chinese = "chinese",
+    Gregorian = "gregorian",
+    Hebrew = "hebrew",
+    /// Tabular Islamic calendar counted from Friday 16 July 622 (Julian).
+    IslamicCivil = "islamic-civil",
+    /// Tabular Islamic calendar counted from Thursday 15 July 622 (Julian).
+    IslamicTbla = "islamic-tbla",
+    /// Gregorian calendar with years counted in Japanese eras.
+    Japanese = "japanese",
+    /// Gregorian calendar with years of the Republic of China (Minguo).
+    Roc = "roc",
+}
+
+/// Month names of the East Asian calendars that number their months.
+const NUMBERED_MONTH_NAMES: [&str; 12] = ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"];
+
+/// Year, month and day on some calendar. Months are numbered from 1 in the order they occur in the year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Fields {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+pub(crate) trait Calendar {
+    /// Date on this calendar of the day `days` after 1970-01-01.
+    fn date_of(&self, days: i64) -> Fields;
+
+    /// Days after 1970-01-01 of `date`, or `None` if no such date exists on this calendar.
+    fn days_of(&self, date: Fields) -> Option<i64>;
+
+    /// Years this calendar can work out, if it cannot work out every year `Unitime` holds.
+    fn years(&self) -> Option<RangeInclusive<i64>> {
+        None
+    }
+
+    /// Name of the month of `date`.
+    fn month_name(&self, date: Fields) -> &'static str;
+
+    /// Era of `date` and the year within it, for calendars that count years in eras.
+    fn era(&self, _date: Fields) -> Option<(&'static str, i64)> {
+        None
+    }
+
+    /// Year without eras of `year` counted in `era`, or in the calendar's usual count if `era` is
+    /// omitted, the other way round from `era`.
+    fn year_from_era(&self, era: Option<&str>, year: i64) -> Result<i64, ValidationError> {
+        match era {
+            None => Ok(year),
+            Some(era) => Err(ValidationError::new(format!("no era {era} on this calendar"))),
+        }
+    }
+
+    /// Check that day `days` after 1970-01-01, given with its year counted in `era`, falls after that
+    /// era began.
+    fn check_era_start(&self, _era: Option<&str>, _days: i64) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Code identifying the month of `date` across years, as in `Temporal`: "M01" to "M12", with an "L"
+    /// suffix for a leap month that follows the month of that number.
+    fn month_code(&self, date: Fields) -> String {
+        format!("M{:02}", date.month)
+    }
+
+    /// Whether the month of `date` is an inserted leap month.
+    fn is_leap_month(&self, _date: Fields) -> bool {
+        false
+    }
+
+    /// Position of the year of `date` in the 60-year cycle of stems and branches, from 0 for 甲子.
+    fn sexagenary_year(&self, _date: Fields) -> Option<u32> {
+        None
+    }
+
+    /// Human-readable form of `date`, e.g. "9 Ramadan 1445 AH".
+    fn format(&self, date: Fields) -> String {
+        format!("{} {} {}", date.day, self.month_name(date), date.year)
+    }
+}
+
+fn calendar(kind: CalendarKind) -> Result<&'static dyn Calendar, ValidationError> {
+    Ok(match kind {
+        CalendarKind::Buddhist => &offset::Buddhist,
+        CalendarKind::Chinese => &chinese::Chinese,
+        CalendarKind::Gregorian => &gregorian::Gregorian,
+        CalendarKind::Hebrew => &hebrew::Hebrew,
+        CalendarKind::IslamicCivil => &islamic::Islamic::CIVIL,
+        CalendarKind::IslamicTbla => &islamic::Islamic::ASTRONOMICAL,
+        CalendarKind::Japanese => &japanese::Japanese,
+        CalendarKind::Roc => &offset::Roc,
+        _ => return Err(ValidationError::new("unknown calendar")),
+    })
+}
+
+/// Check that `year` is one that `calendar` can work out.
+fn check_calendar_year(calendar: &dyn Calendar, year: i64) -> Result<(), ValidationError> {
+    match calendar.years() {
+        Some(years) if !years.contains(&year) => {
+            Err(ValidationError::range("year", year, *years.start(), *years.end()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A date on a non-Gregorian calendar.
+#[wasm_bindgen]
+pub struct CalendarDate {
+    kind: CalendarKind,
+    fields: Fields,
+}
+
+#[wasm_bindgen]
+impl CalendarDate {
+    /// Get the calendar the date is on.
+    #[wasm_bindgen(getter)]
+    pub fn calendar(&self) -> CalendarKind {
+        self.kind
+    }
+
+    /// Get the era, e.g. "令和" on the Japanese calendar, or `undefined` if the calendar or date has none.
+    #[wasm_bindgen(getter)]
+    pub fn era(&self) -> Option<String> {
+        self.imp().era(self.fields).map(|(name, _)| name.to_string())
+    }
+
+    /// Get the year, counted within the era if there is one, as `Unitime.fromCalendar` takes it.
+    #[wasm_bindgen(getter)]
+    pub fn year(&self) -> i32 {
+        self.imp().era(self.fields).map_or(self.fields.year, |(_, year)| year) as i32
+    }
+
+    /// Get the month, counting from 1 in the order the months occur in the year, leap months included.
+    #[wasm_bindgen(getter)]
+    pub fn month(&self) -> u32 {
+        self.fields.month
+    }
+
+    /// Get a code naming the month the same way every year, e.g. "M04L" for the Chinese leap fourth month.
+    #[wasm_bindgen(getter, js_name = "monthCode")]
+    pub fn month_code(&self) -> String {
+        self.imp().month_code(self.fields)
+    }
+
+    /// Get the day of the month, starting at 1.
+    #[wasm_bindgen(getter)]
+    pub fn day(&self) -> u32 {
+        self.fields.day
+    }
+
+    /// Whether the month is one inserted in a leap year, such as Adar I on the Hebrew calendar.
+    #[wasm_bindgen(getter, js_name = "isLeapMonth")]
+    pub fn is_leap_month(&self) -> bool {
+        self.imp().is_leap_month(self.fields)
+    }
+
+    /// Get the stem-branch name of the year, e.g. "甲辰", or `undefined` if the calendar has no such cycle.
+    #[wasm_bindgen(getter, js_name = "cyclicYear")]
+    pub fn cyclic_year(&self) -> Option<String> {
+        self.imp().sexagenary_year(self.fields).map(chinese::stem_branch)
+    }
+
+    /// Get the zodiac animal of the year, e.g. "Dragon", or `undefined` if the calendar has no such cycle.
+    #[wasm_bindgen(getter)]
+    pub fn zodiac(&self) -> Option<String> {
+        self.imp()
+            .sexagenary_year(self.fields)
+            .map(|cycle| chinese::ANIMALS[cycle as usize % 12].to_string())
+    }
+
+    /// Get the name of the month.
+    #[wasm_bindgen(getter, js_name = "monthName")]
+    pub fn month_name(&self) -> String {
+        self.imp().month_name(self.fields).to_string()
+    }
+
+    /// Get the date in the calendar's usual written form.
+    /// # Examples
+    /// ```
+    /// new Unitime().toCalendar("islamic-civil").toString(); // e.g. "9 Ramadan 1445 AH"
+    /// ```
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        self.imp().format(self.fields)
+    }
+}
+
+impl CalendarDate {
+    /// The kind was checked when the date was made, so the lookup cannot fail.
+    fn imp(&self) -> &'static dyn Calendar {
+        calendar(self.kind).unwrap_or(&gregorian::Gregorian)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the date the stored time falls on in the `kind` calendar, in local time unless `utc` is `true`.
+    /// Days run from midnight to midnight, even on calendars whose day traditionally starts at sunset.
+    /// # Examples
+    /// ```
+    /// const d = new Unitime().toCalendar("islamic-civil");
+    /// console.log(d.day, d.monthName, d.year);
+    /// ```
+    #[wasm_bindgen(js_name = "toCalendar")]
+    pub fn to_calendar(&self, kind: CalendarKind, utc: Option<bool>) -> Result<CalendarDate, ValidationError> {
+        let days = self.wall_ms(utc.unwrap_or(false)).div_euclid(MS_PER_DAY);
+        let imp = calendar(kind)?;
+        if let Some(years) = imp.years() {
+            // The Gregorian year is checked first, as far-off dates can be slow to work out; a calendar
+            // year can end in the next Gregorian year.
+            let year = civil::civil_from_days(days).0;
+            if !(*years.start()..=*years.end() + 1).contains(&year) {
+                return Err(ValidationError::range("year", year, *years.start(), *years.end()));
+            }
+        }
+        let fields = imp.date_of(days);
+        check_calendar_year(imp, fields.year)?;
+        Ok(CalendarDate { kind, fields })
+    }
+
+    /// Creates a new `Unitime` at midnight starting `day` of `month` in `year` on the `kind` calendar,
+    /// in local time unless `utc` is `true`. The year is counted as `CalendarDate.year` gives it, in
+    /// `era` if one is given: Buddhist Era years on the Buddhist calendar, and Minguo years on the ROC
+    /// calendar, with era `"民國前"` for years before 1912. On the Japanese calendar the year is counted
+    /// in `era`, such as `"令和"` or `"R"`, and is Gregorian without one, as for dates before Meiji.
+    /// # Examples
+    /// ```
+    /// const eid = Unitime.fromCalendar("islamic-civil", 1445, 10, 1);
+    /// Unitime.fromCalendar("buddhist", 2567, 6, 1); // 2024-06-01
+    /// Unitime.fromCalendar("japanese", 6, 6, 1, false, "令和"); // 2024-06-01
+    /// ```
+    #[wasm_bindgen(js_name = "fromCalendar")]
+    pub fn from_calendar(
+        kind: CalendarKind,
+        year: i32,
+        month: u32,
+        day: u32,
+        utc: Option<bool>,
+        era: Option<String>,
+    ) -> Result<Unitime, ValidationError> {
+        check_year(year as i64)?;
+        let imp = calendar(kind)?;
+        let fields = Fields { year: imp.year_from_era(era.as_deref(), year as i64)?, month, day };
+        check_calendar_year(imp, fields.year)?;
+        let days = imp
+            .days_of(fields)
+            .ok_or_else(|| ValidationError::new("no such date on this calendar"))?;
+        imp.check_era_start(era.as_deref(), days)?;
+        // Calendars counting from an earlier epoch reach a little further than the Gregorian year limit.
+        let wall = check_year(civil::civil_from_days(days).0).ok().and_then(|()| days.checked_mul(MS_PER_DAY));
+        let wall = wall.ok_or_else(|| ValidationError::range("year", year as i64, -civil::MAX_YEAR, civil::MAX_YEAR))?;
+        Ok(Unitime::from_epoch_ms(civil::wall_to_instant(wall, utc.unwrap_or(false))))
+    }
+}
@@ -0,0 +1,90 @@
+//! Calendars that are the Gregorian calendar with years counted from another epoch.
+
+use super::{gregorian::Gregorian, Calendar, Fields, NUMBERED_MONTH_NAMES};
+use crate::validation::ValidationError;
+
+fn unknown_era(era: &str) -> ValidationError {
+    ValidationError::new(format!("no era {era} on this calendar"))
+}
+
+const THAI_MONTH_NAMES: [&str; 12] = [
+    "มกราคม", "กุมภาพันธ์", "มีนาคม", "เมษายน", "พฤษภาคม", "มิถุนายน", "กรกฎาคม", "สิงหาคม", "กันยายน", "ตุลาคม", "พฤศจิกายน",
+    "ธันวาคม",
+];
+
+/// The Thai solar calendar, counting years of the Buddhist Era, 543 years ahead of the Gregorian.
+pub(crate) struct Buddhist;
+
+impl Calendar for Buddhist {
+    fn date_of(&self, days: i64) -> Fields {
+        Gregorian.date_of(days)
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        Gregorian.days_of(date)
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        THAI_MONTH_NAMES[date.month as usize - 1]
+    }
+
+    fn era(&self, date: Fields) -> Option<(&'static str, i64)> {
+        Some(("พ.ศ.", date.year + 543))
+    }
+
+    fn year_from_era(&self, era: Option<&str>, year: i64) -> Result<i64, ValidationError> {
+        match era {
+            None | Some("พ.ศ.") => Ok(year - 543),
+            Some(era) => Err(unknown_era(era)),
+        }
+    }
+
+    fn format(&self, date: Fields) -> String {
+        format!("{} {} พ.ศ. {}", date.day, self.month_name(date), date.year + 543)
+    }
+}
+
+/// The Minguo calendar used in Taiwan, counting years from 1912 as the first year of the Republic of China.
+/// Earlier years count back from it, 1911 being the first year before the Republic.
+pub(crate) struct Roc;
+
+impl Roc {
+    fn era_year(year: i64) -> (&'static str, i64) {
+        if year > 1911 {
+            ("民國", year - 1911)
+        } else {
+            ("民國前", 1912 - year)
+        }
+    }
+}
+
+impl Calendar for Roc {
+    fn date_of(&self, days: i64) -> Fields {
+        Gregorian.date_of(days)
+    }
+
+    fn days_of(&self, date: Fields) -> Option<i64> {
+        Gregorian.days_of(date)
+    }
+
+    fn month_name(&self, date: Fields) -> &'static str {
+        NUMBERED_MONTH_NAMES[date.month as usize - 1]
+    }
+
+    fn era(&self, date: Fields) -> Option<(&'static str, i64)> {
+        Some(Roc::era_year(date.year))
+    }
+
+    fn year_from_era(&self, era: Option<&str>, year: i64) -> Result<i64, ValidationError> {
+        match era {
+            None | Some("民國") => Ok(year + 1911),
+            Some("民國前") => Ok(1912 - year),
+            Some(era) => Err(unknown_era(era)),
+        }
+    }
+
+    fn format(&self, date: Fields) -> String {
+        let (era, year) = Roc::era_year(date.year);
+        format!("{era}{year}年{}月{}日", date.month, date.day)
+    }
+}
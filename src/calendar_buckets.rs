@@ -0,0 +1,158 @@
+use wasm_bindgen::prelude::*;
+
+use crate::calendar_days::zoned_midnight;
+use crate::civil::{civil_from_days, days_from_civil, MAX_YEAR, MS_PER_DAY};
+use crate::series::{aggregate_buckets, count_buckets, Aggregation, TimeSeriesIndex, MAX_BUCKETS};
+use crate::tz::zoned_wall;
+use crate::validation::ValidationError;
+use crate::week::WeekRule;
+
+/// Latest timestamp either side of 1970 that calendar buckets take, in epoch milliseconds, short of
+/// `MAX_YEAR` so that the bucket after the last one still has a start.
+const MAX_MS: i64 = MAX_YEAR * 365 * MS_PER_DAY;
+
+/// The calendar span each bucket of `TimeSeriesIndex.calendarBucketStarts` covers.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarBucket {
+    Day = "day",
+    /// Weeks starting on the day the week rule gives.
+    Week = "week",
+    Month = "month",
+    Year = "year",
+}
+
+impl CalendarBucket {
+    /// The first day of the bucket holding day `days` and the first day of the bucket after it, in days
+    /// since 1970-01-01, with weeks starting as `rule` says.
+    fn span(self, days: i64, rule: WeekRule) -> Result<(i64, i64), ValidationError> {
+        let (year, month, _) = civil_from_days(days);
+        match self {
+            CalendarBucket::Day => Ok((days, days + 1)),
+            CalendarBucket::Week => rule.week_start(days).map(|start| (start, start + 7)),
+            CalendarBucket::Month => {
+                let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                Ok((days_from_civil(year, month, 1), days_from_civil(next_year, next_month, 1)))
+            }
+            CalendarBucket::Year => Ok((days_from_civil(year, 1, 1), days_from_civil(year + 1, 1, 1))),
+            _ => Err(ValidationError::new("unknown calendar bucket, expected day, week, month or year")),
+        }
+    }
+
+    /// The fewest days a bucket covers.
+    fn min_days(self) -> i64 {
+        match self {
+            CalendarBucket::Week => 7,
+            CalendarBucket::Month => 28,
+            CalendarBucket::Year => 365,
+            _ => 1,
+        }
+    }
+}
+
+/// The day of the timestamp `t` on the wall clock of `zone`, or of the host's zone if `None`, in days
+/// since 1970-01-01.
+fn wall_day(t: f64, zone: Option<&str>) -> Result<i64, ValidationError> {
+    if !t.is_finite() || t.abs() > MAX_MS as f64 {
+        return Err(ValidationError::range("time", t as i64, -MAX_MS, MAX_MS));
+    }
+    Ok(zoned_wall(t.floor() as i64, zone)?.div_euclid(MS_PER_DAY))
+}
+
+/// Index of the bucket holding `t` among the buckets starting at `bounds`.
+fn bucket_of(bounds: &[f64], t: f64) -> usize {
+    bounds.partition_point(|&start| start <= t).saturating_sub(1)
+}
+
+impl TimeSeriesIndex {
+    /// Get the start of every calendar bucket spanned by the stored timestamps, followed by the end of
+    /// the last one, in epoch milliseconds, on the wall clock of `zone` or of the host's zone if `None`.
+    fn calendar_bounds(
+        &self,
+        unit: CalendarBucket,
+        zone: Option<&str>,
+        rule: Option<WeekRule>,
+    ) -> Result<Vec<f64>, ValidationError> {
+        let (Some(&first), Some(&last)) = (self.times().first(), self.times().last()) else {
+            return Ok(Vec::new());
+        };
+        let (first, last) = (wall_day(first, zone)?, wall_day(last, zone)?);
+        let most = (last - first) / unit.min_days() + 1;
+        if most > MAX_BUCKETS as i64 {
+            return Err(ValidationError::range("buckets", most, 1, MAX_BUCKETS as i64));
+        }
+        let rule = rule.unwrap_or_default();
+        let mut day = unit.span(first, rule)?.0;
+        let mut bounds = Vec::new();
+        loop {
+            bounds.push(zoned_midnight(day, zone)?.epoch_ms() as f64);
+            if day > last {
+                return Ok(bounds);
+            }
+            day = unit.span(day, rule)?.1;
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl TimeSeriesIndex {
+    /// Get the start time of every calendar bucket spanned by the stored timestamps, for use as the x axis
+    /// of `calendarBucketCounts` and `calendarBucketAggregate`. Buckets are the days, weeks under `rule`
+    /// (ISO 8601 by default), months or years on the wall clock of the IANA zone `zone`, or of the local
+    /// zone if omitted, each starting at midnight. Unlike the fixed-width buckets of `bucketStarts` they
+    /// follow the clocks, so a day is 23 or 25 hours long when they change. Where the clocks skip
+    /// midnight a bucket starts when they resume.
+    /// # Examples
+    /// ```
+    /// const days = index.calendarBucketStarts("day", "Europe/Berlin");
+    /// const weeks = index.calendarBucketStarts("week", undefined, "us");
+    /// ```
+    #[wasm_bindgen(js_name = "calendarBucketStarts")]
+    pub fn calendar_bucket_starts(
+        &self,
+        unit: CalendarBucket,
+        zone: Option<String>,
+        rule: Option<WeekRule>,
+    ) -> Result<Vec<f64>, ValidationError> {
+        let mut bounds = self.calendar_bounds(unit, zone.as_deref(), rule)?;
+        bounds.pop();
+        Ok(bounds)
+    }
+
+    /// Count the timestamps falling into each calendar bucket, as laid out by `calendarBucketStarts`.
+    /// # Examples
+    /// ```
+    /// const perDay = index.calendarBucketCounts("day", "America/New_York");
+    /// ```
+    #[wasm_bindgen(js_name = "calendarBucketCounts")]
+    pub fn calendar_bucket_counts(
+        &self,
+        unit: CalendarBucket,
+        zone: Option<String>,
+        rule: Option<WeekRule>,
+    ) -> Result<Vec<u32>, ValidationError> {
+        let bounds = self.calendar_bounds(unit, zone.as_deref(), rule)?;
+        Ok(count_buckets(self.times(), bounds.len().saturating_sub(1), |t| bucket_of(&bounds, t)))
+    }
+
+    /// Combine `values`, one per stored timestamp, within each calendar bucket as laid out by
+    /// `calendarBucketStarts`, as `bucketAggregate` does for fixed-width buckets.
+    /// # Examples
+    /// ```
+    /// const monthlyTotal = index.calendarBucketAggregate(amounts, "month", "sum", "Europe/London");
+    /// ```
+    #[wasm_bindgen(js_name = "calendarBucketAggregate")]
+    pub fn calendar_bucket_aggregate(
+        &self,
+        values: Vec<f64>,
+        unit: CalendarBucket,
+        op: Aggregation,
+        zone: Option<String>,
+        rule: Option<WeekRule>,
+    ) -> Result<Vec<f64>, ValidationError> {
+        self.check_aggregation(&values, op)?;
+        let bounds = self.calendar_bounds(unit, zone.as_deref(), rule)?;
+        let count = bounds.len().saturating_sub(1);
+        Ok(aggregate_buckets(self.times(), &values, op, count, |t| bucket_of(&bounds, t)))
+    }
+}
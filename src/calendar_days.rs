@@ -0,0 +1,220 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, civil_from_days, days_from_civil, is_leap_year, MS_PER_DAY};
+use crate::tz::{wall_to_zoned, zoned_wall, Disambiguation};
+use crate::validation::{check_date, ValidationError};
+use crate::week::WeekRule;
+use crate::Unitime;
+
+/// Where `Unitime.nextAnniversary` puts February 29 in years without one.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeapDayPolicy {
+    /// The day before, February 28.
+    Feb28 = "feb-28",
+    /// The day after, March 1.
+    Mar1 = "mar-1",
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for LeapDayPolicy {
+    fn default() -> Self {
+        LeapDayPolicy::Feb28
+    }
+}
+
+/// Midnight starting the day `days` after 1970-01-01 on the wall clock of `zone`, or of the host's zone
+/// if `None`. Where the clocks skip midnight the day starts when they resume.
+pub(crate) fn zoned_midnight(days: i64, zone: Option<&str>) -> Result<Unitime, ValidationError> {
+    let wall = days * MS_PER_DAY;
+    let ms = match zone {
+        Some(zone) => wall_to_zoned(wall, zone, Disambiguation::Compatible)?,
+        None => civil::local_to_utc_ms(wall),
+    };
+    Ok(Unitime::from_epoch_ms(ms))
+}
+
+/// The next occurrence of a yearly date, as `Unitime.nextAnniversary` gives it.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Anniversary {
+    /// Days since 1970-01-01.
+    days: i64,
+    /// Nanoseconds since 1970-01-01 UTC of the midnight starting it.
+    start: i128,
+    days_remaining: i64,
+}
+
+#[wasm_bindgen]
+impl Anniversary {
+    /// Get midnight starting the day of the anniversary.
+    #[wasm_bindgen(getter)]
+    pub fn date(&self) -> Unitime {
+        Unitime::from_nanos(self.start)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn year(&self) -> i32 {
+        civil_from_days(self.days).0 as i32
+    }
+
+    /// Get the month, from 1 for January, which for a leap day in a year without one may be March.
+    #[wasm_bindgen(getter)]
+    pub fn month(&self) -> u32 {
+        civil_from_days(self.days).1
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn day(&self) -> u32 {
+        civil_from_days(self.days).2
+    }
+
+    /// Get the number of midnights until the anniversary, 0 when it is today.
+    #[wasm_bindgen(getter, js_name = "daysRemaining")]
+    pub fn days_remaining(&self) -> i32 {
+        self.days_remaining as i32
+    }
+}
+
+impl Unitime {
+    /// The day of the stored time on the wall clock of `zone`, or of the host's zone if `None`, in days
+    /// since 1970-01-01.
+    pub(crate) fn zoned_days(&self, zone: Option<&str>) -> Result<i64, ValidationError> {
+        Ok(zoned_wall(self.epoch_ms(), zone)?.div_euclid(MS_PER_DAY))
+    }
+
+    /// Days from the day of `now` in epoch milliseconds, or of the current time if `None`, to the day of
+    /// the stored time, both on the wall clock of `zone`.
+    pub(crate) fn days_from_now(&self, zone: Option<String>, now: Option<f64>) -> Result<i32, ValidationError> {
+        let now = Unitime::from_epoch_ms(now.map_or_else(crate::now_ms, |ms| ms as i64));
+        now.calendar_days_until(self, zone)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the number of midnights from the stored time to `target` on the wall clock of the IANA zone
+    /// `zone`, or of the local zone if omitted, which is how people count the days until an event: from
+    /// 23:00 tonight to 01:00 tomorrow is 1 day, and from 01:00 to 23:00 the same day is 0. Negative when
+    /// `target` is on an earlier day.
+    /// # Examples
+    /// ```
+    /// const launch = Unitime.fromLocal(2024, 7, 1, 9, 0, 0, "Europe/Paris");
+    /// new Unitime().calendarDaysUntil(launch, "Europe/Paris"); // 3 on the evening of June 28
+    /// ```
+    #[wasm_bindgen(js_name = "calendarDaysUntil")]
+    pub fn calendar_days_until(&self, target: &Unitime, zone: Option<String>) -> Result<i32, ValidationError> {
+        let zone = zone.as_deref();
+        Ok((target.zoned_days(zone)? - self.zoned_days(zone)?) as i32)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the next time `month` and `day` come round, counting the day of the stored time itself, on the
+    /// wall clock of the IANA zone `zone`, or of the local zone if omitted, as for a birthday. An
+    /// anniversary on February 29 falls on February 28 in other years, or on March 1 if `leapDay` is
+    /// `"mar-1"`.
+    /// # Examples
+    /// ```
+    /// const birthday = new Unitime().nextAnniversary(2, 29, "mar-1");
+    /// `${birthday.daysRemaining} days until ${birthday.year}-${birthday.month}-${birthday.day}`;
+    /// ```
+    #[wasm_bindgen(js_name = "nextAnniversary")]
+    pub fn next_anniversary(
+        &self,
+        month: u32,
+        day: u32,
+        leap_day: Option<LeapDayPolicy>,
+        zone: Option<String>,
+    ) -> Result<Anniversary, ValidationError> {
+        // 2000 is a leap year, so this allows February 29.
+        check_date(2000, month, day)?;
+        let leap_day = match leap_day.unwrap_or_default() {
+            LeapDayPolicy::Feb28 => (2, 28),
+            LeapDayPolicy::Mar1 => (3, 1),
+            _ => return Err(ValidationError::new("unknown leap day policy")),
+        };
+        let in_year = |year: i64| match (month, day) {
+            (2, 29) if !is_leap_year(year) => days_from_civil(year, leap_day.0, leap_day.1),
+            _ => days_from_civil(year, month, day),
+        };
+        let zone = zone.as_deref();
+        let today = self.zoned_days(zone)?;
+        let year = civil_from_days(today).0;
+        let days = if in_year(year) >= today { in_year(year) } else { in_year(year + 1) };
+        Ok(Anniversary { days, start: zoned_midnight(days, zone)?.nanos, days_remaining: days - today })
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Check whether the stored time and `other` fall on the same day on the wall clock of the IANA zone
+    /// `zone`, or of the local zone if omitted, as when grouping messages under day headers. Two times a
+    /// minute apart either side of midnight are on different days even if they share a UTC date.
+    /// # Examples
+    /// ```
+    /// const late = Unitime.fromLocal(2024, 6, 5, 23, 30, 0, "America/Los_Angeles");
+    /// const next = Unitime.fromLocal(2024, 6, 6, 0, 30, 0, "America/Los_Angeles");
+    /// late.isSameDay(next, "America/Los_Angeles"); // false
+    /// late.isSameDay(next, "UTC"); // true, both on June 6 in UTC
+    /// ```
+    #[wasm_bindgen(js_name = "isSameDay")]
+    pub fn is_same_day(&self, other: &Unitime, zone: Option<String>) -> Result<bool, ValidationError> {
+        Ok(self.calendar_days_until(other, zone)? == 0)
+    }
+
+    /// Check whether the stored time falls on the same day as `now` in epoch milliseconds, or as the
+    /// current time if omitted, on the wall clock of the IANA zone `zone`, or of the local zone if
+    /// omitted.
+    /// # Examples
+    /// ```
+    /// const label = t.isToday() ? "Today" : t.isYesterday() ? "Yesterday" : t.format("%b %e");
+    /// ```
+    #[wasm_bindgen(js_name = "isToday")]
+    pub fn is_today(&self, zone: Option<String>, now: Option<f64>) -> Result<bool, ValidationError> {
+        Ok(self.days_from_now(zone, now)? == 0)
+    }
+
+    /// Check whether the stored time falls on the day before that of `now`, as for `isToday`.
+    #[wasm_bindgen(js_name = "isYesterday")]
+    pub fn is_yesterday(&self, zone: Option<String>, now: Option<f64>) -> Result<bool, ValidationError> {
+        Ok(self.days_from_now(zone, now)? == -1)
+    }
+
+    /// Check whether the stored time falls on the day after that of `now`, as for `isToday`.
+    #[wasm_bindgen(js_name = "isTomorrow")]
+    pub fn is_tomorrow(&self, zone: Option<String>, now: Option<f64>) -> Result<bool, ValidationError> {
+        Ok(self.days_from_now(zone, now)? == 1)
+    }
+
+    /// Check whether the stored time and `other` fall in the same week under `rule`, ISO 8601 by
+    /// default, on the wall clock of the IANA zone `zone`, or of the local zone if omitted.
+    #[wasm_bindgen(js_name = "isSameWeek")]
+    pub fn is_same_week(
+        &self,
+        other: &Unitime,
+        zone: Option<String>,
+        rule: Option<WeekRule>,
+    ) -> Result<bool, ValidationError> {
+        let (zone, rule) = (zone.as_deref(), rule.unwrap_or_default());
+        Ok(rule.week_start(self.zoned_days(zone)?)? == rule.week_start(other.zoned_days(zone)?)?)
+    }
+
+    /// Check whether the stored time and `other` fall in the same month of the same year on the wall
+    /// clock of the IANA zone `zone`, or of the local zone if omitted.
+    #[wasm_bindgen(js_name = "isSameMonth")]
+    pub fn is_same_month(&self, other: &Unitime, zone: Option<String>) -> Result<bool, ValidationError> {
+        let zone = zone.as_deref();
+        let month = |days: i64| civil_from_days(days).0 * 12 + civil_from_days(days).1 as i64;
+        Ok(month(self.zoned_days(zone)?) == month(other.zoned_days(zone)?))
+    }
+
+    /// Check whether the stored time and `other` fall in the same year on the wall clock of the IANA zone
+    /// `zone`, or of the local zone if omitted.
+    #[wasm_bindgen(js_name = "isSameYear")]
+    pub fn is_same_year(&self, other: &Unitime, zone: Option<String>) -> Result<bool, ValidationError> {
+        let zone = zone.as_deref();
+        Ok(civil_from_days(self.zoned_days(zone)?).0 == civil_from_days(other.zoned_days(zone)?).0)
+    }
+}
@@ -0,0 +1,178 @@
+//! Proleptic Gregorian calendar arithmetic shared by the formatting and parsing code.
+
+pub(crate) const MS_PER_SEC: i64 = 1_000;
+pub(crate) const MS_PER_MIN: i64 = 60 * MS_PER_SEC;
+pub(crate) const MS_PER_HOUR: i64 = 60 * MS_PER_MIN;
+pub(crate) const MS_PER_DAY: i64 = 24 * MS_PER_HOUR;
+/// Largest year either side of year 0 whose dates fit in epoch milliseconds, with room left for zone
+/// offsets and durations added on top.
+pub(crate) const MAX_YEAR: i64 = 290_000_000;
+
+/// A broken-down calendar date and wall-clock time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub milli: u32,
+}
+
+impl DateTime {
+    /// Split epoch milliseconds into calendar fields, without any zone offset applied.
+    pub fn from_epoch_ms(ms: i64) -> DateTime {
+        let days = ms.div_euclid(MS_PER_DAY);
+        let rem = ms.rem_euclid(MS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (rem / MS_PER_HOUR) as u32,
+            minute: (rem % MS_PER_HOUR / MS_PER_MIN) as u32,
+            second: (rem % MS_PER_MIN / MS_PER_SEC) as u32,
+            milli: (rem % MS_PER_SEC) as u32,
+        }
+    }
+
+    /// Join calendar fields back into epoch milliseconds, treating them as UTC.
+    pub fn to_epoch_ms(self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * MS_PER_DAY
+            + self.hour as i64 * MS_PER_HOUR
+            + self.minute as i64 * MS_PER_MIN
+            + self.second as i64 * MS_PER_SEC
+            + self.milli as i64
+    }
+
+    /// Check that every field is within its calendar range.
+    #[cfg_attr(not(feature = "parse"), allow(dead_code))]
+    pub fn is_valid(&self) -> bool {
+        (-MAX_YEAR..=MAX_YEAR).contains(&self.year)
+            && (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+            && self.milli < 1000
+    }
+}
+
+/// Day of the week of the day `days` after 1970-01-01, from 0 for Sunday.
+pub(crate) fn weekday(days: i64) -> usize {
+    (days + 4).rem_euclid(7) as usize
+}
+
+/// Write an astronomical year with at least four digits and a minus sign before year 0, as ISO 8601 does.
+pub(crate) fn year_str(year: i64) -> String {
+    let sign = if year < 0 { "-" } else { "" };
+    format!("{sign}{:04}", year.unsigned_abs())
+}
+
+pub(crate) fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Number of days since 1970-01-01 for the given date (Howard Hinnant's algorithm).
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Move the wall-clock reading `wall` by `months` calendar months, keeping the time of day and moving
+/// the day back to the end of shorter months, so that January 31 plus one month is the last of February.
+pub(crate) fn add_months(wall: i64, months: i64) -> i64 {
+    let dt = DateTime::from_epoch_ms(wall);
+    let total = dt.year * 12 + dt.month as i64 - 1 + months;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+    let day = dt.day.min(days_in_month(year, month));
+    days_from_civil(year, month, day) * MS_PER_DAY + wall.rem_euclid(MS_PER_DAY)
+}
+
+/// Like [`add_months`], or `None` if that goes past [`MAX_YEAR`].
+#[cfg_attr(not(feature = "parse"), allow(dead_code))]
+pub(crate) fn checked_add_months(wall: i64, months: i64) -> Option<i64> {
+    let dt = DateTime::from_epoch_ms(wall);
+    let total = (dt.year * 12 + dt.month as i64 - 1).checked_add(months)?;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+    if year.abs() > MAX_YEAR {
+        return None;
+    }
+    let day = dt.day.min(days_in_month(year, month));
+    Some(days_from_civil(year, month, day) * MS_PER_DAY + wall.rem_euclid(MS_PER_DAY))
+}
+
+/// Inverse of [`days_from_civil`].
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Offset of the host's local time zone from UTC in minutes at the given instant.
+/// Outside of a JS host there is no zone information and UTC is assumed.
+pub(crate) fn local_offset_minutes(ms: i64) -> i64 {
+    #[cfg(js)]
+    {
+        let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(ms as f64));
+        -(date.get_timezone_offset() as i64)
+    }
+    #[cfg(not(js))]
+    {
+        let _ = ms;
+        0
+    }
+}
+
+/// Convert a local wall-clock reading, expressed as epoch milliseconds, to a UTC instant.
+pub(crate) fn local_to_utc_ms(wall_ms: i64) -> i64 {
+    let guess = wall_ms - local_offset_minutes(wall_ms) * MS_PER_MIN;
+    wall_ms - local_offset_minutes(guess) * MS_PER_MIN
+}
+
+/// Convert a UTC instant to the local wall-clock reading, expressed as epoch milliseconds.
+pub(crate) fn utc_to_local_ms(ms: i64) -> i64 {
+    ms + local_offset_minutes(ms) * MS_PER_MIN
+}
+
+/// Convert an instant to a wall-clock reading, in UTC if `utc` is `true`, else in local time.
+pub(crate) fn instant_to_wall(ms: i64, utc: bool) -> i64 {
+    if utc {
+        ms
+    } else {
+        utc_to_local_ms(ms)
+    }
+}
+
+/// Convert a wall-clock reading, in UTC if `utc` is `true`, else in local time, to an instant.
+pub(crate) fn wall_to_instant(wall_ms: i64, utc: bool) -> i64 {
+    if utc {
+        wall_ms
+    } else {
+        local_to_utc_ms(wall_ms)
+    }
+}
@@ -0,0 +1,88 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::UniDuration;
+use crate::validation::ValidationError;
+
+const NANOS_PER_MILLI: u128 = 1_000_000;
+
+/// When `UniDuration.toClockString` writes hours.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockHours {
+    /// "04:12", or "01:04:12" from an hour on.
+    Auto = "auto",
+    /// "00:04:12"
+    Always = "always",
+    /// "64:12", counting whole hours as minutes.
+    Never = "never",
+}
+
+/// Layout of a clock string written by `clock_string`.
+pub(crate) struct ClockOptions {
+    pub hours: ClockHours,
+    /// Whether whole days are split off in front, as in "2d 01:04:12".
+    pub days: bool,
+    /// Whether milliseconds follow the seconds, as in "04:12.500".
+    pub millis: bool,
+}
+
+/// Write `nanos` as a clock string, rounded toward zero to what is shown, with a minus sign if what is
+/// shown is negative.
+pub(crate) fn clock_string(nanos: i128, options: &ClockOptions) -> Result<String, ValidationError> {
+    let ms = nanos.unsigned_abs() / NANOS_PER_MILLI;
+    let shown = if options.millis { ms } else { ms - ms % 1000 };
+    let sign = if nanos < 0 && shown != 0 { "-" } else { "" };
+    let mut secs = ms / 1000;
+    let mut out = String::from(sign);
+    let mut hours_shown = false;
+    if options.days && secs >= 86_400 {
+        out.push_str(&format!("{}d ", secs / 86_400));
+        secs %= 86_400;
+        hours_shown = true;
+    }
+    let show_hours = match options.hours {
+        ClockHours::Auto => hours_shown || secs >= 3600,
+        ClockHours::Always => true,
+        ClockHours::Never => hours_shown,
+        _ => return Err(ValidationError::new("unknown clock hours option")),
+    };
+    if show_hours {
+        out.push_str(&format!("{:02}:", secs / 3600));
+        secs %= 3600;
+    }
+    out.push_str(&format!("{:02}:{:02}", secs / 60, secs % 60));
+    if options.millis {
+        out.push_str(&format!(".{:03}", ms % 1000));
+    }
+    Ok(out)
+}
+
+#[wasm_bindgen]
+impl UniDuration {
+    /// Write the duration as a clock reading such as "04:12" or "01:04:12", with hours written only from
+    /// an hour on unless `hours` is `"always"` or `"never"`, as "00:04:12" or "64:12". With `days` `true`
+    /// whole days are split off in front, as in "2d 01:04:12", and with `millis` `true` milliseconds
+    /// follow, as in "04:12.500". Negative durations get a minus sign. By default this is the layout
+    /// of `Unitime.getElapsedStr`.
+    /// # Examples
+    /// ```
+    /// UniDuration.fromSeconds(3852).toClockString(); // "01:04:12"
+    /// UniDuration.fromSeconds(-252).toClockString(); // "-04:12"
+    /// UniDuration.fromSeconds(252.5).toClockString("always", false, true); // "00:04:12.500"
+    /// UniDuration.fromSeconds(180000).toClockString(undefined, true); // "2d 02:00:00"
+    /// ```
+    #[wasm_bindgen(js_name = "toClockString")]
+    pub fn to_clock_string(
+        &self,
+        hours: Option<ClockHours>,
+        days: Option<bool>,
+        millis: Option<bool>,
+    ) -> Result<String, ValidationError> {
+        let options = ClockOptions {
+            hours: hours.unwrap_or(ClockHours::Auto),
+            days: days.unwrap_or(false),
+            millis: millis.unwrap_or(false),
+        };
+        clock_string(self.nanos() as i128, &options)
+    }
+}
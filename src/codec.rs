@@ -0,0 +1,187 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+use crate::{Unitime, MAX_NANOS, MIN_NANOS};
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+/// MessagePack extension type of timestamps.
+const MSGPACK_TIMESTAMP: u8 = 0xff;
+/// CBOR tag 1, epoch-based date/time, as its initial byte.
+const CBOR_EPOCH_TAG: u8 = 0xc1;
+
+fn from_parts(secs: i128, nanos: i128) -> Result<Unitime, ValidationError> {
+    let nanos = secs * NANOS_PER_SEC + nanos;
+    if !(MIN_NANOS..=MAX_NANOS).contains(&nanos) {
+        return Err(ValidationError::new("encoded time is out of range"));
+    }
+    Ok(Unitime::from_nanos(nanos))
+}
+
+fn be_uint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |n, &b| n << 8 | b as u64)
+}
+
+/// Read the data of a timestamp extension, in the 32-, 64- or 96-bit format.
+fn msgpack_payload(data: &[u8]) -> Result<Unitime, ValidationError> {
+    let (secs, nanos) = match data.len() {
+        4 => (be_uint(data) as i128, 0),
+        8 => {
+            let n = be_uint(data);
+            ((n & 0x3_ffff_ffff) as i128, (n >> 34) as i128)
+        }
+        12 => (be_uint(&data[4..]) as i64 as i128, be_uint(&data[..4]) as i128),
+        len => return Err(ValidationError::new(format!("invalid MessagePack timestamp length {len}"))),
+    };
+    if nanos >= NANOS_PER_SEC {
+        return Err(ValidationError::range("nanoseconds", nanos as i64, 0, 999_999_999));
+    }
+    from_parts(secs, nanos)
+}
+
+/// Read a CBOR half-precision float.
+fn f16_to_f64(bits: u16) -> f64 {
+    let exp = (bits >> 10 & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    let value = match exp {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1024.0 + mantissa) * 2f64.powi(exp - 25),
+    };
+    if bits & 0x8000 != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Write the CBOR head of major type `major` with argument `arg` in its shortest form.
+fn cbor_head(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    match arg {
+        0..=23 => out.push(major | arg as u8),
+        24..=0xff => out.extend([major | 24, arg as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((arg as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((arg as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(arg.to_be_bytes());
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Encode as a MessagePack timestamp, extension type -1, in the smallest of its 32-, 64- and 96-bit
+    /// formats that holds the time exactly. The result includes the extension header, so it can be
+    /// spliced into a MessagePack message as is.
+    /// # Examples
+    /// ```
+    /// const packed = new Unitime().toMsgPack(); // Uint8Array [0xd7, 0xff, ...]
+    /// ```
+    #[wasm_bindgen(js_name = "toMsgPack")]
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        let secs = self.nanos.div_euclid(NANOS_PER_SEC);
+        let nanos = self.nanos.rem_euclid(NANOS_PER_SEC) as u64;
+        let mut out = Vec::with_capacity(15);
+        if nanos == 0 && (0..=u32::MAX as i128).contains(&secs) {
+            out.extend([0xd6, MSGPACK_TIMESTAMP]);
+            out.extend((secs as u32).to_be_bytes());
+        } else if (0..1 << 34).contains(&secs) {
+            out.extend([0xd7, MSGPACK_TIMESTAMP]);
+            out.extend((nanos << 34 | secs as u64).to_be_bytes());
+        } else {
+            out.extend([0xc7, 12, MSGPACK_TIMESTAMP]);
+            out.extend((nanos as u32).to_be_bytes());
+            out.extend((secs as i64).to_be_bytes());
+        }
+        out
+    }
+
+    /// Creates a `Unitime` from a MessagePack timestamp, either a whole extension value or just its 4, 8
+    /// or 12 data bytes as handed to an extension decoder.
+    /// # Examples
+    /// ```
+    /// const codec = new ExtensionCodec();
+    /// codec.register({ type: -1, decode: data => Unitime.fromMsgPack(data), encode: t => null });
+    /// ```
+    #[wasm_bindgen(js_name = "fromMsgPack")]
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Unitime, ValidationError> {
+        let data = match bytes {
+            [0xd6, MSGPACK_TIMESTAMP, data @ ..] if data.len() == 4 => data,
+            [0xd7, MSGPACK_TIMESTAMP, data @ ..] if data.len() == 8 => data,
+            [0xc7, 12, MSGPACK_TIMESTAMP, data @ ..] if data.len() == 12 => data,
+            data => data,
+        };
+        msgpack_payload(data)
+    }
+
+    /// Encode as a CBOR epoch date/time, tag 1 followed by the seconds since 1970. Whole seconds are
+    /// written as an integer and anything else as a double, which keeps about microsecond precision.
+    /// # Examples
+    /// ```
+    /// const cbor = new Unitime().toCBOR(); // Uint8Array [0xc1, 0xfb, ...]
+    /// ```
+    #[wasm_bindgen(js_name = "toCBOR")]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut out = vec![CBOR_EPOCH_TAG];
+        if self.nanos % NANOS_PER_SEC == 0 {
+            let secs = (self.nanos / NANOS_PER_SEC) as i64;
+            if secs >= 0 {
+                cbor_head(&mut out, 0, secs as u64);
+            } else {
+                cbor_head(&mut out, 1, !secs as u64);
+            }
+        } else {
+            out.push(0xfb);
+            out.extend((self.nanos as f64 / NANOS_PER_SEC as f64).to_be_bytes());
+        }
+        out
+    }
+
+    /// Creates a `Unitime` from a CBOR epoch date/time, tag 1 followed by an integer or floating-point
+    /// number of seconds since 1970. The tag may be left out, for data already unwrapped by a decoder.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromCBOR(new Uint8Array([0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0])); // 2013-03-21T20:04:00Z
+    /// ```
+    #[wasm_bindgen(js_name = "fromCBOR")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Unitime, ValidationError> {
+        let item = bytes.strip_prefix(&[CBOR_EPOCH_TAG]).unwrap_or(bytes);
+        let invalid = || ValidationError::new("not a CBOR epoch date/time");
+        let (&first, rest) = item.split_first().ok_or_else(invalid)?;
+        let (major, info) = (first >> 5, first & 0x1f);
+        let len = match info {
+            0..=23 => 0,
+            24..=27 => 1 << (info - 24),
+            _ => return Err(invalid()),
+        };
+        if rest.len() != len {
+            return Err(invalid());
+        }
+        let arg = if len == 0 { info as u64 } else { be_uint(rest) };
+        let secs = match (major, len) {
+            (0, _) => return from_parts(arg as i128, 0),
+            (1, _) => return from_parts(-1 - arg as i128, 0),
+            (7, 2) => f16_to_f64(arg as u16),
+            (7, 4) => f32::from_bits(arg as u32) as f64,
+            (7, 8) => f64::from_bits(arg),
+            _ => return Err(invalid()),
+        };
+        if !secs.is_finite() {
+            return Err(ValidationError::new("CBOR epoch date/time must be finite"));
+        }
+        let nanos = (secs * NANOS_PER_SEC as f64).round();
+        if !(MIN_NANOS as f64..=MAX_NANOS as f64).contains(&nanos) {
+            return Err(ValidationError::new("encoded time is out of range"));
+        }
+        // The bounds themselves round up in floating point.
+        Ok(Unitime::from_nanos((nanos as i128).clamp(MIN_NANOS, MAX_NANOS)))
+    }
+}
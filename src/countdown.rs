@@ -0,0 +1,117 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::Unitime;
+
+const COMPACT_UNITS: [(i64, &str); 4] = [(MS_PER_DAY, "d"), (MS_PER_HOUR, "h"), (MS_PER_MIN, "m"), (MS_PER_SEC, "s")];
+
+/// Write `ms` as e.g. "2d 4h", starting at its largest unit and using at most `max_units` consecutive
+/// units, of which zero ones are left out. Leftovers are dropped, so a countdown never shows more than remains.
+fn compact(ms: i64, max_units: usize) -> String {
+    let mut rest = ms.max(0);
+    let Some(first) = COMPACT_UNITS.iter().position(|&(unit, _)| rest >= unit) else {
+        return "0s".to_string();
+    };
+    let mut parts = Vec::new();
+    for &(unit, suffix) in COMPACT_UNITS[first..].iter().take(max_units.max(1)) {
+        if rest >= unit {
+            parts.push(format!("{}{suffix}", rest / unit));
+        }
+        rest %= unit;
+    }
+    parts.join(" ")
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the time left until the stored time as a compact string such as "2d 4h", "4h 30m" or "45s",
+    /// using at most `maxUnits` units (2 by default) from the largest one needed. Times that have passed
+    /// give "0s".
+    /// # Examples
+    /// ```
+    /// badge.textContent = deadline.remainingStr();
+    /// deadline.remainingStr(3); // "2d 4h 12m"
+    /// ```
+    #[wasm_bindgen(js_name = "remainingStr")]
+    pub fn remaining_str(&self, max_units: Option<u32>) -> String {
+        compact(self.epoch_ms().saturating_sub(crate::now_ms()), max_units.unwrap_or(2) as usize)
+    }
+}
+
+/// Countdown to a target time, for timers, event pages and launch clocks. Every method taking `now`
+/// uses the current time in epoch milliseconds if it is omitted.
+#[wasm_bindgen]
+pub struct Countdown {
+    target_ms: i64,
+}
+
+impl Countdown {
+    fn remaining_ms(&self, now: Option<f64>) -> i64 {
+        self.target_ms.saturating_sub(now.map_or_else(crate::now_ms, |ms| ms.floor() as i64))
+    }
+}
+
+#[wasm_bindgen]
+impl Countdown {
+    /// Creates a new `Countdown` to `target`.
+    /// # Examples
+    /// ```
+    /// const launch = new Countdown(Unitime.fromSQLString("2025-03-14 15:30:00", true));
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(target: &Unitime) -> Countdown {
+        Countdown { target_ms: target.epoch_ms() }
+    }
+
+    /// Creates a new `Countdown` that ends `duration` after `now`.
+    /// # Examples
+    /// ```
+    /// const timer = Countdown.after(UniDuration.fromSeconds(300));
+    /// ```
+    pub fn after(duration: &UniDuration, now: Option<f64>) -> Countdown {
+        let start = now.map_or_else(crate::now_ms, |ms| ms.floor() as i64);
+        Countdown { target_ms: start.saturating_add(duration.nanos().div_euclid(NANOS_PER_MILLI)) }
+    }
+
+    /// Get the target time.
+    #[wasm_bindgen(getter)]
+    pub fn target(&self) -> Unitime {
+        Unitime::from_epoch_ms(self.target_ms)
+    }
+
+    /// Get the time left until the target, which is negative once it has passed.
+    pub fn remaining(&self, now: Option<f64>) -> UniDuration {
+        UniDuration::from_nanos(self.remaining_ms(now).saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Check whether the target time has been reached.
+    #[wasm_bindgen(js_name = "isDone")]
+    pub fn is_done(&self, now: Option<f64>) -> bool {
+        self.remaining_ms(now) <= 0
+    }
+
+    /// Get the time left as a compact string like `Unitime.remainingStr`, e.g. "4h 30m".
+    #[wasm_bindgen(js_name = "remainingStr")]
+    pub fn remaining_str(&self, max_units: Option<u32>, now: Option<f64>) -> String {
+        compact(self.remaining_ms(now), max_units.unwrap_or(2) as usize)
+    }
+
+    /// Format as a launch clock, `T-00:04:35` before the target and `T+00:01:02` from the moment it
+    /// passes. Seconds left are rounded up and seconds since rounded down, so the clock reads
+    /// `T+00:00:00` exactly at the target. Hours keep counting past 99.
+    /// # Examples
+    /// ```
+    /// setInterval(() => clock.textContent = launch.tMinus(), 250);
+    /// ```
+    #[wasm_bindgen(js_name = "tMinus")]
+    pub fn t_minus(&self, now: Option<f64>) -> String {
+        let remaining = self.remaining_ms(now);
+        let (sign, seconds) = if remaining > 0 {
+            ('-', remaining.unsigned_abs().div_ceil(MS_PER_SEC as u64))
+        } else {
+            ('+', remaining.unsigned_abs() / MS_PER_SEC as u64)
+        };
+        format!("T{sign}{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+}
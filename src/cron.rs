@@ -0,0 +1,143 @@
+use crate::civil::{self, civil_from_days, weekday, MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN};
+use crate::validation::{char_offset, ValidationError};
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Name, smallest and largest value of each of the five fields. Weekday 7 is another Sunday.
+const FIELDS: [(&str, u32, u32); 5] =
+    [("minute", 0, 59), ("hour", 0, 23), ("day", 1, 31), ("month", 1, 12), ("weekday", 0, 7)];
+
+/// Every day of the year comes round within eight years, even February 29 across a century.
+const SEARCH_DAYS: i64 = 8 * 366;
+
+/// A five-field cron expression, `minute hour day month weekday`, with `*`, lists, ranges, steps, month
+/// and weekday names, and the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shorthands.
+#[derive(Clone, Debug)]
+pub(crate) struct Cron {
+    /// Bit sets of the allowed values of each field.
+    fields: [u64; 5],
+    /// Whether the day and weekday fields are `*`. When both are restricted, a day matching either counts.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+/// Byte position of `part`, a slice of `text`, within it.
+fn position(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+fn value(expr: &str, text: &str, field: usize) -> Result<u32, ValidationError> {
+    let (name, min, max) = FIELDS[field];
+    let names: &[&str] = match field {
+        3 => &MONTHS,
+        4 => &WEEKDAYS,
+        _ => &[],
+    };
+    if let Some(i) = names.iter().position(|n| n.eq_ignore_ascii_case(text)) {
+        return Ok(min + i as u32);
+    }
+    let value: u32 = text.parse().map_err(|_| {
+        ValidationError::syntax(format!("invalid cron {name}: {text}"), char_offset(expr, position(expr, text)))
+    })?;
+    if !(min..=max).contains(&value) {
+        return Err(ValidationError::range(name, value as i64, min as i64, max as i64));
+    }
+    Ok(value)
+}
+
+/// Read one field as a bit set of its values, and whether it is `*`.
+fn field(expr: &str, text: &str, field: usize) -> Result<(u64, bool), ValidationError> {
+    let (name, min, max) = FIELDS[field];
+    let syntax = |part: &str| {
+        ValidationError::syntax(format!("invalid cron {name}: {part}"), char_offset(expr, position(expr, part)))
+    };
+    let mut bits = 0u64;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u32>().ok().filter(|&s| s > 0).ok_or_else(|| syntax(step))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (value(expr, a, field)?, value(expr, b, field)?)
+        } else {
+            let v = value(expr, range, field)?;
+            (v, if step.is_some() { max } else { v })
+        };
+        if lo > hi {
+            return Err(syntax(part));
+        }
+        for v in (lo..=hi).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << v;
+        }
+    }
+    if field == 4 && bits & 1 << 7 != 0 {
+        bits = bits & !(1 << 7) | 1;
+    }
+    Ok((bits, text == "*"))
+}
+
+fn values(bits: u64) -> impl Iterator<Item = i64> {
+    (0..64).filter(move |b| bits >> b & 1 != 0)
+}
+
+impl Cron {
+    pub(crate) fn parse(expr: &str) -> Result<Cron, ValidationError> {
+        let text = match expr.trim().to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => expr,
+        };
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        if parts.len() != 5 {
+            return Err(ValidationError::new(format!("a cron expression needs 5 fields, not {}", parts.len())));
+        }
+        let mut fields = [0; 5];
+        let mut any = [false; 5];
+        for (i, part) in parts.iter().enumerate() {
+            (fields[i], any[i]) = field(text, part, i)?;
+        }
+        Ok(Cron { fields, any_day: any[2], any_weekday: any[4] })
+    }
+
+    fn matches_day(&self, day: i64) -> bool {
+        let (_, month, dom) = civil_from_days(day);
+        if self.fields[3] & 1 << month == 0 {
+            return false;
+        }
+        let dom = self.fields[2] & 1 << dom != 0;
+        let dow = self.fields[4] & 1 << weekday(day) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => dow,
+            (false, true) => dom,
+            (false, false) => dom || dow,
+        }
+    }
+
+    /// The first instant after `after` that matches, reading fields as local time unless `utc` is `true`,
+    /// or `None` if the expression never matches, such as on February 30.
+    pub(crate) fn next(&self, after: i64, utc: bool) -> Option<i64> {
+        let today = civil::instant_to_wall(after, utc).div_euclid(MS_PER_DAY);
+        for day in (today..today + SEARCH_DAYS).filter(|&day| self.matches_day(day)) {
+            for hour in values(self.fields[1]) {
+                for minute in values(self.fields[0]) {
+                    let wall = day * MS_PER_DAY + hour * MS_PER_HOUR + minute * MS_PER_MIN;
+                    let t = civil::wall_to_instant(wall, utc);
+                    if t > after {
+                        return Some(t);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,42 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::MS_PER_DAY;
+use crate::Unitime;
+
+/// Hand angles of an analog clock in degrees clockwise from 12 o'clock.
+#[wasm_bindgen]
+pub struct ClockAngles {
+    /// Hour hand angle, from 0 to 360.
+    pub hour: f64,
+    /// Minute hand angle, from 0 to 360.
+    pub minute: f64,
+    /// Second hand angle, from 0 to 360.
+    pub second: f64,
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the hand angles of an analog clock showing the stored time, in local time unless `utc` is `true`.
+    /// Without `smooth` the second and minute hands jump once per second and minute like a quartz clock;
+    /// with it every hand sweeps continuously, down to the millisecond.
+    /// # Examples
+    /// ```
+    /// const a = new Unitime().clockAngles(true);
+    /// secondHand.setAttribute("transform", `rotate(${a.second} 50 50)`);
+    /// ```
+    #[wasm_bindgen(js_name = "clockAngles")]
+    pub fn clock_angles(&self, smooth: Option<bool>, utc: Option<bool>) -> ClockAngles {
+        let ms_of_day = self.wall_ms(utc.unwrap_or(false)).rem_euclid(MS_PER_DAY) as f64;
+        let seconds = ms_of_day / 1000.0;
+        let (s, m) = if smooth.unwrap_or(false) {
+            (seconds, seconds / 60.0)
+        } else {
+            (seconds.floor(), (seconds / 60.0).floor())
+        };
+        ClockAngles {
+            hour: m / 60.0 % 12.0 * 30.0,
+            minute: m % 60.0 * 6.0,
+            second: s % 60.0 * 6.0,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Points kept by `downsample`, in their original order.
+#[wasm_bindgen]
+pub struct Downsampled {
+    indices: Vec<u32>,
+    times: Vec<f64>,
+    values: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Downsampled {
+    /// Get the indices of the kept points in the input arrays.
+    #[wasm_bindgen(getter)]
+    pub fn indices(&self) -> Vec<u32> {
+        self.indices.clone()
+    }
+
+    /// Get the timestamps of the kept points.
+    #[wasm_bindgen(getter)]
+    pub fn times(&self) -> Vec<f64> {
+        self.times.clone()
+    }
+
+    /// Get the values of the kept points.
+    #[wasm_bindgen(getter)]
+    pub fn values(&self) -> Vec<f64> {
+        self.values.clone()
+    }
+}
+
+/// Pick the indices of at most `target` points using largest-triangle-three-buckets.
+fn lttb(times: &[f64], values: &[f64], target: usize) -> Vec<u32> {
+    let n = times.len();
+    if target >= n {
+        return (0..n as u32).collect();
+    }
+
+    let mut kept = Vec::with_capacity(target);
+    kept.push(0);
+    // The first and last points are always kept, the rest is split into `target - 2` buckets.
+    let every = (n - 2) as f64 / (target - 2) as f64;
+    let mut a = 0;
+    for i in 0..target - 2 {
+        let start = (i as f64 * every) as usize + 1;
+        let end = ((i + 1) as f64 * every) as usize + 1;
+
+        // The average of the next bucket is the third corner of the triangle.
+        let next_start = end;
+        let next_end = (((i + 2) as f64 * every) as usize + 1).min(n);
+        let next_len = (next_end - next_start) as f64;
+        let avg_t = times[next_start..next_end].iter().sum::<f64>() / next_len;
+        let avg_v = values[next_start..next_end].iter().sum::<f64>() / next_len;
+
+        let (at, av) = (times[a], values[a]);
+        let mut max_area = -1.0;
+        let mut chosen = start;
+        for j in start..end {
+            let area = ((at - avg_t) * (values[j] - av) - (at - times[j]) * (avg_v - av)).abs();
+            if area > max_area {
+                max_area = area;
+                chosen = j;
+            }
+        }
+        kept.push(chosen as u32);
+        a = chosen;
+    }
+    kept.push(n as u32 - 1);
+    kept
+}
+
+/// Reduce a dense time series to at most `target_points` points with the largest-triangle-three-buckets algorithm,
+/// which keeps the visual shape of the series. `times` must be ascending and of the same length as `values`.
+/// # Examples
+/// ```
+/// const d = downsample(times, values, 500);
+/// chart.draw(d.times, d.values);
+/// ```
+#[wasm_bindgen]
+pub fn downsample(
+    times: Vec<f64>,
+    values: Vec<f64>,
+    target_points: usize,
+) -> Result<Downsampled, ValidationError> {
+    if times.len() != values.len() {
+        return Err(ValidationError::new("times and values must have the same length"));
+    }
+    if target_points < 3 && target_points < times.len() {
+        return Err(ValidationError::range("targetPoints", target_points as i64, 3, u32::MAX as i64));
+    }
+    let indices = lttb(&times, &values, target_points);
+    Ok(Downsampled {
+        times: indices.iter().map(|&i| times[i as usize]).collect(),
+        values: indices.iter().map(|&i| values[i as usize]).collect(),
+        indices,
+    })
+}
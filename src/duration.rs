@@ -0,0 +1,220 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::validation::ValidationError;
+
+pub(crate) const NANOS_PER_MILLI: i64 = 1_000_000;
+
+const MS_PER_WEEK: i64 = 7 * MS_PER_DAY;
+
+/// The error of checked arithmetic whose result, `saturated` at the bound it passed, does not fit.
+fn overflow(saturated: i64) -> ValidationError {
+    ValidationError::range("nanoseconds", saturated, i64::MIN, i64::MAX)
+}
+
+/// UniDuration is a signed span of time with nanosecond precision.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UniDuration {
+    nanos: i64,
+}
+
+impl UniDuration {
+    pub(crate) fn from_nanos(nanos: i64) -> UniDuration {
+        UniDuration { nanos }
+    }
+
+    pub(crate) fn nanos(&self) -> i64 {
+        self.nanos
+    }
+}
+
+#[wasm_bindgen]
+impl UniDuration {
+    /// Creates a new `UniDuration` of the given milliseconds, which may have a fraction.
+    /// # Examples
+    /// ```
+    /// const d = new UniDuration(1500);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(millis: f64) -> UniDuration {
+        UniDuration { nanos: (millis * NANOS_PER_MILLI as f64).round() as i64 }
+    }
+
+    /// Creates a new `UniDuration` of the given seconds, which may have a fraction.
+    /// # Examples
+    /// ```
+    /// const d = UniDuration.fromSeconds(90);
+    /// ```
+    #[wasm_bindgen(js_name = "fromSeconds")]
+    pub fn from_seconds(seconds: f64) -> UniDuration {
+        UniDuration::new(seconds * 1000.0)
+    }
+
+    /// Creates a new `UniDuration` of the given minutes, which may have a fraction.
+    pub fn minutes(minutes: f64) -> UniDuration {
+        UniDuration::new(minutes * MS_PER_MIN as f64)
+    }
+
+    /// Creates a new `UniDuration` of the given hours, which may have a fraction.
+    pub fn hours(hours: f64) -> UniDuration {
+        UniDuration::new(hours * MS_PER_HOUR as f64)
+    }
+
+    /// Creates a new `UniDuration` of the given days of 24 hours, which may have a fraction.
+    /// # Examples
+    /// ```
+    /// const expiry = UniDuration.days(30);
+    /// ```
+    pub fn days(days: f64) -> UniDuration {
+        UniDuration::new(days * MS_PER_DAY as f64)
+    }
+
+    /// Creates a new `UniDuration` of the given weeks of 7 days, which may have a fraction.
+    pub fn weeks(weeks: f64) -> UniDuration {
+        UniDuration::new(weeks * MS_PER_WEEK as f64)
+    }
+
+    /// Get the duration in milliseconds, with fraction.
+    #[wasm_bindgen(getter)]
+    pub fn millis(&self) -> f64 {
+        self.nanos as f64 / NANOS_PER_MILLI as f64
+    }
+
+    /// Get the duration in seconds, with fraction.
+    #[wasm_bindgen(getter)]
+    pub fn seconds(&self) -> f64 {
+        self.nanos as f64 / 1e9
+    }
+
+    /// Get the sum of this and another duration. Sums beyond about ±292 years saturate, as with `saturatingAdd`.
+    pub fn add(&self, other: &UniDuration) -> UniDuration {
+        self.saturating_add(other)
+    }
+
+    /// Get the difference of this and another duration. Differences beyond about ±292 years saturate, as with
+    /// `saturatingSub`.
+    pub fn sub(&self, other: &UniDuration) -> UniDuration {
+        self.saturating_sub(other)
+    }
+
+    /// Get the sum of this and another duration, throwing if it is beyond about ±292 years.
+    /// # Examples
+    /// ```
+    /// const total = a.checkedAdd(b);
+    /// ```
+    #[wasm_bindgen(js_name = "checkedAdd")]
+    pub fn checked_add(&self, other: &UniDuration) -> Result<UniDuration, ValidationError> {
+        let nanos = self.nanos.checked_add(other.nanos);
+        Ok(UniDuration { nanos: nanos.ok_or_else(|| overflow(self.nanos.saturating_add(other.nanos)))? })
+    }
+
+    /// Get the difference of this and another duration, throwing if it is beyond about ±292 years.
+    #[wasm_bindgen(js_name = "checkedSub")]
+    pub fn checked_sub(&self, other: &UniDuration) -> Result<UniDuration, ValidationError> {
+        let nanos = self.nanos.checked_sub(other.nanos);
+        Ok(UniDuration { nanos: nanos.ok_or_else(|| overflow(self.nanos.saturating_sub(other.nanos)))? })
+    }
+
+    /// Get the sum of this and another duration, stopping at the longest duration of either sign
+    /// when it does not fit.
+    #[wasm_bindgen(js_name = "saturatingAdd")]
+    pub fn saturating_add(&self, other: &UniDuration) -> UniDuration {
+        UniDuration { nanos: self.nanos.saturating_add(other.nanos) }
+    }
+
+    /// Get the difference of this and another duration, stopping at the longest duration of either sign
+    /// when it does not fit.
+    #[wasm_bindgen(js_name = "saturatingSub")]
+    pub fn saturating_sub(&self, other: &UniDuration) -> UniDuration {
+        UniDuration { nanos: self.nanos.saturating_sub(other.nanos) }
+    }
+
+    /// Get the sum of this and another duration, wrapping around from the longest positive duration to the
+    /// longest negative one when it does not fit, as 64-bit nanosecond counters do.
+    #[wasm_bindgen(js_name = "wrappingAdd")]
+    pub fn wrapping_add(&self, other: &UniDuration) -> UniDuration {
+        UniDuration { nanos: self.nanos.wrapping_add(other.nanos) }
+    }
+
+    /// Get the difference of this and another duration, wrapping around when it does not fit.
+    #[wasm_bindgen(js_name = "wrappingSub")]
+    pub fn wrapping_sub(&self, other: &UniDuration) -> UniDuration {
+        UniDuration { nanos: self.nanos.wrapping_sub(other.nanos) }
+    }
+
+    /// Get the duration with its sign flipped.
+    pub fn negate(&self) -> UniDuration {
+        UniDuration { nanos: self.nanos.saturating_neg() }
+    }
+
+    /// Get the duration without its sign.
+    pub fn abs(&self) -> UniDuration {
+        UniDuration { nanos: self.nanos.saturating_abs() }
+    }
+
+    /// Compare with another duration, returning -1, 0 or 1.
+    pub fn compare(&self, other: &UniDuration) -> i32 {
+        self.cmp(other) as i32
+    }
+}
+
+/// Fixed unit lengths, so that JS code can write `UniDuration.MILLIS_PER_DAY` instead of `86400000`.
+/// Days and weeks are exactly 24 and 168 hours, as elapsed time counts them, whatever the calendar does
+/// around clock changes.
+#[wasm_bindgen]
+impl UniDuration {
+    /// Milliseconds in a second, 1000.
+    #[wasm_bindgen(getter, js_name = "MILLIS_PER_SECOND")]
+    pub fn millis_per_second() -> f64 {
+        MS_PER_SEC as f64
+    }
+
+    /// Milliseconds in a minute, 60 000.
+    #[wasm_bindgen(getter, js_name = "MILLIS_PER_MINUTE")]
+    pub fn millis_per_minute() -> f64 {
+        MS_PER_MIN as f64
+    }
+
+    /// Milliseconds in an hour, 3 600 000.
+    #[wasm_bindgen(getter, js_name = "MILLIS_PER_HOUR")]
+    pub fn millis_per_hour() -> f64 {
+        MS_PER_HOUR as f64
+    }
+
+    /// Milliseconds in a day, 86 400 000.
+    #[wasm_bindgen(getter, js_name = "MILLIS_PER_DAY")]
+    pub fn millis_per_day() -> f64 {
+        MS_PER_DAY as f64
+    }
+
+    /// Milliseconds in a week, 604 800 000.
+    #[wasm_bindgen(getter, js_name = "MILLIS_PER_WEEK")]
+    pub fn millis_per_week() -> f64 {
+        MS_PER_WEEK as f64
+    }
+
+    /// Seconds in a minute, 60.
+    #[wasm_bindgen(getter, js_name = "SECS_PER_MINUTE")]
+    pub fn secs_per_minute() -> f64 {
+        (MS_PER_MIN / MS_PER_SEC) as f64
+    }
+
+    /// Seconds in an hour, 3600.
+    #[wasm_bindgen(getter, js_name = "SECS_PER_HOUR")]
+    pub fn secs_per_hour() -> f64 {
+        (MS_PER_HOUR / MS_PER_SEC) as f64
+    }
+
+    /// Seconds in a day, 86 400.
+    #[wasm_bindgen(getter, js_name = "SECS_PER_DAY")]
+    pub fn secs_per_day() -> f64 {
+        (MS_PER_DAY / MS_PER_SEC) as f64
+    }
+
+    /// Seconds in a week, 604 800.
+    #[wasm_bindgen(getter, js_name = "SECS_PER_WEEK")]
+    pub fn secs_per_week() -> f64 {
+        (MS_PER_WEEK / MS_PER_SEC) as f64
+    }
+}
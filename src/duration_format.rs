@@ -0,0 +1,137 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::validation::ValidationError;
+
+/// How `UniDuration.formatDuration` writes units, as in the `style` option of `Intl.DurationFormat`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DurationStyle {
+    /// "2 hours, 15 minutes"
+    Long = "long",
+    /// "2 hr, 15 min"
+    Short = "short",
+    /// "2h 15m"
+    Narrow = "narrow",
+    /// "2:15:00"
+    Digital = "digital",
+}
+
+impl DurationStyle {
+    fn name(self) -> Result<&'static str, ValidationError> {
+        match self {
+            DurationStyle::Long => Ok("long"),
+            DurationStyle::Short => Ok("short"),
+            DurationStyle::Narrow => Ok("narrow"),
+            DurationStyle::Digital => Ok("digital"),
+            _ => Err(ValidationError::new("unknown duration style")),
+        }
+    }
+}
+
+/// Field name for `Intl.DurationFormat` of each unit, then its English names for the built-in fallback:
+/// long and short singular and plural, and narrow.
+type Unit = (&'static str, [&'static str; 2], [&'static str; 2], &'static str);
+
+const UNITS: [Unit; 5] = [
+    ("days", ["day", "days"], ["day", "days"], "d"),
+    ("hours", ["hour", "hours"], ["hr", "hr"], "h"),
+    ("minutes", ["minute", "minutes"], ["min", "min"], "m"),
+    ("seconds", ["second", "seconds"], ["sec", "sec"], "s"),
+    ("milliseconds", ["millisecond", "milliseconds"], ["ms", "ms"], "ms"),
+];
+
+/// Split `ms` into days, hours, minutes, seconds and milliseconds, all carrying its sign. Digital
+/// style keeps whole days in the hours.
+fn split(ms: i64, digital: bool) -> [i64; 5] {
+    let sign = ms.signum();
+    let ms = ms.abs();
+    let days = if digital { 0 } else { ms / MS_PER_DAY };
+    let hours = (ms - days * MS_PER_DAY) / MS_PER_HOUR;
+    [days, hours, ms % MS_PER_HOUR / MS_PER_MIN, ms % MS_PER_MIN / MS_PER_SEC, ms % MS_PER_SEC].map(|v| v * sign)
+}
+
+/// Built-in English rendering. A negative duration gets a single minus sign in front, as with `Intl`.
+fn english(parts: [i64; 5], style: DurationStyle) -> String {
+    let sign = if parts.iter().any(|&v| v < 0) { "-" } else { "" };
+    let parts = parts.map(i64::abs);
+    if style == DurationStyle::Digital {
+        let [_, h, m, s, ms] = parts;
+        let fraction = if ms != 0 { format!(".{ms:03}") } else { String::new() };
+        return format!("{sign}{h}:{m:02}:{s:02}{fraction}");
+    }
+    let words: Vec<String> = parts
+        .iter()
+        .zip(UNITS)
+        .filter(|(&v, _)| v != 0)
+        .map(|(&v, (_, long, short, narrow))| {
+            let plural = (v != 1) as usize;
+            match style {
+                DurationStyle::Long => format!("{v} {}", long[plural]),
+                DurationStyle::Short => format!("{v} {}", short[plural]),
+                _ => format!("{v}{narrow}"),
+            }
+        })
+        .collect();
+    match (words.is_empty(), style) {
+        (true, DurationStyle::Long) => "0 seconds".to_string(),
+        (true, DurationStyle::Short) => "0 sec".to_string(),
+        (true, _) => "0s".to_string(),
+        (false, DurationStyle::Narrow) => format!("{sign}{}", words.join(" ")),
+        (false, _) => format!("{sign}{}", words.join(", ")),
+    }
+}
+
+/// Format with the host's `Intl.DurationFormat`, if it has one.
+#[cfg(js)]
+fn intl(parts: [i64; 5], locale: Option<&str>, style: &str) -> Option<String> {
+    use js_sys::{Array, Function, Object, Reflect};
+    use wasm_bindgen::JsCast;
+
+    let intl = Reflect::get(&js_sys::global(), &"Intl".into()).ok()?;
+    let constructor: Function = Reflect::get(&intl, &"DurationFormat".into()).ok()?.dyn_into().ok()?;
+    let options = Object::new();
+    Reflect::set(&options, &"style".into(), &style.into()).ok()?;
+    let duration = Object::new();
+    for (&value, (field, ..)) in parts.iter().zip(UNITS) {
+        Reflect::set(&duration, &field.into(), &(value as f64).into()).ok()?;
+    }
+    if parts.iter().all(|&v| v == 0) {
+        Reflect::set(&options, &"secondsDisplay".into(), &"always".into()).ok()?;
+    }
+    let locales = locale.map_or(JsValue::UNDEFINED, JsValue::from);
+    let formatter = Reflect::construct(&constructor, &Array::of2(&locales, &options)).ok()?;
+    let format: Function = Reflect::get(&formatter, &"format".into()).ok()?.dyn_into().ok()?;
+    format.call1(&formatter, &duration).ok()?.as_string()
+}
+
+#[cfg(not(js))]
+fn intl(_parts: [i64; 5], _locale: Option<&str>, _style: &str) -> Option<String> {
+    None
+}
+
+#[wasm_bindgen]
+impl UniDuration {
+    /// Format the duration for people, in days, hours, minutes, seconds and milliseconds, leaving out
+    /// units that are zero. This uses the host's `Intl.DurationFormat` for `locale` where there is one,
+    /// and otherwise falls back to English. `style` is `"short"` by default.
+    /// # Examples
+    /// ```
+    /// const d = new UniDuration(8_100_000);
+    /// d.formatDuration(); // "2 hr, 15 min"
+    /// d.formatDuration("de", "long"); // "2 Stunden und 15 Minuten"
+    /// d.formatDuration(undefined, "digital"); // "2:15:00"
+    /// ```
+    #[wasm_bindgen(js_name = "formatDuration")]
+    pub fn format_duration(
+        &self,
+        locale: Option<String>,
+        style: Option<DurationStyle>,
+    ) -> Result<String, ValidationError> {
+        let style = style.unwrap_or(DurationStyle::Short);
+        let name = style.name()?;
+        let parts = split(self.nanos() / NANOS_PER_MILLI, style == DurationStyle::Digital);
+        Ok(intl(parts, locale.as_deref(), name).unwrap_or_else(|| english(parts, style)))
+    }
+}
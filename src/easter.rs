@@ -0,0 +1,127 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::days_from_civil;
+use crate::month::midnight;
+use crate::validation::{check_year, ValidationError};
+use crate::work::WorkSchedule;
+use crate::Unitime;
+
+/// Most years `WorkSchedule.addMoveableHoliday` marks in one call.
+const MAX_HOLIDAY_YEARS: i64 = 10_000;
+
+/// Holidays whose date follows Easter, as kept in Western churches and many European calendars.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveableFeast {
+    /// 46 days before Easter.
+    AshWednesday = "ash-wednesday",
+    /// 3 days before Easter.
+    MaundyThursday = "maundy-thursday",
+    /// 2 days before Easter.
+    GoodFriday = "good-friday",
+    EasterSunday = "easter-sunday",
+    EasterMonday = "easter-monday",
+    /// 39 days after Easter.
+    Ascension = "ascension",
+    /// 49 days after Easter, also called Whit Sunday.
+    Pentecost = "pentecost",
+    /// 50 days after Easter.
+    WhitMonday = "whit-monday",
+    /// 60 days after Easter.
+    CorpusChristi = "corpus-christi",
+}
+
+impl MoveableFeast {
+    /// Days from Easter Sunday.
+    fn offset(self) -> Result<i64, ValidationError> {
+        match self {
+            MoveableFeast::AshWednesday => Ok(-46),
+            MoveableFeast::MaundyThursday => Ok(-3),
+            MoveableFeast::GoodFriday => Ok(-2),
+            MoveableFeast::EasterSunday => Ok(0),
+            MoveableFeast::EasterMonday => Ok(1),
+            MoveableFeast::Ascension => Ok(39),
+            MoveableFeast::Pentecost => Ok(49),
+            MoveableFeast::WhitMonday => Ok(50),
+            MoveableFeast::CorpusChristi => Ok(60),
+            _ => Err(ValidationError::new("unknown moveable feast")),
+        }
+    }
+
+    /// The feast in `year`, in days since 1970-01-01.
+    fn days_in(self, year: i64) -> Result<i64, ValidationError> {
+        Ok(easter_days(year)? + self.offset()?)
+    }
+}
+
+/// Easter Sunday of `year` on the Gregorian calendar, in days since 1970-01-01, by the anonymous
+/// Gregorian algorithm as given by Meeus.
+fn easter_days(year: i64) -> Result<i64, ValidationError> {
+    check_year(year)?;
+    let a = year.rem_euclid(19);
+    let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b / 4, b % 4);
+    let g = (8 * b + 13) / 25;
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 19 * l) / 433;
+    let month = (h + l - 7 * m + 90) / 25;
+    let day = (h + l - 7 * m + 33 * month + 19).rem_euclid(32);
+    Ok(days_from_civil(year, month as u32, day as u32))
+}
+
+/// Get midnight starting Easter Sunday of `year` as Western churches keep it, on the Gregorian
+/// calendar, in local time unless `utc` is `true`.
+/// # Examples
+/// ```
+/// easterSunday(2024); // Sunday March 31
+/// easterSunday(2025, true); // Sunday April 20
+/// ```
+#[wasm_bindgen(js_name = "easterSunday")]
+pub fn easter_sunday(year: i32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+    Ok(midnight(easter_days(year as i64)?, utc))
+}
+
+/// Get midnight starting the moveable `feast` of `year`, in local time unless `utc` is `true`.
+/// # Examples
+/// ```
+/// moveableFeast("good-friday", 2024); // Friday March 29
+/// moveableFeast("pentecost", 2024); // Sunday May 19
+/// ```
+#[wasm_bindgen(js_name = "moveableFeast")]
+pub fn moveable_feast(feast: MoveableFeast, year: i32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+    Ok(midnight(feast.days_in(year as i64)?, utc))
+}
+
+#[wasm_bindgen]
+impl WorkSchedule {
+    /// Mark the moveable `feast` as a holiday in every year from `fromYear` to `toYear`, or only in
+    /// `fromYear` if `toYear` is omitted, at most 10,000 years at a time.
+    /// # Examples
+    /// ```
+    /// for (const feast of ["good-friday", "easter-monday", "ascension", "whit-monday"]) {
+    ///     berlin.addMoveableHoliday(feast, 2024, 2030);
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = "addMoveableHoliday")]
+    pub fn add_moveable_holiday(
+        &mut self,
+        feast: MoveableFeast,
+        from_year: i32,
+        to_year: Option<i32>,
+    ) -> Result<(), ValidationError> {
+        let to_year = to_year.unwrap_or(from_year);
+        if to_year < from_year {
+            return Err(ValidationError::new("toYear must not be before fromYear"));
+        }
+        let last = from_year as i64 + MAX_HOLIDAY_YEARS - 1;
+        if to_year as i64 > last {
+            return Err(ValidationError::range("toYear", to_year as i64, from_year as i64, last));
+        }
+        for year in from_year..=to_year {
+            self.insert_holiday(feast.days_in(year as i64)?);
+        }
+        Ok(())
+    }
+}
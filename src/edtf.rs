@@ -0,0 +1,418 @@
+//! Extended Date/Time Format (EDTF, ISO 8601-2) at levels 0 and 1.
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_from_civil, days_in_month, year_str, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::interval::UniInterval;
+use crate::month::midnight;
+use crate::parse::Cursor;
+use crate::partial_date::PartialDate;
+use crate::validation::{char_offset, check_year, ValidationError};
+use crate::Unitime;
+
+/// Months each season code covers, as meteorological seasons of the northern hemisphere: 21 spring,
+/// 22 summer, 23 autumn and 24 winter, which runs on into the next year.
+const SEASONS: [(u32, u32); 4] = [(3, 5), (6, 8), (9, 11), (12, 14)];
+
+/// Time of day of a level 0 date and time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Time {
+    /// Milliseconds after midnight, in whole seconds.
+    ms: i64,
+    /// Offset from UTC in minutes, if given.
+    offset: Option<i64>,
+}
+
+/// Date in EDTF: a year, month or day as in `PartialDate`, which may also leave trailing digits
+/// unspecified as in `201X` or `1985-04-XX`, name a season as in `2001-21`, carry a time as in
+/// `1985-04-12T23:20:30Z`, and be marked uncertain with `?`, approximate with `~` or both with `%`.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdtfDate {
+    /// The part that is specified, with the year rounded down where its last digits are not.
+    date: PartialDate,
+    /// Number of trailing year digits written `X`, up to 2.
+    unspecified_year: u32,
+    unspecified_month: bool,
+    unspecified_day: bool,
+    /// Season code from 21 to 24, in which case `date` holds only the year.
+    season: Option<u32>,
+    time: Option<Time>,
+    uncertain: bool,
+    approximate: bool,
+}
+
+/// The first and last year `year` could be with its last `unspecified` digits written `X`. Negative
+/// years count away from zero, so -201X runs from -2019 to -2010.
+fn year_span(year: i64, unspecified: u32) -> (i64, i64) {
+    let span = 10_i64.pow(unspecified) - 1;
+    if year < 0 {
+        (year - span, year)
+    } else {
+        (year, year + span)
+    }
+}
+
+/// Check that every year `year` could be, with its last `unspecified` digits written `X`, is one
+/// `Unitime` can hold.
+fn check_year_span(year: i64, unspecified: u32) -> Result<(), ValidationError> {
+    let (first, last) = year_span(year, unspecified);
+    check_year(first)?;
+    check_year(last)
+}
+
+/// Read two digits or `XX`, giving `None` for `XX`.
+fn two_digits_or_x(c: &mut Cursor) -> Option<Option<u32>> {
+    if c.eat_str("XX") {
+        return Some(None);
+    }
+    c.fixed(2).map(Some)
+}
+
+/// Read the time and offset after the `T` of a date and time.
+fn read_time(c: &mut Cursor) -> Option<Time> {
+    let hour = c.fixed(2).filter(|&h| h < 24)?;
+    c.eat(b':').then_some(())?;
+    let minute = c.fixed(2).filter(|&m| m < 60)?;
+    c.eat(b':').then_some(())?;
+    let second = c.fixed(2).filter(|&s| s < 60)?;
+    let ms = hour as i64 * MS_PER_HOUR + minute as i64 * MS_PER_MIN + second as i64 * MS_PER_SEC;
+    Some(Time { ms, offset: c.utc_offset() })
+}
+
+impl EdtfDate {
+    /// `date` with nothing unspecified or marked.
+    fn plain(date: PartialDate) -> EdtfDate {
+        EdtfDate {
+            date,
+            unspecified_year: 0,
+            unspecified_month: false,
+            unspecified_day: false,
+            season: None,
+            time: None,
+            uncertain: false,
+            approximate: false,
+        }
+    }
+
+    /// Read a date from `c`, leaving anything after it. `invalid` makes the error for a syntax error at
+    /// the cursor.
+    fn read(c: &mut Cursor, invalid: &dyn Fn(&Cursor) -> ValidationError) -> Result<EdtfDate, ValidationError> {
+        let sign = |c: &mut Cursor| if c.eat(b'-') { -1 } else { 1 };
+        if c.eat(b'Y') {
+            // Years of more than four digits, which can only stand alone.
+            let sign = sign(c);
+            let year = sign * c.digits(5, 9).ok_or_else(|| invalid(c))?.0 as i64;
+            check_year_span(year, 0)?;
+            let date = PartialDate::from_parts(year, None, None)?;
+            return EdtfDate::read_qualifier(c, EdtfDate::plain(date));
+        }
+        let sign = sign(c);
+        let (known, digits) = c.digits(2, 4).ok_or_else(|| invalid(c))?;
+        let mut unspecified_year = 0;
+        while unspecified_year < 4 - digits as u32 && c.eat(b'X') {
+            unspecified_year += 1;
+        }
+        if digits as u32 + unspecified_year != 4 || unspecified_year > 2 {
+            return Err(invalid(c));
+        }
+        let year = sign * (known as i64 * 10_i64.pow(unspecified_year));
+        check_year_span(year, unspecified_year)?;
+        let (mut month, mut day, mut season) = (None, None, None);
+        let (mut unspecified_month, mut unspecified_day) = (false, false);
+        let mut ahead = c.clone();
+        if unspecified_year == 0 && ahead.eat(b'-') {
+            *c = ahead;
+            match two_digits_or_x(c).ok_or_else(|| invalid(c))? {
+                Some(code @ 21..=24) => season = Some(code),
+                Some(m) => month = Some(m),
+                None => unspecified_month = true,
+            }
+            let mut ahead = c.clone();
+            if season.is_none() && ahead.eat(b'-') {
+                *c = ahead;
+                match two_digits_or_x(c).ok_or_else(|| invalid(c))? {
+                    // A known day of an unknown month is beyond level 1.
+                    Some(_) if unspecified_month => return Err(invalid(c)),
+                    Some(d) => day = Some(d),
+                    None => unspecified_day = true,
+                }
+            }
+        }
+        let mut edtf = EdtfDate {
+            unspecified_year,
+            unspecified_month,
+            unspecified_day,
+            season,
+            ..EdtfDate::plain(PartialDate::from_parts(year, month, day)?)
+        };
+        // Only dates known to the day may carry a time, and times are never qualified.
+        if edtf.date.day().is_some() && c.eat(b'T') {
+            edtf.time = Some(read_time(c).ok_or_else(|| invalid(c))?);
+            return Ok(edtf);
+        }
+        EdtfDate::read_qualifier(c, edtf)
+    }
+
+    /// Read a `?`, `~` or `%` after a date, if there is one.
+    fn read_qualifier(c: &mut Cursor, mut edtf: EdtfDate) -> Result<EdtfDate, ValidationError> {
+        match c.peek() {
+            Some(b'?') => edtf.uncertain = true,
+            Some(b'~') => edtf.approximate = true,
+            Some(b'%') => (edtf.uncertain, edtf.approximate) = (true, true),
+            _ => return Ok(edtf),
+        }
+        c.eat(c.peek().unwrap_or(0));
+        Ok(edtf)
+    }
+
+    fn parse(s: &str) -> Result<EdtfDate, ValidationError> {
+        let invalid = |c: &Cursor| ValidationError::syntax(format!("invalid EDTF date: {s}"), char_offset(s, c.pos()));
+        let mut c = Cursor::new(s);
+        let date = EdtfDate::read(&mut c, &invalid)?;
+        if !c.is_end() {
+            return Err(invalid(&c));
+        }
+        Ok(date)
+    }
+
+    /// The first and last day the date could be, in days since 1970-01-01.
+    fn day_range(&self) -> (i64, i64) {
+        let year = self.date.year() as i64;
+        if let Some(season) = self.season {
+            let (first, last) = SEASONS[season as usize - 21];
+            let (last_year, last) = (year + (last as i64 - 1) / 12, (last - 1) % 12 + 1);
+            let last_day = days_in_month(last_year, last);
+            return (days_from_civil(year, first, 1), days_from_civil(last_year, last, last_day));
+        }
+        if self.unspecified_year == 0 {
+            return self.date.day_range();
+        }
+        let (first, last) = year_span(year, self.unspecified_year);
+        (days_from_civil(first, 1, 1), days_from_civil(last, 12, 31))
+    }
+}
+
+#[wasm_bindgen]
+impl EdtfDate {
+    /// Read an EDTF level 0 or 1 date, such as `1985`, `1985-04-12T23:20:30Z`, `201X`, `1985-XX-XX`,
+    /// `2001-21` for spring 2001, `Y-170000002`, `1984?`, `2004-06~` or `2004-06-11%`.
+    /// # Examples
+    /// ```
+    /// const d = EdtfDate.fromString("1984?");
+    /// d.uncertain; // true
+    /// d.date.toString(); // "1984"
+    /// ```
+    #[wasm_bindgen(js_name = "fromString")]
+    pub fn from_string(s: &str) -> Result<EdtfDate, ValidationError> {
+        EdtfDate::parse(s)
+    }
+
+    /// Creates a new `EdtfDate` from a partial date, marked uncertain or approximate as given.
+    #[wasm_bindgen(js_name = "fromPartialDate")]
+    pub fn from_partial_date(date: &PartialDate, uncertain: Option<bool>, approximate: Option<bool>) -> EdtfDate {
+        EdtfDate {
+            uncertain: uncertain.unwrap_or(false),
+            approximate: approximate.unwrap_or(false),
+            ..EdtfDate::plain(date.clone())
+        }
+    }
+
+    /// Get the part of the date that is specified, or `undefined` if some year digits are not, as in
+    /// `201X`. Seasons give their year, and `1985-04-XX` gives April 1985.
+    #[wasm_bindgen(getter)]
+    pub fn date(&self) -> Option<PartialDate> {
+        (self.unspecified_year == 0).then(|| self.date.clone())
+    }
+
+    /// Get the season code, 21 for spring to 24 for winter, if the date names one.
+    #[wasm_bindgen(getter)]
+    pub fn season(&self) -> Option<u32> {
+        self.season
+    }
+
+    /// Check whether the date is marked uncertain, with `?` or `%`.
+    #[wasm_bindgen(getter)]
+    pub fn uncertain(&self) -> bool {
+        self.uncertain
+    }
+
+    /// Check whether the date is marked approximate, with `~` or `%`.
+    #[wasm_bindgen(getter)]
+    pub fn approximate(&self) -> bool {
+        self.approximate
+    }
+
+    /// Get the instant a date and time names, or `undefined` for a date without a time. A time without
+    /// an offset is read in local time unless `utc` is `true`.
+    #[wasm_bindgen(js_name = "toUnitime")]
+    pub fn to_unitime(&self, utc: Option<bool>) -> Option<Unitime> {
+        let time = self.time.as_ref()?;
+        let wall = self.date.day_range().0 * crate::civil::MS_PER_DAY + time.ms;
+        Some(Unitime::from_epoch_ms(match time.offset {
+            Some(offset) => wall - offset * MS_PER_MIN,
+            None => crate::civil::wall_to_instant(wall, utc.unwrap_or(false)),
+        }))
+    }
+
+    /// Get the span of time the date could be, from midnight starting its first day to the end of its
+    /// last day, in local time unless `utc` is `true`; `201X` spans 2010 to 2019. A date and time gives
+    /// just its instant.
+    #[wasm_bindgen(js_name = "toInterval")]
+    pub fn to_interval(&self, utc: Option<bool>) -> Result<UniInterval, ValidationError> {
+        if let Some(t) = self.to_unitime(utc) {
+            return UniInterval::from_nanos(t.nanos, t.nanos);
+        }
+        let (first, last) = self.day_range();
+        UniInterval::from_nanos(midnight(first, utc).nanos, midnight(last + 1, utc).nanos - 1)
+    }
+
+    /// Get the date in EDTF.
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        let year = self.date.year() as i64;
+        let mut out = if year.unsigned_abs() > 9999 {
+            format!("Y{year}")
+        } else {
+            let year = year_str(year);
+            let known = year.len() - self.unspecified_year as usize;
+            format!("{}{}", &year[..known], "X".repeat(self.unspecified_year as usize))
+        };
+        let month = self.season.or(self.date.month());
+        if let Some(month) = month {
+            out.push_str(&format!("-{month:02}"));
+        } else if self.unspecified_month {
+            out.push_str("-XX");
+        }
+        if let Some(day) = self.date.day() {
+            out.push_str(&format!("-{day:02}"));
+        } else if self.unspecified_day {
+            out.push_str("-XX");
+        }
+        if let Some(time) = &self.time {
+            let secs = time.ms / MS_PER_SEC;
+            out.push_str(&format!("T{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60));
+            match time.offset {
+                Some(0) => out.push('Z'),
+                Some(offset) => {
+                    let sign = if offset < 0 { '-' } else { '+' };
+                    out.push_str(&format!("{sign}{:02}:{:02}", offset.abs() / 60, offset.abs() % 60));
+                }
+                None => {}
+            }
+        }
+        match (self.uncertain, self.approximate) {
+            (true, true) => out.push('%'),
+            (true, false) => out.push('?'),
+            (false, true) => out.push('~'),
+            (false, false) => {}
+        }
+        out
+    }
+}
+
+/// What one end of an `EdtfInterval` is.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdtfEndpoint {
+    /// A date, as in `1985/2004`.
+    Date = "date",
+    /// Open, with no bound, written `..` as in `1985/..`.
+    Open = "open",
+    /// Unknown, written as nothing as in `1985/`.
+    Unknown = "unknown",
+}
+
+/// Interval in EDTF, such as `1964/2008`, `2004-06/2006-08~`, the open-ended `1985-04-12/..` or one
+/// with an unknown end such as `1985-04-12/`.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdtfInterval {
+    start: Option<EdtfDate>,
+    end: Option<EdtfDate>,
+    /// How each end without a date is written.
+    start_kind: EdtfEndpoint,
+    end_kind: EdtfEndpoint,
+}
+
+/// Read the end of interval `s` that starts at byte `offset` and is `text`.
+fn endpoint(s: &str, offset: usize, text: &str) -> Result<(Option<EdtfDate>, EdtfEndpoint), ValidationError> {
+    match text {
+        "" => Ok((None, EdtfEndpoint::Unknown)),
+        ".." => Ok((None, EdtfEndpoint::Open)),
+        _ => {
+            let invalid = |c: &Cursor| {
+                ValidationError::syntax(format!("invalid EDTF interval: {s}"), char_offset(s, offset + c.pos()))
+            };
+            let mut c = Cursor::new(text);
+            let date = EdtfDate::read(&mut c, &invalid)?;
+            // Level 1 intervals are of dates without times.
+            if !c.is_end() || date.time.is_some() {
+                return Err(invalid(&c));
+            }
+            Ok((Some(date), EdtfEndpoint::Date))
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl EdtfInterval {
+    /// Read an EDTF level 0 or 1 interval of two dates, either of which may be `..` for open or left
+    /// out for unknown, but not both. The start must not be after the end.
+    /// # Examples
+    /// ```
+    /// const career = EdtfInterval.fromString("1985-04/..");
+    /// career.endKind; // "open"
+    /// career.start.date.toString(); // "1985-04"
+    /// ```
+    #[wasm_bindgen(js_name = "fromString")]
+    pub fn from_string(s: &str) -> Result<EdtfInterval, ValidationError> {
+        let Some((start, end)) = s.split_once('/') else {
+            return Err(ValidationError::syntax(format!("invalid EDTF interval: {s}"), s.chars().count()));
+        };
+        let (end, end_kind) = endpoint(s, start.len() + 1, end)?;
+        let (start, start_kind) = endpoint(s, 0, start)?;
+        if start.is_none() && end.is_none() {
+            return Err(ValidationError::new(format!("an EDTF interval needs at least one date: {s}")));
+        }
+        if let (Some(start), Some(end)) = (&start, &end) {
+            if start.day_range().0 > end.day_range().1 {
+                return Err(ValidationError::new(format!("the EDTF interval ends before it starts: {s}")));
+            }
+        }
+        Ok(EdtfInterval { start, end, start_kind, end_kind })
+    }
+
+    /// Get the date the interval starts, if it has one.
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> Option<EdtfDate> {
+        self.start.clone()
+    }
+
+    /// Get the date the interval ends, if it has one.
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> Option<EdtfDate> {
+        self.end.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "startKind")]
+    pub fn start_kind(&self) -> EdtfEndpoint {
+        self.start_kind
+    }
+
+    #[wasm_bindgen(getter, js_name = "endKind")]
+    pub fn end_kind(&self) -> EdtfEndpoint {
+        self.end_kind
+    }
+
+    /// Get the interval in EDTF.
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        let end = |date: &Option<EdtfDate>, kind| match (date, kind) {
+            (Some(date), _) => date.to_string_js(),
+            (None, EdtfEndpoint::Open) => "..".to_string(),
+            (None, _) => String::new(),
+        };
+        format!("{}/{}", end(&self.start, self.start_kind), end(&self.end, self.end_kind))
+    }
+}
@@ -0,0 +1,108 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{add_months, DateTime, MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::Unitime;
+
+/// Time between two instants in calendar units, as `Unitime.elapsedCalendar` gives it. Every field has
+/// the same sign, negative when the stored time is in the future.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarElapsed {
+    years: i32,
+    months: i32,
+    days: i32,
+    hours: i32,
+    minutes: i32,
+    seconds: i32,
+}
+
+impl CalendarElapsed {
+    /// Break down the time from the wall-clock reading `from` to the later reading `to`.
+    fn between(from: i64, to: i64) -> CalendarElapsed {
+        let (start, end) = (DateTime::from_epoch_ms(from), DateTime::from_epoch_ms(to));
+        let mut months = (end.year - start.year) * 12 + end.month as i64 - start.month as i64;
+        if add_months(from, months) > to {
+            months -= 1;
+        }
+        let rest = to - add_months(from, months);
+        CalendarElapsed {
+            years: (months / 12) as i32,
+            months: (months % 12) as i32,
+            days: (rest / MS_PER_DAY) as i32,
+            hours: (rest % MS_PER_DAY / MS_PER_HOUR) as i32,
+            minutes: (rest % MS_PER_HOUR / MS_PER_MIN) as i32,
+            seconds: (rest % MS_PER_MIN / MS_PER_SEC) as i32,
+        }
+    }
+
+    fn negated(self) -> CalendarElapsed {
+        CalendarElapsed {
+            years: -self.years,
+            months: -self.months,
+            days: -self.days,
+            hours: -self.hours,
+            minutes: -self.minutes,
+            seconds: -self.seconds,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl CalendarElapsed {
+    #[wasm_bindgen(getter)]
+    pub fn years(&self) -> i32 {
+        self.years
+    }
+
+    /// Get the whole months after the whole years, from 0 to 11.
+    #[wasm_bindgen(getter)]
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    /// Get the whole days after the whole months, from 0 to 30.
+    #[wasm_bindgen(getter)]
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn hours(&self) -> i32 {
+        self.hours
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn minutes(&self) -> i32 {
+        self.minutes
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn seconds(&self) -> i32 {
+        self.seconds
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the time from the stored time to `now` in epoch milliseconds, or to the current time if
+    /// omitted, in years, months, days, hours, minutes and seconds as a calendar counts them, on the wall
+    /// clock in local time unless `utc` is `true`. Months have their real lengths: from January 31 to the
+    /// last of February is one month, and from then to March 31 is one month more. When the stored time
+    /// is in the future every field is negative, as for the other elapsed getters.
+    /// # Examples
+    /// ```
+    /// const age = Unitime.fromDateMillis(Date.UTC(2022, 2, 15)).elapsedCalendar(true, Date.UTC(2024, 5, 20));
+    /// `${age.years} years, ${age.months} months`; // "2 years, 3 months"
+    /// ```
+    #[wasm_bindgen(js_name = "elapsedCalendar")]
+    pub fn elapsed_calendar(&self, utc: Option<bool>, now: Option<f64>) -> CalendarElapsed {
+        let utc = utc.unwrap_or(false);
+        let now = Unitime::from_epoch_ms(now.map_or_else(crate::now_ms, |ms| ms as i64)).wall_ms(utc);
+        let then = self.wall_ms(utc);
+        if then > now {
+            CalendarElapsed::between(now, then).negated()
+        } else {
+            CalendarElapsed::between(then, now)
+        }
+    }
+}
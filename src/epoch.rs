@@ -0,0 +1,92 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Unit of a numeric epoch timestamp.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EpochUnit {
+    Seconds = "seconds",
+    Milliseconds = "milliseconds",
+    Microseconds = "microseconds",
+    Nanoseconds = "nanoseconds",
+}
+
+impl EpochUnit {
+    fn nanos(self) -> Result<i128, ValidationError> {
+        match self {
+            EpochUnit::Seconds => Ok(1_000_000_000),
+            EpochUnit::Milliseconds => Ok(1_000_000),
+            EpochUnit::Microseconds => Ok(1_000),
+            EpochUnit::Nanoseconds => Ok(1),
+            _ => Err(ValidationError::new("unknown epoch unit")),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Guess the unit of an epoch timestamp from its magnitude. Values up to 1e11 are read as seconds, up to
+    /// 1e14 as milliseconds, up to 1e17 as microseconds and beyond that as nanoseconds, in either direction.
+    /// That reads any time from 1973 to 5138 in any unit correctly, while seconds work from 1970 on.
+    /// # Examples
+    /// ```
+    /// Unitime.detectEpochUnit(1717416309); // "seconds"
+    /// Unitime.detectEpochUnit(1717416309123456); // "microseconds"
+    /// ```
+    #[wasm_bindgen(js_name = "detectEpochUnit")]
+    pub fn detect_epoch_unit(value: f64) -> EpochUnit {
+        match value.abs() {
+            v if v < 1e11 => EpochUnit::Seconds,
+            v if v < 1e14 => EpochUnit::Milliseconds,
+            v if v < 1e17 => EpochUnit::Microseconds,
+            _ => EpochUnit::Nanoseconds,
+        }
+    }
+
+    /// Creates a new `Unitime` from an epoch timestamp in seconds, milliseconds, microseconds or
+    /// nanoseconds, guessed as `detectEpochUnit` does unless `unit` is given. Nanosecond timestamps lose
+    /// some precision as JavaScript numbers. Throws for values that are not finite.
+    /// # Examples
+    /// ```
+    /// const rows = data.map(row => Unitime.fromEpochAuto(row.timestamp));
+    /// ```
+    #[wasm_bindgen(js_name = "fromEpochAuto")]
+    pub fn from_epoch_auto(value: f64, unit: Option<EpochUnit>) -> Result<Unitime, ValidationError> {
+        if !value.is_finite() {
+            return Err(ValidationError::new("epoch timestamp is not a finite number"));
+        }
+        let unit = unit.unwrap_or_else(|| Unitime::detect_epoch_unit(value));
+        Ok(Unitime::from_epoch_float(value, unit.nanos()?))
+    }
+
+    /// Get the stored time in whole epoch seconds, rounded down, as a `BigInt`.
+    /// # Examples
+    /// ```
+    /// const exp = new Unitime().epochSeconds + 3600n;
+    /// ```
+    #[wasm_bindgen(getter, js_name = "epochSeconds")]
+    pub fn epoch_seconds(&self) -> i64 {
+        self.nanos.div_euclid(1_000_000_000) as i64
+    }
+
+    /// Get the stored time in whole epoch milliseconds, rounded down, as a `BigInt`.
+    #[wasm_bindgen(getter, js_name = "epochMillis")]
+    pub fn epoch_millis(&self) -> i64 {
+        self.epoch_ms()
+    }
+
+    /// Get the stored time in whole epoch microseconds, rounded down, as a `BigInt`. Times more than
+    /// about 292,000 years from 1970 saturate at the `i64` limits.
+    #[wasm_bindgen(getter, js_name = "epochMicros")]
+    pub fn epoch_micros(&self) -> i64 {
+        self.nanos.div_euclid(1_000).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Get the stored time in epoch nanoseconds as a `BigInt`, exact for every time a `Unitime` holds.
+    #[wasm_bindgen(getter, js_name = "epochNanos")]
+    pub fn epoch_nanos(&self) -> i128 {
+        self.nanos
+    }
+}
@@ -0,0 +1,119 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Exponentially-weighted moving average of the time between events, for smoothed rate displays
+/// such as download speed or heartbeat intervals.
+#[wasm_bindgen]
+pub struct IntervalEWMA {
+    alpha: f64,
+    last: Option<f64>,
+    interval: Option<f64>,
+    amount: Option<f64>,
+    samples: u32,
+}
+
+#[wasm_bindgen]
+impl IntervalEWMA {
+    /// Creates a new `IntervalEWMA` with smoothing factor `alpha` in `(0, 1]`; higher values follow changes faster.
+    /// # Examples
+    /// ```
+    /// const speed = new IntervalEWMA(0.2);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(alpha: f64) -> Result<IntervalEWMA, ValidationError> {
+        if alpha.is_nan() || alpha <= 0.0 || alpha > 1.0 {
+            return Err(ValidationError::new("alpha must be in (0, 1]"));
+        }
+        Ok(IntervalEWMA { alpha, last: None, interval: None, amount: None, samples: 0 })
+    }
+
+    /// Creates a new `IntervalEWMA` whose samples lose half their weight after `samples` further events.
+    /// # Examples
+    /// ```
+    /// const heartbeat = IntervalEWMA.withHalfLife(10);
+    /// ```
+    #[wasm_bindgen(js_name = "withHalfLife")]
+    pub fn with_half_life(samples: f64) -> Result<IntervalEWMA, ValidationError> {
+        if samples.is_nan() || samples <= 0.0 {
+            return Err(ValidationError::new("half-life must be positive"));
+        }
+        IntervalEWMA::new(1.0 - 0.5f64.powf(1.0 / samples))
+    }
+
+    /// Record an event at time `t` in epoch milliseconds, or now if omitted, carrying `amount` units of work
+    /// (e.g. bytes received), which defaults to 1. Events earlier than the previous one are ignored.
+    /// # Examples
+    /// ```
+    /// speed.record(undefined, chunk.byteLength);
+    /// ```
+    pub fn record(&mut self, t: Option<f64>, amount: Option<f64>) {
+        let t = t.unwrap_or_else(|| crate::now_ms() as f64);
+        let amount = amount.unwrap_or(1.0);
+        if t.is_nan() || amount.is_nan() {
+            return;
+        }
+        if let Some(last) = self.last {
+            if t < last {
+                return;
+            }
+            let dt = t - last;
+            self.interval = Some(match self.interval {
+                Some(avg) => avg + self.alpha * (dt - avg),
+                None => dt,
+            });
+            self.amount = Some(match self.amount {
+                Some(avg) => avg + self.alpha * (amount - avg),
+                None => amount,
+            });
+            self.samples += 1;
+        }
+        self.last = Some(t);
+    }
+
+    /// Get the smoothed time between events in milliseconds, or `undefined` before the second event.
+    #[wasm_bindgen(getter)]
+    pub fn interval(&self) -> Option<f64> {
+        self.interval
+    }
+
+    /// Get the smoothed rate in units per second, or `undefined` before the second event.
+    /// # Examples
+    /// ```
+    /// label.textContent = `${(speed.ratePerSecond / 1024).toFixed(1)} KiB/s`;
+    /// ```
+    #[wasm_bindgen(getter, js_name = "ratePerSecond")]
+    pub fn rate_per_second(&self) -> Option<f64> {
+        match (self.interval, self.amount) {
+            (Some(interval), Some(amount)) if interval > 0.0 => Some(amount * 1000.0 / interval),
+            _ => None,
+        }
+    }
+
+    /// Get the time of the latest event in epoch milliseconds, or `undefined` if none was recorded.
+    #[wasm_bindgen(getter, js_name = "lastEvent")]
+    pub fn last_event(&self) -> Option<f64> {
+        self.last
+    }
+
+    /// Get the number of intervals that went into the average.
+    #[wasm_bindgen(getter)]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Get the milliseconds since the latest event as of `now`, or the current time if omitted.
+    #[wasm_bindgen(js_name = "timeSinceLast")]
+    pub fn time_since_last(&self, now: Option<f64>) -> Option<f64> {
+        let now = now.unwrap_or_else(|| crate::now_ms() as f64);
+        self.last.map(|last| now - last)
+    }
+
+    /// Forget all recorded events.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.interval = None;
+        self.amount = None;
+        self.samples = 0;
+    }
+}
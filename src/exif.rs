@@ -0,0 +1,72 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, DateTime, MS_PER_MIN};
+use crate::parse::Cursor;
+use crate::validation::{char_offset, ValidationError};
+use crate::Unitime;
+
+/// Read an EXIF `YYYY:MM:DD HH:MM:SS` date and time.
+fn parse_exif(c: &mut Cursor) -> Option<DateTime> {
+    let mut fields = [0; 6];
+    for (i, separator) in [Some(b':'), Some(b':'), Some(b' '), Some(b':'), Some(b':'), None].into_iter().enumerate() {
+        fields[i] = c.fixed(if i == 0 { 4 } else { 2 })?;
+        if separator.is_some_and(|separator| !c.eat(separator)) {
+            return None;
+        }
+    }
+    let [year, month, day, hour, minute, second] = fields;
+    Some(DateTime { year: year as i64, month, day, hour, minute, second, milli: 0 })
+}
+
+/// Trim the spaces and NUL padding that EXIF strings are stored with.
+fn trim(s: &str) -> &str {
+    s.trim_matches(|c: char| c.is_whitespace() || c == '\0')
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Creates a new `Unitime` from an EXIF date and time such as the `DateTimeOriginal` tag of a photo,
+    /// `2024:06:01 14:30:22`. `offsetTime` is the matching `OffsetTimeOriginal` tag, such as `+02:00`,
+    /// and `subSecTime` the `SubSecTimeOriginal` tag, the digits of the fraction of the second. EXIF
+    /// times without an offset are the camera's wall clock, read as local time unless `utc` is `true`.
+    /// A date left blank or zero by the camera is an error.
+    /// # Examples
+    /// ```
+    /// const taken = Unitime.fromEXIF(tags.DateTimeOriginal, tags.OffsetTimeOriginal, tags.SubSecTimeOriginal);
+    /// ```
+    #[wasm_bindgen(js_name = "fromEXIF")]
+    pub fn from_exif(
+        date_time: &str,
+        offset_time: Option<String>,
+        sub_sec_time: Option<String>,
+        utc: Option<bool>,
+    ) -> Result<Unitime, ValidationError> {
+        let text = trim(date_time);
+        let lead = date_time.len() - date_time.trim_start_matches(|c: char| c.is_whitespace() || c == '\0').len();
+        let syntax = |s: &str, at| ValidationError::syntax(format!("invalid EXIF time: {s}"), char_offset(s, at));
+        if text.is_empty() || text.bytes().all(|b| matches!(b, b'0' | b':' | b' ')) {
+            return Err(ValidationError::new("the EXIF date and time are not set"));
+        }
+        let mut c = Cursor::new(text);
+        let mut dt = parse_exif(&mut c).ok_or_else(|| syntax(date_time, lead + c.pos()))?;
+        // Some writers put the offset right after the time.
+        let mut offset = c.utc_offset();
+        if !c.is_end() {
+            return Err(syntax(date_time, lead + c.pos()));
+        }
+        if let Some(sub_sec) = sub_sec_time.as_deref().map(trim).filter(|s| !s.is_empty()) {
+            let mut c = Cursor::new(sub_sec);
+            dt.milli = c.fraction_ms().filter(|_| c.is_end()).ok_or_else(|| syntax(sub_sec, c.pos()))?;
+        }
+        if let Some(text) = offset_time.as_deref().map(trim).filter(|s| !s.is_empty() && offset.is_none()) {
+            let mut c = Cursor::new(text);
+            offset = Some(c.utc_offset().filter(|_| c.is_end()).ok_or_else(|| syntax(text, c.pos()))?);
+        }
+        dt.validate()?;
+        let ms = match offset {
+            Some(offset) => dt.to_epoch_ms() - offset * MS_PER_MIN,
+            None => civil::wall_to_instant(dt.to_epoch_ms(), utc.unwrap_or(false)),
+        };
+        Ok(Unitime::from_epoch_ms(ms))
+    }
+}
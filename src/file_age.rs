@@ -0,0 +1,101 @@
+use wasm_bindgen::prelude::*;
+
+use crate::calendar_days::zoned_midnight;
+use crate::civil::{civil_from_days, days_from_civil};
+use crate::validation::ValidationError;
+use crate::week::WeekRule;
+use crate::Unitime;
+
+/// The group a file listing puts a modification time under, as `Unitime.fileAge` gives it. Each time
+/// goes under the first group that holds it, so yesterday comes before this week even when this week
+/// began today.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileAge {
+    /// After today, as with a clock set wrong.
+    Future = "future",
+    Today = "today",
+    Yesterday = "yesterday",
+    /// Earlier this week.
+    ThisWeek = "this-week",
+    LastWeek = "last-week",
+    /// Earlier this month.
+    ThisMonth = "this-month",
+    /// Earlier this year.
+    ThisYear = "this-year",
+    Older = "older",
+}
+
+/// Where each group of `FileAge` starts, found once so that many times can be grouped with comparisons
+/// alone.
+struct AgeBounds([(i64, FileAge); 7]);
+
+impl AgeBounds {
+    fn new(zone: Option<String>, rule: Option<WeekRule>, now: Option<f64>) -> Result<AgeBounds, ValidationError> {
+        let zone = zone.as_deref();
+        let rule = rule.unwrap_or_default();
+        let now = Unitime::from_epoch_ms(now.map_or_else(crate::now_ms, |ms| ms as i64));
+        let today = now.zoned_days(zone)?;
+        let (year, month, _) = civil_from_days(today);
+        let week = rule.week_start(today)?;
+        let starts = [
+            (today + 1, FileAge::Future),
+            (today, FileAge::Today),
+            (today - 1, FileAge::Yesterday),
+            (week, FileAge::ThisWeek),
+            (week - 7, FileAge::LastWeek),
+            (days_from_civil(year, month, 1), FileAge::ThisMonth),
+            (days_from_civil(year, 1, 1), FileAge::ThisYear),
+        ];
+        let mut bounds = [(0, FileAge::Older); 7];
+        for (bound, (days, age)) in bounds.iter_mut().zip(starts) {
+            *bound = (zoned_midnight(days, zone)?.epoch_ms(), age);
+        }
+        Ok(AgeBounds(bounds))
+    }
+
+    fn age(&self, ms: i64) -> FileAge {
+        self.0.iter().find(|&&(start, _)| ms >= start).map_or(FileAge::Older, |&(_, age)| age)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the group a file listing shows the stored time under, relative to `now` in epoch milliseconds,
+    /// or to the current time if omitted: today, yesterday, earlier this week under `rule`, ISO 8601 by
+    /// default, last week, earlier this month, earlier this year, or older, on the wall clock of the IANA
+    /// zone `zone`, or of the local zone if omitted.
+    /// # Examples
+    /// ```
+    /// Unitime.fromJsTimestamp(file.lastModified).fileAge(); // "yesterday"
+    /// ```
+    #[wasm_bindgen(js_name = "fileAge")]
+    pub fn file_age(
+        &self,
+        zone: Option<String>,
+        rule: Option<WeekRule>,
+        now: Option<f64>,
+    ) -> Result<FileAge, ValidationError> {
+        Ok(AgeBounds::new(zone, rule, now)?.age(self.epoch_ms()))
+    }
+
+    /// Get the `fileAge` group of each of `timestamps` in epoch milliseconds, such as the
+    /// `lastModified` of the files in a listing. The time zone is looked up only for the group bounds,
+    /// not for each timestamp, so this is quick for large listings. `NaN` timestamps are `"older"`.
+    /// # Examples
+    /// ```
+    /// const ages = Unitime.fileAges(files.map(f => f.lastModified));
+    /// const today = files.filter((f, i) => ages[i] === "today");
+    /// ```
+    #[wasm_bindgen(js_name = "fileAges", unchecked_return_type = "FileAge[]")]
+    pub fn file_ages(
+        timestamps: Vec<f64>,
+        zone: Option<String>,
+        rule: Option<WeekRule>,
+        now: Option<f64>,
+    ) -> Result<Vec<JsValue>, ValidationError> {
+        let bounds = AgeBounds::new(zone, rule, now)?;
+        let age = |ms: f64| if ms.is_nan() { FileAge::Older } else { bounds.age(ms.floor() as i64) };
+        Ok(timestamps.into_iter().map(|ms| age(ms).into()).collect())
+    }
+}
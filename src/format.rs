@@ -0,0 +1,218 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_from_civil, weekday, year_str, DateTime, MS_PER_DAY, MS_PER_MIN};
+use crate::locale::Locale;
+use crate::partial_date::{DatePrecision, PartialDate};
+use crate::validation::ValidationError;
+use crate::week::WeekRule;
+use crate::Unitime;
+
+/// Preset levels of detail for `Unitime.formatStyle`, as in the `dateStyle` and `timeStyle` options of `Intl.DateTimeFormat`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormatStyle {
+    Short = "short",
+    Medium = "medium",
+    Long = "long",
+    Full = "full",
+}
+
+impl FormatStyle {
+    fn index(self) -> Result<usize, ValidationError> {
+        match self {
+            FormatStyle::Short => Ok(0),
+            FormatStyle::Medium => Ok(1),
+            FormatStyle::Long => Ok(2),
+            FormatStyle::Full => Ok(3),
+            _ => Err(ValidationError::new("unknown format style")),
+        }
+    }
+}
+
+/// Name a UTC offset the way long time styles do, e.g. "GMT+2" or "GMT-3:30", or "UTC" for UTC itself.
+fn offset_name(offset_min: i64, utc: bool) -> String {
+    let (hours, minutes) = (offset_min.abs() / 60, offset_min.abs() % 60);
+    let sign = if offset_min < 0 { '-' } else { '+' };
+    match (utc, offset_min, minutes) {
+        (true, _, _) => "UTC".to_string(),
+        (false, 0, _) => "GMT".to_string(),
+        (false, _, 0) => format!("GMT{sign}{hours}"),
+        _ => format!("GMT{sign}{hours}:{minutes:02}"),
+    }
+}
+
+/// Render `pattern`, with strftime-style `%` specifiers, for the wall-clock reading `wall_ms` whose
+/// offset from UTC is `offset_min` minutes, numbering weeks by `week_rule`. Bad specifiers are syntax
+/// errors at the character offset of their `%`.
+pub(crate) fn render(
+    pattern: &str,
+    wall_ms: i64,
+    offset_min: i64,
+    locale: &Locale,
+    week_rule: WeekRule,
+) -> Result<String, ValidationError> {
+    let dt = DateTime::from_epoch_ms(wall_ms);
+    let days = wall_ms.div_euclid(MS_PER_DAY);
+    let mut out = String::with_capacity(pattern.len() * 2);
+    let mut chars = pattern.chars().enumerate();
+    while let Some((at, c)) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let syntax = |message: &str| ValidationError::syntax(message, at);
+        let mut spec = chars.next().ok_or_else(|| syntax("pattern ends with %"))?.1;
+        let pad = spec != '-';
+        if !pad {
+            spec = chars.next().ok_or_else(|| syntax("pattern ends with %-"))?.1;
+        }
+        let number = |n: u32, width: usize| if pad { format!("{n:0width$}") } else { n.to_string() };
+        match spec {
+            'Y' => out.push_str(&year_str(dt.year)),
+            'N' => out.push_str(&(if dt.year > 0 { dt.year } else { 1 - dt.year }).to_string()),
+            'E' => out.push_str(if dt.year > 0 { "AD" } else { "BC" }),
+            'Q' => out.push_str(if dt.year > 0 { "CE" } else { "BCE" }),
+            'y' => out.push_str(&number(dt.year.rem_euclid(100) as u32, 2)),
+            'm' => out.push_str(&number(dt.month, 2)),
+            'B' => out.push_str(locale.months[dt.month as usize - 1]),
+            'b' => out.push_str(locale.months_short[dt.month as usize - 1]),
+            'd' => out.push_str(&number(dt.day, 2)),
+            'e' => out.push_str(&format!("{:2}", dt.day)),
+            'o' => out.push_str(&(locale.ordinal)(dt.day)),
+            'j' => out.push_str(&number((days - days_from_civil(dt.year, 1, 1) + 1) as u32, 3)),
+            'A' => out.push_str(locale.weekdays[weekday(days)]),
+            'a' => out.push_str(locale.weekdays_short[weekday(days)]),
+            'u' => out.push_str(&((weekday(days) + 6) % 7 + 1).to_string()),
+            'w' => out.push_str(&weekday(days).to_string()),
+            'V' => out.push_str(&number(week_rule.week_of(days)?.1, 2)),
+            'G' => out.push_str(&year_str(week_rule.week_of(days)?.0)),
+            'H' => out.push_str(&number(dt.hour, 2)),
+            'I' => out.push_str(&number((dt.hour + 11) % 12 + 1, 2)),
+            'p' => out.push_str(if dt.hour < 12 { "AM" } else { "PM" }),
+            'M' => out.push_str(&number(dt.minute, 2)),
+            'S' => out.push_str(&number(dt.second, 2)),
+            'L' => out.push_str(&format!("{:03}", dt.milli)),
+            'z' => {
+                let sign = if offset_min < 0 { '-' } else { '+' };
+                out.push_str(&format!("{sign}{:02}{:02}", offset_min.abs() / 60, offset_min.abs() % 60));
+            }
+            's' => out.push_str(&(wall_ms - offset_min * MS_PER_MIN).div_euclid(1000).to_string()),
+            'F' => out.push_str(&format!("{}-{:02}-{:02}", year_str(dt.year), dt.month, dt.day)),
+            'T' => out.push_str(&format!("{:02}:{:02}:{:02}", dt.hour, dt.minute, dt.second)),
+            '%' => out.push('%'),
+            other => return Err(syntax(&format!("unknown format specifier %{other}"))),
+        }
+    }
+    Ok(out)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Format the stored time with a strftime-style `pattern`, in local time unless `utc` is `true`,
+    /// naming months and weekdays in `locale` (English by default).
+    ///
+    /// | Specifier | Meaning | Example |
+    /// |-----------|---------|---------|
+    /// | `%Y` `%y` | year, two-digit year | `2024` `24` |
+    /// | `%N` `%E` `%Q` | year of the era, era, common era | `44` `BC` `BCE` |
+    /// | `%m` `%B` `%b` | month number, name, short name | `06` `June` `Jun` |
+    /// | `%d` `%e` `%o` | day, space-padded day, ordinal day | `03` ` 3` `3rd` |
+    /// | `%j` | day of the year | `155` |
+    /// | `%A` `%a` | weekday name, short name | `Monday` `Mon` |
+    /// | `%u` `%w` | weekday number from Monday = 1, from Sunday = 0 | `1` `1` |
+    /// | `%V` `%G` | week of the year, and the year it belongs to | `23` `2024` |
+    /// | `%H` `%I` `%p` | 24-hour and 12-hour hour, AM/PM | `14` `02` `PM` |
+    /// | `%M` `%S` `%L` | minute, second, millisecond | `05` `09` `120` |
+    /// | `%z` `%s` | UTC offset, epoch seconds | `+0200` `1717416309` |
+    /// | `%F` `%T` `%%` | `%Y-%m-%d`, `%H:%M:%S`, a literal `%` | |
+    ///
+    /// `%Y` counts years astronomically, so 1 BC is `0000` and 44 BC is `-0043`; `%N %E` writes them as
+    /// `1 BC` and `44 BC`. A `-` after the `%` drops the zero padding of a number, as in `%-d`. Ordinals
+    /// follow the language: "3rd" in English, "3." in German, "1er" but "3" in French. Weeks are numbered
+    /// by `weekRule` as in `getWeek`, ISO 8601 by default.
+    /// # Examples
+    /// ```
+    /// new Unitime().format("%B %o, %Y"); // "June 3rd, 2024"
+    /// new Unitime().format("%A %-d %B", false, "fr"); // "lundi 3 juin"
+    /// new Unitime().format("%G-W%V-%u"); // "2024-W23-1"
+    /// ```
+    pub fn format(
+        &self,
+        pattern: &str,
+        utc: Option<bool>,
+        locale: Option<String>,
+        week_rule: Option<WeekRule>,
+    ) -> Result<String, ValidationError> {
+        let utc = utc.unwrap_or(false);
+        let wall = self.wall_ms(utc);
+        let offset_min = (wall - self.epoch_ms()) / MS_PER_MIN;
+        let locale = Locale::get(locale.as_deref());
+        render(pattern, wall, offset_min, locale, week_rule.unwrap_or_default())
+    }
+
+    /// Format the stored time in one of the preset styles of `locale` (English by default), in local time
+    /// unless `utc` is `true`. `dateStyle` and `timeStyle` work like the options of the same names of
+    /// `Intl.DateTimeFormat`, and at least one of them must be given.
+    /// # Examples
+    /// ```
+    /// new Unitime().formatStyle("medium"); // "Jun 3, 2024"
+    /// new Unitime().formatStyle("full", "short"); // "Monday, June 3, 2024 at 2:05 PM"
+    /// new Unitime().formatStyle(undefined, "long", true, "de"); // "14:05:09 UTC"
+    /// ```
+    #[wasm_bindgen(js_name = "formatStyle")]
+    pub fn format_style(
+        &self,
+        date_style: Option<FormatStyle>,
+        time_style: Option<FormatStyle>,
+        utc: Option<bool>,
+        locale: Option<String>,
+    ) -> Result<String, ValidationError> {
+        let utc = utc.unwrap_or(false);
+        let locale = Locale::get(locale.as_deref());
+        let wall = self.wall_ms(utc);
+        let offset_min = (wall - self.epoch_ms()) / MS_PER_MIN;
+        let render = |pattern| render(pattern, wall, offset_min, locale, WeekRule::Iso);
+        let time = match time_style {
+            Some(style) => {
+                let index = style.index()?;
+                let time = render(locale.time_styles[index.min(1)])?;
+                Some(if index >= 2 { format!("{time} {}", offset_name(offset_min, utc)) } else { time })
+            }
+            None => None,
+        };
+        match (date_style, time) {
+            (Some(style), Some(time)) => {
+                let index = style.index()?;
+                let date = render(locale.date_styles[index])?;
+                Ok(format!("{date}{}{time}", locale.date_time_separators[index / 2]))
+            }
+            (Some(style), None) => render(locale.date_styles[style.index()?]),
+            (None, Some(time)) => Ok(time),
+            (None, None) => Err(ValidationError::new("a date or time style is required")),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl PartialDate {
+    /// Format the date to its precision in `locale` (English by default): the year alone, the month and
+    /// year, or the full date in `style`, long by default. Years before 1 AD are written with an era.
+    /// # Examples
+    /// ```
+    /// PartialDate.fromString("1871").format(); // "1871"
+    /// PartialDate.fromString("1871-06").format("fr"); // "juin 1871"
+    /// PartialDate.fromString("1871-06-05").format(undefined, "medium"); // "Jun 5, 1871"
+    /// new PartialDate(-43, 3, 15).format(); // "March 15, 44 BC"
+    /// ```
+    pub fn format(&self, locale: Option<String>, style: Option<FormatStyle>) -> Result<String, ValidationError> {
+        let locale = Locale::get(locale.as_deref());
+        let pattern = match self.precision() {
+            DatePrecision::Year => "%Y",
+            DatePrecision::Month => locale.month_year,
+            _ => locale.date_styles[style.unwrap_or(FormatStyle::Long).index()?],
+        };
+        let pattern = if self.year() < 1 { pattern.replace("%Y", "%N %E") } else { pattern.to_string() };
+        let wall = self.day_range().0 * MS_PER_DAY;
+        render(&pattern, wall, 0, locale, WeekRule::Iso)
+    }
+}
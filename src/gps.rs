@@ -0,0 +1,80 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_from_civil, MS_PER_DAY, MS_PER_SEC};
+use crate::tai::{tai_minus_utc, tai_to_utc};
+use crate::Unitime;
+
+/// GPS time was TAI − 19 s when it started, and has not taken leap seconds since.
+const TAI_MINUS_GPS: i64 = 19;
+const MS_PER_WEEK: i64 = 7 * MS_PER_DAY;
+
+/// Start of GPS week 0, 1980-01-06 00:00:00 UTC, in UTC epoch milliseconds.
+fn gps_epoch_ms() -> i64 {
+    days_from_civil(1980, 1, 6) * MS_PER_DAY
+}
+
+/// A GPS timestamp: the week since 1980-01-06 and the seconds into it.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GpsTime {
+    week: i32,
+    time_of_week: f64,
+    leap_seconds: i32,
+}
+
+#[wasm_bindgen]
+impl GpsTime {
+    /// Get the GPS week number, counted from 1980-01-06 without the 1024-week rollover of the broadcast
+    /// signal. Times before 1980 have negative weeks.
+    #[wasm_bindgen(getter)]
+    pub fn week(&self) -> i32 {
+        self.week
+    }
+
+    /// Get the seconds since the start of the week at Saturday/Sunday midnight GPS time, with fraction.
+    #[wasm_bindgen(getter, js_name = "timeOfWeek")]
+    pub fn time_of_week(&self) -> f64 {
+        self.time_of_week
+    }
+
+    /// Get how many seconds GPS time was ahead of UTC at this time, 18 since 2017.
+    #[wasm_bindgen(getter, js_name = "leapSeconds")]
+    pub fn leap_seconds(&self) -> i32 {
+        self.leap_seconds
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the stored time as a GPS week number and time of week, including the offset of GPS time from
+    /// UTC by the leap seconds since 1980.
+    /// # Examples
+    /// ```
+    /// const gps = new Unitime().toGPS();
+    /// console.log(gps.week, gps.timeOfWeek);
+    /// ```
+    #[wasm_bindgen(js_name = "toGPS")]
+    pub fn to_gps(&self) -> GpsTime {
+        let ms = self.epoch_ms();
+        let leap_seconds = tai_minus_utc(ms) - TAI_MINUS_GPS;
+        let gps_ms = ms.saturating_add(leap_seconds * MS_PER_SEC - gps_epoch_ms());
+        GpsTime {
+            week: gps_ms.div_euclid(MS_PER_WEEK) as i32,
+            time_of_week: gps_ms.rem_euclid(MS_PER_WEEK) as f64 / MS_PER_SEC as f64,
+            leap_seconds: leap_seconds as i32,
+        }
+    }
+
+    /// Creates a new `Unitime` from a GPS week number and seconds into the week, as in GPS receiver and
+    /// telemetry data. `timeOfWeek` may have a fraction and may run past the end of the week.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromGPS(2300, 345_600);
+    /// ```
+    #[wasm_bindgen(js_name = "fromGPS")]
+    pub fn from_gps(week: i32, time_of_week: f64) -> Unitime {
+        let gps_ms = (week as i64 * MS_PER_WEEK).saturating_add((time_of_week * MS_PER_SEC as f64).floor() as i64);
+        let tai_ms = gps_ms.saturating_add(gps_epoch_ms() + TAI_MINUS_GPS * MS_PER_SEC);
+        Unitime::from_epoch_ms(tai_to_utc(tai_ms))
+    }
+}
@@ -0,0 +1,160 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+struct State {
+    interval_ms: i64,
+    last_ms: i64,
+    /// Missed beats already reported to the callback since the last beat.
+    reported: i64,
+    on_missed: Option<Function>,
+    timer: Option<JsValue>,
+    #[cfg(js)]
+    tick: Option<Closure<dyn FnMut()>>,
+}
+
+impl State {
+    fn missed(&self, now: i64) -> i64 {
+        (now.saturating_sub(self.last_ms) / self.interval_ms).max(0)
+    }
+}
+
+/// Report beats missed since the last report, then wait for the next interval to pass.
+#[cfg(js)]
+fn check(state: &Rc<RefCell<State>>) {
+    let report = {
+        let mut s = state.borrow_mut();
+        let missed = s.missed(crate::now_ms());
+        if missed > s.reported {
+            s.reported = missed;
+            s.on_missed.clone().map(|callback| (callback, missed))
+        } else {
+            None
+        }
+    };
+    if let Some((callback, missed)) = report {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(missed as f64));
+    }
+    arm(state);
+}
+
+/// Replace the pending timeout with one for the next missed beat, if there is a callback.
+#[cfg(js)]
+fn arm(state: &Rc<RefCell<State>>) {
+    let mut s = state.borrow_mut();
+    if let Some(timer) = s.timer.take() {
+        crate::timer::clear_timeout(&timer);
+    }
+    if s.on_missed.is_none() {
+        return;
+    }
+    let due = s.last_ms.saturating_add(s.reported.saturating_add(1).saturating_mul(s.interval_ms));
+    let weak = Rc::downgrade(state);
+    let tick = s.tick.get_or_insert_with(|| {
+        Closure::new(move || {
+            if let Some(state) = weak.upgrade() {
+                state.borrow_mut().timer = None;
+                check(&state);
+            }
+        })
+    });
+    s.timer = crate::timer::set_timeout(tick, due.saturating_sub(crate::now_ms()));
+}
+
+/// There are no timers outside a JS host.
+#[cfg(not(js))]
+fn arm(state: &Rc<RefCell<State>>) {
+    state.borrow_mut().timer = None;
+}
+
+/// Connection health for a WebSocket or any other link that should hear from its peer at a regular
+/// interval. Call `beat()` on every pong or message; the heartbeat tracks the time since, and can call
+/// back as beats are missed. Every method taking `now` uses the current time in epoch milliseconds if it
+/// is omitted.
+/// # Examples
+/// ```
+/// const health = new Heartbeat(15000);
+/// socket.onmessage = () => health.beat();
+/// health.onMissed(missed => missed >= 2 ? reconnect() : ping());
+/// ```
+#[wasm_bindgen]
+pub struct Heartbeat {
+    state: Rc<RefCell<State>>,
+}
+
+#[wasm_bindgen]
+impl Heartbeat {
+    /// Creates a new `Heartbeat` expecting a beat every `intervalMs` milliseconds, counting as if a beat
+    /// just arrived.
+    #[wasm_bindgen(constructor)]
+    pub fn new(interval_ms: f64) -> Result<Heartbeat, ValidationError> {
+        if !(interval_ms >= 1.0 && interval_ms.is_finite()) {
+            return Err(ValidationError::new("heartbeat interval must be at least 1 ms"));
+        }
+        let state = State {
+            interval_ms: interval_ms as i64,
+            last_ms: crate::now_ms(),
+            reported: 0,
+            on_missed: None,
+            timer: None,
+            #[cfg(js)]
+            tick: None,
+        };
+        Ok(Heartbeat { state: Rc::new(RefCell::new(state)) })
+    }
+
+    /// Record a beat at `now`.
+    pub fn beat(&self, now: Option<f64>) {
+        {
+            let mut s = self.state.borrow_mut();
+            s.last_ms = now.map_or_else(crate::now_ms, |ms| ms.floor() as i64);
+            s.reported = 0;
+        }
+        arm(&self.state);
+    }
+
+    /// Get the time of the last beat.
+    #[wasm_bindgen(getter, js_name = "lastBeat")]
+    pub fn last_beat(&self) -> Unitime {
+        Unitime::from_epoch_ms(self.state.borrow().last_ms)
+    }
+
+    /// Get the time from the last beat to `now`.
+    #[wasm_bindgen(js_name = "timeSinceLastBeat")]
+    pub fn time_since_last_beat(&self, now: Option<f64>) -> UniDuration {
+        let now = now.map_or_else(crate::now_ms, |ms| ms.floor() as i64);
+        UniDuration::from_nanos(now.saturating_sub(self.state.borrow().last_ms).saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Check whether more than `thresholdMs` milliseconds have passed since the last beat.
+    #[wasm_bindgen(js_name = "isStale")]
+    pub fn is_stale(&self, threshold_ms: f64, now: Option<f64>) -> bool {
+        self.time_since_last_beat(now).millis() > threshold_ms
+    }
+
+    /// Get the number of whole intervals that have passed since the last beat.
+    pub fn missed(&self, now: Option<f64>) -> f64 {
+        let now = now.map_or_else(crate::now_ms, |ms| ms.floor() as i64);
+        self.state.borrow().missed(now) as f64
+    }
+
+    /// Call `callback` with the number of missed beats each time another interval passes without a
+    /// beat, replacing any earlier callback. It first runs one interval after the last beat.
+    #[wasm_bindgen(js_name = "onMissed")]
+    pub fn on_missed(&self, callback: Function) {
+        self.state.borrow_mut().on_missed = Some(callback);
+        arm(&self.state);
+    }
+
+    /// Stop calling back on missed beats.
+    pub fn stop(&self) {
+        self.state.borrow_mut().on_missed = None;
+        arm(&self.state);
+    }
+}
@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::{char_offset, ValidationError};
+
+/// A remote timestamp further ahead of the local clock than this is rejected by default.
+const DEFAULT_MAX_DRIFT_MS: f64 = 60_000.0;
+
+/// A hybrid logical clock timestamp: physical milliseconds, a logical counter and the node that issued it.
+/// Timestamps order by time, then counter, then node, and their string encoding sorts the same way.
+#[wasm_bindgen(js_name = "HLCTimestamp")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HlcTimestamp {
+    millis: u64,
+    counter: u16,
+    node: String,
+}
+
+impl Ord for HlcTimestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.millis, self.counter, &self.node).cmp(&(other.millis, other.counter, &other.node))
+    }
+}
+
+impl PartialOrd for HlcTimestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[wasm_bindgen(js_class = "HLCTimestamp")]
+impl HlcTimestamp {
+    /// Get the physical component in epoch milliseconds.
+    #[wasm_bindgen(getter)]
+    pub fn millis(&self) -> f64 {
+        self.millis as f64
+    }
+
+    /// Get the logical counter.
+    #[wasm_bindgen(getter)]
+    pub fn counter(&self) -> u16 {
+        self.counter
+    }
+
+    /// Get the ID of the node that issued the timestamp.
+    #[wasm_bindgen(getter)]
+    pub fn node(&self) -> String {
+        self.node.clone()
+    }
+
+    /// Encode the timestamp as `<12 hex digit millis>-<4 hex digit counter>-<node>`, which sorts lexicographically in timestamp order.
+    /// # Examples
+    /// ```
+    /// const key = clock.now().encode(); // "0191a2b3c4d5-0000-tab-1"
+    /// ```
+    pub fn encode(&self) -> String {
+        format!("{:012x}-{:04x}-{}", self.millis, self.counter, self.node)
+    }
+
+    /// Decode a timestamp produced by `encode`.
+    /// # Examples
+    /// ```
+    /// const ts = HLCTimestamp.decode("0191a2b3c4d5-0000-tab-1");
+    /// ```
+    pub fn decode(s: &str) -> Result<HlcTimestamp, ValidationError> {
+        let syntax = |at| ValidationError::syntax(format!("invalid HLC timestamp: {s}"), char_offset(s, at));
+        // The first 18 bytes are 12 hex digits, a dash, 4 hex digits and a dash
+        let fits = |i: usize| {
+            s.as_bytes().get(i).is_some_and(|&b| if i == 12 || i == 17 { b == b'-' } else { b.is_ascii_hexdigit() })
+        };
+        if let Some(at) = (0..18).find(|&i| !fits(i)) {
+            return Err(syntax(at));
+        }
+        Ok(HlcTimestamp {
+            millis: u64::from_str_radix(&s[..12], 16).map_err(|_| syntax(0))?,
+            counter: u16::from_str_radix(&s[13..17], 16).map_err(|_| syntax(13))?,
+            node: s[18..].to_string(),
+        })
+    }
+
+    /// Compare with another timestamp, returning -1, 0 or 1.
+    pub fn compare(&self, other: &HlcTimestamp) -> i32 {
+        self.cmp(other) as i32
+    }
+}
+
+/// Hybrid logical clock that combines the physical clock with a logical counter, so that timestamps
+/// issued across tabs or peers respect causality while staying close to wall-clock time.
+#[wasm_bindgen(js_name = "HLC")]
+pub struct Hlc {
+    node: String,
+    max_drift_ms: f64,
+    millis: u64,
+    counter: u16,
+}
+
+impl Hlc {
+    /// The error for a counter that would pass `u16::MAX`.
+    fn overflow() -> ValidationError {
+        ValidationError::range("counter", u16::MAX as i64 + 1, 0, u16::MAX as i64)
+    }
+
+    fn physical() -> u64 {
+        crate::now_ms().max(0) as u64
+    }
+
+    fn stamp(&self) -> HlcTimestamp {
+        HlcTimestamp { millis: self.millis, counter: self.counter, node: self.node.clone() }
+    }
+}
+
+#[wasm_bindgen(js_class = "HLC")]
+impl Hlc {
+    /// Creates a new `HLC` for the node `node`, rejecting remote timestamps more than `max_drift_ms`
+    /// (60 seconds by default) ahead of the local clock. `Infinity` accepts any remote timestamp.
+    /// # Examples
+    /// ```
+    /// const clock = new HLC(crypto.randomUUID());
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(node: String, max_drift_ms: Option<f64>) -> Result<Hlc, ValidationError> {
+        if node.is_empty() {
+            return Err(ValidationError::new("node ID must not be empty"));
+        }
+        let max_drift_ms = max_drift_ms.unwrap_or(DEFAULT_MAX_DRIFT_MS);
+        if max_drift_ms.is_nan() || max_drift_ms < 0.0 {
+            return Err(ValidationError::new("maximum drift must be a non-negative number of milliseconds"));
+        }
+        Ok(Hlc { node, max_drift_ms, millis: 0, counter: 0 })
+    }
+
+    /// Issue a timestamp for a local event.
+    /// # Examples
+    /// ```
+    /// doc.set(key, value, clock.now().encode());
+    /// ```
+    pub fn now(&mut self) -> Result<HlcTimestamp, ValidationError> {
+        let pt = Hlc::physical();
+        if pt > self.millis {
+            self.millis = pt;
+            self.counter = 0;
+        } else {
+            self.counter = self.counter.checked_add(1).ok_or_else(Hlc::overflow)?;
+        }
+        Ok(self.stamp())
+    }
+
+    /// Merge a timestamp received from another node and issue a timestamp for the receive event,
+    /// which is later than both the remote timestamp and every timestamp issued before.
+    /// # Examples
+    /// ```
+    /// clock.update(HLCTimestamp.decode(message.hlc));
+    /// ```
+    pub fn update(&mut self, remote: &HlcTimestamp) -> Result<HlcTimestamp, ValidationError> {
+        let pt = Hlc::physical();
+        if remote.millis as f64 - pt as f64 > self.max_drift_ms {
+            let latest = (pt as f64 + self.max_drift_ms) as i64;
+            return Err(ValidationError::range("millis", remote.millis as i64, 0, latest));
+        }
+        let millis = self.millis.max(remote.millis).max(pt);
+        let counter = if millis == self.millis && millis == remote.millis {
+            self.counter.max(remote.counter).checked_add(1)
+        } else if millis == self.millis {
+            self.counter.checked_add(1)
+        } else if millis == remote.millis {
+            remote.counter.checked_add(1)
+        } else {
+            Some(0)
+        };
+        self.counter = counter.ok_or_else(Hlc::overflow)?;
+        self.millis = millis;
+        Ok(self.stamp())
+    }
+
+    /// Get the latest timestamp issued, without advancing the clock.
+    #[wasm_bindgen(getter)]
+    pub fn last(&self) -> HlcTimestamp {
+        self.stamp()
+    }
+}
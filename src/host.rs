@@ -0,0 +1,143 @@
+//! Clocks and timers of the host the module runs in. JS runtimes differ in what they provide, so the
+//! runtime and its functions are looked up when first needed rather than imported: Bun's
+//! `Bun.nanoseconds()` is the finest monotonic clock there, browsers, Node and Deno have
+//! `performance.now()`, and some embedded and older server runtimes have neither. WASI runtimes and
+//! native builds use the Rust standard library.
+
+#[cfg(js)]
+use std::cell::Cell;
+
+#[cfg(js)]
+use js_sys::{Function, Reflect};
+use wasm_bindgen::prelude::*;
+#[cfg(js)]
+use wasm_bindgen::JsCast;
+
+use crate::Unitime;
+
+/// The JS runtime the module runs in, as `Unitime.runtime` tells.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsRuntime {
+    /// A browser page or web worker.
+    Browser = "browser",
+    Node = "node",
+    Deno = "deno",
+    Bun = "bun",
+    /// Another JS runtime, such as an embedded engine.
+    Other = "other",
+    /// No JS host, as under WASI or in a native build.
+    None = "none",
+}
+
+/// Get the property `key` of `value`, or `None` if it is `undefined` or `null`.
+#[cfg(js)]
+fn property(value: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(value, &key.into()).ok().filter(|v| !v.is_undefined() && !v.is_null())
+}
+
+/// Get the function `key` of `value` with `value` to call it on.
+#[cfg(js)]
+fn method(value: JsValue, key: &str) -> Option<(JsValue, Function)> {
+    let function = property(&value, key)?.dyn_into().ok()?;
+    Some((value, function))
+}
+
+#[cfg(js)]
+fn detect() -> JsRuntime {
+    let global: JsValue = js_sys::global().into();
+    let has_string = |path: &[&str]| {
+        path.iter().try_fold(global.clone(), |value, key| property(&value, key)).is_some_and(|v| v.is_string())
+    };
+    // Deno and Bun also set `process.versions.node` for Node compatibility, so they are asked for first.
+    if has_string(&["Deno", "version", "deno"]) {
+        JsRuntime::Deno
+    } else if has_string(&["Bun", "version"]) {
+        JsRuntime::Bun
+    } else if has_string(&["process", "versions", "node"]) {
+        JsRuntime::Node
+    } else if property(&global, "document").is_some() || property(&global, "importScripts").is_some() {
+        JsRuntime::Browser
+    } else {
+        JsRuntime::Other
+    }
+}
+
+#[cfg(js)]
+thread_local! {
+    static RUNTIME: JsRuntime = detect();
+    /// The function giving the monotonic clock, what to call it on, and its unit in milliseconds.
+    static MONOTONIC: Option<(JsValue, Function, f64)> = {
+        let global = js_sys::global();
+        let bun = || method(property(&global, "Bun")?, "nanoseconds").map(|(bun, f)| (bun, f, 1e-6));
+        let performance = || method(property(&global, "performance")?, "now").map(|(p, f)| (p, f, 1.0));
+        if RUNTIME.with(|runtime| *runtime) == JsRuntime::Bun { bun().or_else(performance) } else { performance() }
+    };
+    /// The last reading of `Date.now()` given as the monotonic clock, so that it never goes backward.
+    static LAST_DATE_NOW: Cell<f64> = const { Cell::new(f64::NEG_INFINITY) };
+    static SET_TIMEOUT: Option<(JsValue, Function)> = method(js_sys::global().into(), "setTimeout");
+    static CLEAR_TIMEOUT: Option<(JsValue, Function)> = method(js_sys::global().into(), "clearTimeout");
+}
+
+/// Read the monotonic clock in milliseconds: the finest clock the JS runtime has, or else `Date.now()`
+/// held back from going backward when the system clock is set back.
+#[cfg(js)]
+pub(crate) fn monotonic_ms() -> f64 {
+    let reading = MONOTONIC.with(|monotonic| {
+        let (this, now, unit) = monotonic.as_ref()?;
+        Some(now.call0(this).ok()?.as_f64()? * unit)
+    });
+    reading.unwrap_or_else(|| {
+        LAST_DATE_NOW.with(|last| {
+            last.set(last.get().max(js_sys::Date::now()));
+            last.get()
+        })
+    })
+}
+
+/// Read the monotonic clock in milliseconds since its first reading.
+#[cfg(not(js))]
+pub(crate) fn monotonic_ms() -> f64 {
+    use std::sync::OnceLock;
+    static ORIGIN: OnceLock<std::time::Instant> = OnceLock::new();
+    ORIGIN.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Call `callback` after `delay_ms` milliseconds with the runtime's `setTimeout`, returning the timer
+/// handle, a number in browsers and Deno and an object in Node and Bun, if there is one.
+#[cfg(all(feature = "timers", js))]
+pub(crate) fn set_timeout(callback: &JsValue, delay_ms: f64) -> Option<JsValue> {
+    SET_TIMEOUT.with(|set| {
+        let (this, set) = set.as_ref()?;
+        set.call2(this, callback, &delay_ms.into()).ok()
+    })
+}
+
+/// Cancel a timer started by `set_timeout`.
+#[cfg(all(feature = "timers", js))]
+pub(crate) fn clear_timeout(handle: &JsValue) {
+    CLEAR_TIMEOUT.with(|clear| {
+        if let Some((this, clear)) = clear {
+            let _ = clear.call1(this, handle);
+        }
+    });
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the JS runtime the module found itself in, whose clock and timers it uses.
+    /// # Examples
+    /// ```
+    /// Unitime.runtime(); // "node"
+    /// ```
+    pub fn runtime() -> JsRuntime {
+        #[cfg(js)]
+        {
+            RUNTIME.with(|runtime| *runtime)
+        }
+        #[cfg(not(js))]
+        {
+            JsRuntime::None
+        }
+    }
+}
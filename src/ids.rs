@@ -0,0 +1,224 @@
+use std::cell::Cell;
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::{char_offset, ValidationError};
+use crate::Unitime;
+
+/// Fill a buffer from the platform's secure random source (`crypto.getRandomValues` in browsers).
+pub(crate) fn random_bytes<const N: usize>() -> Result<[u8; N], ValidationError> {
+    let mut buf = [0u8; N];
+    getrandom::getrandom(&mut buf).map_err(|_| ValidationError::unavailable("no secure random source available"))?;
+    Ok(buf)
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+thread_local! {
+    /// Millisecond and counter of the last UUIDv7, used to keep IDs from one module instance ordered.
+    static LAST_UUIDV7: Cell<(u64, u16)> = const { Cell::new((0, 0)) };
+    /// Millisecond and 80-bit randomness of the last ULID, used for monotonic ordering within a millisecond.
+    static LAST_ULID: Cell<(u64, u128)> = const { Cell::new((0, 0)) };
+}
+
+/// Custom epoch of Twitter snowflake IDs, the default for `Unitime.fromSnowflake`.
+const TWITTER_EPOCH_MS: f64 = 1_288_834_974_657.0;
+
+/// Crockford's base32 alphabet used by ULIDs.
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn crockford_digit(c: u8) -> Option<u8> {
+    let c = match c.to_ascii_uppercase() {
+        b'O' => b'0',
+        b'I' | b'L' => b'1',
+        c => c,
+    };
+    CROCKFORD.iter().position(|&d| d == c).map(|i| i as u8)
+}
+
+/// Pick the millisecond and randomness for the next ULID. Within one millisecond the randomness of the
+/// previous ULID is incremented, as the ULID spec asks for monotonic generation.
+fn next_ulid_parts(now: u64) -> Result<(u64, u128), ValidationError> {
+    const MAX_RANDOM: u128 = (1 << 80) - 1;
+    LAST_ULID.with(|last| {
+        let (last_ms, last_random) = last.get();
+        let next = if now > last_ms {
+            let mut buf = [0u8; 16];
+            buf[6..].copy_from_slice(&random_bytes::<10>()?);
+            (now, u128::from_be_bytes(buf))
+        } else if last_random < MAX_RANDOM {
+            (last_ms, last_random + 1)
+        } else {
+            (last_ms + 1, 0)
+        };
+        last.set(next);
+        Ok(next)
+    })
+}
+
+/// Read the millisecond timestamp from the first 10 characters of a ULID.
+fn ulid_timestamp(s: &str) -> Result<u64, ValidationError> {
+    let syntax = |at| ValidationError::syntax(format!("invalid ULID: {s}"), char_offset(s, at));
+    let lead = s.len() - s.trim_start().len();
+    let trimmed = s.trim().as_bytes();
+    // 26 characters hold 130 bits, so a valid ULID's first character only uses its low three.
+    let mut value: u128 = 0;
+    for i in 0..26 {
+        let digit = trimmed.get(i).and_then(|&c| crockford_digit(c)).filter(|&digit| i > 0 || digit <= 7);
+        value = value << 5 | digit.ok_or_else(|| syntax(lead + i))? as u128;
+    }
+    if trimmed.len() > 26 {
+        return Err(syntax(lead + 26));
+    }
+    Ok((value >> 80) as u64)
+}
+
+/// Pick the next millisecond and 12-bit counter for a UUIDv7 so that IDs are strictly increasing,
+/// even when several are made within one millisecond or the clock steps back.
+fn next_uuidv7_counter(now: u64) -> Result<(u64, u16), ValidationError> {
+    LAST_UUIDV7.with(|last| {
+        let (last_ms, last_counter) = last.get();
+        let next = if now > last_ms {
+            // Start each millisecond in the lower half of the counter to leave room for increments.
+            (now, u16::from_be_bytes(random_bytes::<2>()?) & 0x07ff)
+        } else if last_counter < 0x0fff {
+            (last_ms, last_counter + 1)
+        } else {
+            (last_ms + 1, 0)
+        };
+        last.set(next);
+        Ok(next)
+    })
+}
+
+/// Format a 128-bit UUID in the canonical 8-4-4-4-12 form.
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let mut s = String::with_capacity(36);
+    for (i, b) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            s.push('-');
+        }
+        s.push_str(&format!("{b:02x}"));
+    }
+    s
+}
+
+/// Parse a UUID with or without hyphens.
+fn parse_uuid(s: &str) -> Result<[u8; 16], ValidationError> {
+    let syntax = |at| ValidationError::syntax(format!("invalid UUID: {s}"), char_offset(s, at));
+    let lead = s.len() - s.trim_start().len();
+    let trimmed = s.trim().as_bytes();
+    let (len, dashes): (usize, &[usize]) =
+        if trimmed.get(8) == Some(&b'-') { (36, &[8, 13, 18, 23]) } else { (32, &[]) };
+    let mut bytes = [0u8; 16];
+    let mut nibble = 0;
+    for i in 0..len {
+        let c = trimmed.get(i).copied();
+        if dashes.contains(&i) {
+            if c != Some(b'-') {
+                return Err(syntax(lead + i));
+            }
+            continue;
+        }
+        let digit = c.and_then(hex_digit).ok_or_else(|| syntax(lead + i))?;
+        bytes[nibble / 2] |= if nibble % 2 == 0 { digit << 4 } else { digit };
+        nibble += 1;
+    }
+    if trimmed.len() > len {
+        return Err(syntax(lead + len));
+    }
+    Ok(bytes)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Generate an RFC 9562 version 7 UUID from the current time. IDs generated by the same module instance
+    /// are strictly increasing, so they sort in creation order.
+    /// # Examples
+    /// ```
+    /// const id = Unitime.uuidv7();
+    /// ```
+    pub fn uuidv7() -> Result<String, ValidationError> {
+        let (ms, counter) = next_uuidv7_counter(crate::now_ms().max(0) as u64)?;
+        let mut bytes = [0u8; 16];
+        bytes[..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+        bytes[6..8].copy_from_slice(&(0x7000 | counter).to_be_bytes());
+        bytes[8..].copy_from_slice(&random_bytes::<8>()?);
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Ok(format_uuid(&bytes))
+    }
+
+    /// Creates a new `Unitime` with the creation time embedded in a version 7 UUID.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromUuidv7("01918d6a-7b3e-7cc4-9f1b-0a8d3d2c4e5f");
+    /// ```
+    #[wasm_bindgen(js_name = "fromUuidv7")]
+    pub fn from_uuidv7(s: &str) -> Result<Unitime, ValidationError> {
+        let bytes = parse_uuid(s)?;
+        if bytes[6] >> 4 != 7 || bytes[8] >> 6 != 0b10 {
+            return Err(ValidationError::new(format!("not a version 7 UUID: {s}")));
+        }
+        let mut ms = [0u8; 8];
+        ms[2..].copy_from_slice(&bytes[..6]);
+        Ok(Unitime::from_epoch_ms(u64::from_be_bytes(ms) as i64))
+    }
+
+    /// Generate a ULID from the current time. ULIDs generated within the same millisecond by the same module
+    /// instance are strictly increasing, so they sort in creation order.
+    /// # Examples
+    /// ```
+    /// const id = Unitime.ulid();
+    /// ```
+    pub fn ulid() -> Result<String, ValidationError> {
+        let (ms, random) = next_ulid_parts(crate::now_ms().max(0) as u64)?;
+        let value = (ms as u128) << 80 | random;
+        Ok((0..26).rev().map(|i| CROCKFORD[(value >> (i * 5)) as usize & 0x1f] as char).collect())
+    }
+
+    /// Get the creation time embedded in a ULID in epoch milliseconds.
+    /// # Examples
+    /// ```
+    /// const created = Unitime.timestampFromUlid("01ARZ3NDEKTSV4RRFFQ69G5FAV");
+    /// ```
+    #[wasm_bindgen(js_name = "timestampFromUlid")]
+    pub fn timestamp_from_ulid(s: &str) -> Result<f64, ValidationError> {
+        Ok(ulid_timestamp(s)? as f64)
+    }
+
+    /// Creates a new `Unitime` with the creation time embedded in a ULID.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromUlid("01ARZ3NDEKTSV4RRFFQ69G5FAV");
+    /// ```
+    #[wasm_bindgen(js_name = "fromUlid")]
+    pub fn from_ulid(s: &str) -> Result<Unitime, ValidationError> {
+        Ok(Unitime::from_epoch_ms(ulid_timestamp(s)? as i64))
+    }
+
+    /// Creates a new `Unitime` with the creation time embedded in a Twitter/Discord-style snowflake ID,
+    /// whose top 42 bits count milliseconds since `epoch_offset_ms`. The offset defaults to the Twitter epoch;
+    /// Discord uses `1420070400000`.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromSnowflake(BigInt(message.id), 1420070400000);
+    /// ```
+    #[wasm_bindgen(js_name = "fromSnowflake")]
+    pub fn from_snowflake(id: u64, epoch_offset_ms: Option<f64>) -> Result<Unitime, ValidationError> {
+        let offset = epoch_offset_ms.unwrap_or(TWITTER_EPOCH_MS);
+        if !offset.is_finite() {
+            return Err(ValidationError::new("snowflake epoch offset must be a finite number"));
+        }
+        // Float to integer casts saturate, so offsets too large for epoch milliseconds fail here too.
+        let (ms, offset) = ((id >> 22) as i64, offset as i64);
+        let out_of_range = || ValidationError::range("epochOffsetMs", offset, i64::MIN, i64::MAX - ms);
+        Ok(Unitime::from_epoch_ms(ms.checked_add(offset).ok_or_else(out_of_range)?))
+    }
+}
@@ -0,0 +1,84 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::UniDuration;
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Closed range of time from `start` to `end`, both included, such as the window in which a booking
+/// may be made.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UniInterval {
+    /// Nanoseconds since 1970 as in `Unitime`, with `start <= end`.
+    start: i128,
+    end: i128,
+}
+
+impl UniInterval {
+    pub(crate) fn from_nanos(start: i128, end: i128) -> Result<UniInterval, ValidationError> {
+        if start > end {
+            return Err(ValidationError::new("the interval ends before it starts"));
+        }
+        Ok(UniInterval { start, end })
+    }
+}
+
+#[wasm_bindgen]
+impl UniInterval {
+    /// Creates a new `UniInterval` from `start` to `end`, which must not be before `start`.
+    /// # Examples
+    /// ```
+    /// const window = new UniInterval(new Unitime().fromEpochMil(opensAt), new Unitime().fromEpochMil(closesAt));
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(start: &Unitime, end: &Unitime) -> Result<UniInterval, ValidationError> {
+        UniInterval::from_nanos(start.nanos, end.nanos)
+    }
+
+    /// Get the start of the interval.
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> Unitime {
+        Unitime::from_nanos(self.start)
+    }
+
+    /// Get the end of the interval.
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> Unitime {
+        Unitime::from_nanos(self.end)
+    }
+
+    /// Get the length of the interval, at most about 292 years.
+    #[wasm_bindgen(getter)]
+    pub fn duration(&self) -> UniDuration {
+        UniDuration::from_nanos((self.end - self.start).min(i64::MAX as i128) as i64)
+    }
+
+    /// Check whether `t` lies in the interval, counting both ends.
+    pub fn contains(&self, t: &Unitime) -> bool {
+        (self.start..=self.end).contains(&t.nanos)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get a new `Unitime` with the stored time moved into `interval`: the start if it is earlier, the
+    /// end if it is later, or the same time if it is already inside.
+    /// # Examples
+    /// ```
+    /// const picked = new Unitime().fromEpochMil(picker.valueAsNumber).clampToInterval(bookingWindow);
+    /// ```
+    #[wasm_bindgen(js_name = "clampToInterval")]
+    pub fn clamp_to_interval(&self, interval: &UniInterval) -> Unitime {
+        Unitime::from_nanos(self.nanos.clamp(interval.start, interval.end))
+    }
+
+    /// Get a new `Unitime` with `value` moved to `min` if it is earlier or to `max` if it is later. Fails
+    /// if `max` is before `min`.
+    /// # Examples
+    /// ```
+    /// Unitime.clamp(picked, new Unitime(), lastBookable);
+    /// ```
+    pub fn clamp(value: &Unitime, min: &Unitime, max: &Unitime) -> Result<Unitime, ValidationError> {
+        Ok(value.clamp_to_interval(&UniInterval::from_nanos(min.nanos, max.nanos)?))
+    }
+}
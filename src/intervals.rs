@@ -0,0 +1,111 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::Unitime;
+
+/// Set of half-open time ranges `[start, end)` in epoch milliseconds, such as nights, weekends or
+/// maintenance windows. Overlapping and touching ranges are merged as they are added.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct IntervalSet {
+    /// Disjoint, non-touching ranges sorted by start.
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub(crate) fn insert(&mut self, start: i64, end: i64) {
+        if start >= end {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        let merged = self.ranges[first..last]
+            .iter()
+            .fold((start, end), |(s, e), &(rs, re)| (s.min(rs), e.max(re)));
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Milliseconds of `[start, end)` covered by the set, saturating at `i64::MAX`.
+    pub(crate) fn overlap(&self, start: i64, end: i64) -> i64 {
+        let first = self.ranges.partition_point(|&(_, e)| e <= start);
+        self.ranges[first..]
+            .iter()
+            .take_while(|&&(s, _)| s < end)
+            .fold(0i64, |sum, &(s, e)| sum.saturating_add(e.min(end).saturating_sub(s.max(start))))
+    }
+}
+
+#[wasm_bindgen]
+impl IntervalSet {
+    /// Creates a new, empty `IntervalSet`.
+    /// # Examples
+    /// ```
+    /// const blackout = new IntervalSet();
+    /// blackout.add(Date.parse("2024-06-01T22:00Z"), Date.parse("2024-06-02T06:00Z"));
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    /// Add the range from `start` up to `end` in epoch milliseconds. Empty and reversed ranges are ignored.
+    pub fn add(&mut self, start: f64, end: f64) {
+        if start.is_nan() || end.is_nan() {
+            return;
+        }
+        self.insert(start.floor() as i64, end.floor() as i64);
+    }
+
+    /// Check whether `t` in epoch milliseconds lies in one of the ranges.
+    pub fn contains(&self, t: f64) -> bool {
+        let t = t.floor() as i64;
+        self.overlap(t, t.saturating_add(1)) > 0
+    }
+
+    /// Get the number of separate ranges after merging.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Get the total time covered by the set.
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> UniDuration {
+        let ms: i64 = self.ranges.iter().map(|&(s, e)| e - s).sum();
+        UniDuration::from_nanos(ms.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Get the time the set covers between `start` and `end` in epoch milliseconds.
+    pub fn overlapping(&self, start: f64, end: f64) -> UniDuration {
+        let ms = self.overlap(start.floor() as i64, end.floor() as i64);
+        UniDuration::from_nanos(ms.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Get the merged ranges as a flat array of start and end times, `[start0, end0, start1, end1, ...]`.
+    #[wasm_bindgen(js_name = "toArray")]
+    pub fn to_array(&self) -> Vec<f64> {
+        self.ranges.iter().flat_map(|&(s, e)| [s as f64, e as f64]).collect()
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the time elapsed from the stored time to `now` in epoch milliseconds (the current time if
+    /// omitted), leaving out the time covered by `intervals`. This is how SLA timers count time outside
+    /// nights, weekends or maintenance windows. The result is negative while the stored time is in the future.
+    /// # Examples
+    /// ```
+    /// const open = ticket.createdAt.elapsedExcluding(blackout);
+    /// ```
+    #[wasm_bindgen(js_name = "elapsedExcluding")]
+    pub fn elapsed_excluding(&self, intervals: &IntervalSet, now: Option<f64>) -> UniDuration {
+        let start = self.epoch_ms();
+        let end = now.map_or_else(crate::now_ms, |ms| ms.floor() as i64);
+        let ms = if end >= start {
+            end.saturating_sub(start) - intervals.overlap(start, end)
+        } else {
+            -(start.saturating_sub(end) - intervals.overlap(end, start))
+        };
+        UniDuration::from_nanos(ms.saturating_mul(NANOS_PER_MILLI))
+    }
+}
@@ -0,0 +1,341 @@
+//! ISO 8601 time intervals and repeating intervals, such as `2024-06-01T09:00Z/PT1H30M` or
+//! `R5/2024-06-03/P1W`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, days_from_civil, days_in_month, DateTime};
+use crate::civil::{MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::interval::UniInterval;
+use crate::parse::Cursor;
+use crate::sql::parse_date_time;
+use crate::validation::{char_offset, ValidationError};
+use crate::{Unitime, NANOS_PER_MILLI};
+
+/// Years either side of 1970 that a shifted wall-clock reading may reach, a little past what `Unitime`
+/// holds, so that the day arithmetic cannot overflow.
+const MAX_YEARS: i64 = 300_000_000;
+
+/// Most repetitions `IsoRecurrence.toArray` returns at once.
+const MAX_ARRAY_LEN: u32 = 100_000;
+
+/// Duration such as `P1Y2M10DT2H30M`. Years and months are nominal and so kept apart from the rest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct IsoDuration {
+    months: i64,
+    days: i64,
+    ms: i64,
+}
+
+impl IsoDuration {
+    /// Read `PnYnMnWnDTnHnMnS`, where every part is optional but at least one is needed and the last
+    /// may have a fraction, as in `PT1.5H` or `P0.5D`. Fractions of years and months must come to whole
+    /// months; fractions of weeks and days are exact time, and time below a millisecond is dropped.
+    fn read(c: &mut Cursor) -> Option<IsoDuration> {
+        if !c.eat(b'P') {
+            return None;
+        }
+        let mut duration = IsoDuration { months: 0, days: 0, ms: 0 };
+        let mut parts = 0;
+        let mut in_time = false;
+        loop {
+            if !in_time && c.eat(b'T') {
+                in_time = true;
+                continue;
+            }
+            let Some((n, _)) = c.digits(1, 9) else {
+                break;
+            };
+            let fraction = if c.eat(b'.') || c.eat(b',') { Some(c.digits(1, 9)?) } else { None };
+            // Months, days and milliseconds in one of the unit.
+            let (months, days, ms) = match (in_time, c.peek()?) {
+                (false, b'Y') => (12, 0, 0),
+                (false, b'M') => (1, 0, 0),
+                (false, b'W') => (0, 7, 0),
+                (false, b'D') => (0, 1, 0),
+                (true, b'H') => (0, 0, MS_PER_HOUR),
+                (true, b'M') => (0, 0, MS_PER_MIN),
+                (true, b'S') => (0, 0, MS_PER_SEC),
+                _ => return None,
+            };
+            c.eat(c.peek()?);
+            let n = n as i64;
+            duration.months += n * months;
+            duration.days += n * days;
+            duration.ms += n * ms;
+            parts += 1;
+            if let Some((value, count)) = fraction {
+                let (value, scale) = (value as i64, 10i64.pow(count as u32));
+                if value * months % scale != 0 {
+                    return None;
+                }
+                duration.months += value * months / scale;
+                duration.ms += (value * days * MS_PER_DAY + value * ms) / scale;
+                // Only the last part may have a fraction.
+                break;
+            }
+        }
+        (parts > 0).then_some(duration)
+    }
+
+    /// The duration `n` times over, if that fits.
+    fn times(self, n: i64) -> Option<IsoDuration> {
+        Some(IsoDuration {
+            months: self.months.checked_mul(n)?,
+            days: self.days.checked_mul(n)?,
+            ms: self.ms.checked_mul(n)?,
+        })
+    }
+
+    /// Move wall-clock reading `wall` by the duration, or back by it if `sign` is -1: months first,
+    /// keeping the day of the month where they can and otherwise clamping it, then days, then time.
+    fn shift(self, wall: i64, sign: i64) -> Option<i64> {
+        let dt = DateTime::from_epoch_ms(wall);
+        let total = (dt.year * 12 + dt.month as i64 - 1).checked_add(sign * self.months)?;
+        let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+        if year.abs() > MAX_YEARS {
+            return None;
+        }
+        let day = dt.day.min(days_in_month(year, month));
+        let days = days_from_civil(year, month, day).checked_add(sign * self.days)?;
+        days.checked_mul(MS_PER_DAY)?.checked_add(wall.rem_euclid(MS_PER_DAY))?.checked_add(sign * self.ms)
+    }
+}
+
+/// Date with an optional time and UTC offset at one end of an interval.
+#[derive(Clone, Copy, Debug)]
+struct Endpoint {
+    wall: i64,
+    /// Offset from UTC in minutes, if given; otherwise the time is local or UTC as the caller asked.
+    offset: Option<i64>,
+}
+
+impl Endpoint {
+    fn read(c: &mut Cursor) -> Option<Result<Endpoint, ValidationError>> {
+        let dt = parse_date_time(c)?;
+        let offset = c.utc_offset();
+        Some(dt.validate().map(|()| Endpoint { wall: dt.to_epoch_ms(), offset }))
+    }
+
+    /// The same offset at another wall-clock reading, as an instant in epoch milliseconds.
+    fn instant(self, wall: i64, utc: bool) -> i64 {
+        match self.offset {
+            Some(offset) => wall - offset * MS_PER_MIN,
+            None => civil::wall_to_instant(wall, utc),
+        }
+    }
+}
+
+/// One side of the `/` in an interval.
+enum Part {
+    Time(Endpoint),
+    Duration(IsoDuration),
+}
+
+/// How each repetition of an interval follows the one before.
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    /// By a duration in calendar terms, counted from the first start wall-clock reading.
+    Nominal(IsoDuration),
+    /// By a duration in calendar terms, counted from the first end wall-clock reading, as given by
+    /// `duration/end`, so that the end stays where it was written.
+    NominalToEnd(IsoDuration),
+    /// By a fixed number of milliseconds.
+    Exact(i64),
+}
+
+/// Interval read from text: the end it was given by and how repetitions follow from it.
+struct Parsed {
+    anchor: Endpoint,
+    step: Step,
+}
+
+/// Read the interval `text`, found at byte `offset` of `s`, with times that have no offset in local time
+/// unless `utc` is `true`.
+fn parse_interval(s: &str, text: &str, offset: usize, utc: bool) -> Result<Parsed, ValidationError> {
+    let syntax = |at| ValidationError::syntax(format!("invalid ISO 8601 interval: {s}"), char_offset(s, at));
+    let Some((first, second)) = text.split_once('/') else {
+        return Err(syntax(offset + text.len()));
+    };
+    let part = |text: &str, at: usize| -> Result<Part, ValidationError> {
+        let mut c = Cursor::new(text);
+        let part = match IsoDuration::read(&mut c) {
+            Some(duration) => Part::Duration(duration),
+            None => {
+                c = Cursor::new(text);
+                Part::Time(Endpoint::read(&mut c).ok_or_else(|| syntax(at + c.pos()))??)
+            }
+        };
+        if !c.is_end() {
+            return Err(syntax(at + c.pos()));
+        }
+        Ok(part)
+    };
+    let out_of_range = || ValidationError::new(format!("the ISO 8601 interval is out of range: {s}"));
+    match (part(first, offset)?, part(second, offset + first.len() + 1)?) {
+        (Part::Time(start), Part::Time(end)) => {
+            let ms = end.instant(end.wall, utc) - start.instant(start.wall, utc);
+            Ok(Parsed { anchor: start, step: Step::Exact(ms) })
+        }
+        (Part::Time(start), Part::Duration(duration)) => Ok(Parsed { anchor: start, step: Step::Nominal(duration) }),
+        (Part::Duration(duration), Part::Time(end)) => {
+            duration.shift(end.wall, -1).ok_or_else(out_of_range)?;
+            Ok(Parsed { anchor: end, step: Step::NominalToEnd(duration) })
+        }
+        (Part::Duration(_), Part::Duration(_)) => Err(syntax(offset + first.len() + 1)),
+    }
+}
+
+/// An interval given by its first start and how repetitions follow on, understood in local time unless
+/// it is UTC.
+struct Series {
+    parsed: Parsed,
+    utc: bool,
+}
+
+impl Series {
+    /// The `index`th repetition, from 0, if it is within range.
+    fn at(&self, index: i64) -> Option<UniInterval> {
+        let Parsed { anchor, step } = self.parsed;
+        let (from, to) = match step {
+            Step::Nominal(duration) | Step::NominalToEnd(duration) => {
+                // Counted from the end, the first repetition is the one before the anchor.
+                let first = if matches!(step, Step::NominalToEnd(_)) { index.checked_sub(1)? } else { index };
+                let from = duration.times(first)?.shift(anchor.wall, 1)?;
+                let to = duration.times(first.checked_add(1)?)?.shift(anchor.wall, 1)?;
+                (anchor.instant(from, self.utc), anchor.instant(to, self.utc))
+            }
+            Step::Exact(ms) => {
+                let from = anchor.instant(anchor.wall, self.utc).checked_add(ms.checked_mul(index)?)?;
+                (from, from.checked_add(ms)?)
+            }
+        };
+        let nanos = |ms: i64| ms as i128 * NANOS_PER_MILLI;
+        UniInterval::from_nanos(nanos(from), nanos(to)).ok()
+    }
+}
+
+#[wasm_bindgen]
+impl UniInterval {
+    /// Read an ISO 8601 interval: `start/end`, `start/duration` or `duration/end`. Ends are dates, or
+    /// dates and times after a `T`, with an optional UTC offset such as `Z` or `+02:00`; those without
+    /// one are read in local time unless `utc` is `true`. Durations such as `P1Y2M10DT2H30M`, `P2W` or
+    /// `PT1.5H` count years, months and days on the calendar, so `P1M` from January 31 ends on February
+    /// 29 of a leap year, and `P1M` before March 31 starts on February 29.
+    /// # Examples
+    /// ```
+    /// const slot = UniInterval.fromISO("2024-06-01T09:00Z/PT1H30M");
+    /// UniInterval.fromISO("2024-06-01/2024-06-08", true).duration.days; // 7
+    /// UniInterval.fromISO("P1D/2024-06-02T00:00Z"); // from 2024-06-01T00:00Z
+    /// ```
+    #[wasm_bindgen(js_name = "fromISO")]
+    pub fn from_iso(s: &str, utc: Option<bool>) -> Result<UniInterval, ValidationError> {
+        let utc = utc.unwrap_or(false);
+        let series = Series { parsed: parse_interval(s, s, 0, utc)?, utc };
+        if let Step::Exact(ms) = series.parsed.step {
+            if ms < 0 {
+                return Err(ValidationError::new(format!("the ISO 8601 interval ends before it starts: {s}")));
+            }
+        }
+        series.at(0).ok_or_else(|| ValidationError::new(format!("the ISO 8601 interval is out of range: {s}")))
+    }
+}
+
+/// Repeating ISO 8601 interval such as `R5/2024-06-03T09:00/PT1H` or the endless `R/2024-01-01/P1M`.
+/// The interval given is the first, and each repetition starts where the one before ends; durations in
+/// years, months or days are counted on the calendar from the first start, so monthly repetitions from
+/// January 31 fall on the last day of shorter months without drifting.
+/// # Examples
+/// ```
+/// const standup = IsoRecurrence.fromString("R10/2024-06-03T09:00/P1D");
+/// standup.occurrence(2).start; // 2024-06-05 09:00 local time
+/// for (const slot of standup.toArray(3)) book(slot);
+/// ```
+#[wasm_bindgen]
+pub struct IsoRecurrence {
+    /// Number of repetitions, or `None` for no limit.
+    count: Option<u32>,
+    series: Series,
+}
+
+#[wasm_bindgen]
+impl IsoRecurrence {
+    /// Read a repeating interval, `R` with an optional number of repetitions and then an interval as
+    /// `UniInterval.fromISO` reads it, in local time unless `utc` is `true`.
+    #[wasm_bindgen(js_name = "fromString")]
+    pub fn from_string(s: &str, utc: Option<bool>) -> Result<IsoRecurrence, ValidationError> {
+        let mut c = Cursor::new(s);
+        let syntax = |c: &Cursor| {
+            ValidationError::syntax(format!("invalid ISO 8601 repeating interval: {s}"), char_offset(s, c.pos()))
+        };
+        if !c.eat(b'R') {
+            return Err(syntax(&c));
+        }
+        let count = c.digits(1, 9).map(|(n, _)| n);
+        if !c.eat(b'/') {
+            return Err(syntax(&c));
+        }
+        let utc = utc.unwrap_or(false);
+        let series = Series { parsed: parse_interval(s, &s[c.pos()..], c.pos(), utc)?, utc };
+        if matches!(series.parsed.step, Step::Exact(ms) if ms <= 0) {
+            return Err(ValidationError::new(format!("the repeated interval must end after it starts: {s}")));
+        }
+        Ok(IsoRecurrence { count, series })
+    }
+
+    /// Get the number of repetitions, or `undefined` if they go on without end.
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Get the `index`th repetition, from 0, or `undefined` if there are not that many.
+    pub fn occurrence(&self, index: u32) -> Option<UniInterval> {
+        if self.count.is_some_and(|count| index >= count) {
+            return None;
+        }
+        self.series.at(index as i64)
+    }
+
+    /// Get the first repetition that ends after `t`, or `undefined` if none does.
+    pub fn next(&self, t: &Unitime) -> Option<UniInterval> {
+        let limit = self.count.map_or(i64::MAX, |count| count as i64);
+        // Repetitions only move forward, so the first ending after `t` is found by doubling and then
+        // halving. Indices past the count or the range of `Unitime` count as ending after it.
+        let ends_after =
+            |index: i64| index >= limit || self.series.at(index).is_none_or(|i| i.end().nanos > t.nanos);
+        let (mut low, mut high) = (-1, 0);
+        while !ends_after(high) {
+            low = high;
+            high = high.saturating_mul(2).saturating_add(1);
+        }
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if ends_after(mid) {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        (high < limit).then(|| self.series.at(high)).flatten()
+    }
+
+    /// Get up to `limit` repetitions from the first, all of them if `limit` is omitted and the count is
+    /// not unlimited. At most 100,000 are returned at once, so an unlimited recurrence, or one repeating
+    /// more often than that, needs a `limit` of no more than 100,000.
+    #[wasm_bindgen(js_name = "toArray")]
+    pub fn to_array(&self, limit: Option<u32>) -> Result<Vec<UniInterval>, ValidationError> {
+        let n = match (self.count, limit) {
+            (_, Some(limit)) if limit > MAX_ARRAY_LEN => {
+                return Err(ValidationError::range("limit", limit as i64, 0, MAX_ARRAY_LEN as i64));
+            }
+            (Some(count), Some(limit)) => count.min(limit),
+            (Some(count), None) if count <= MAX_ARRAY_LEN => count,
+            (Some(_), None) => {
+                return Err(ValidationError::new("a recurrence of more than 100,000 repetitions needs a limit"));
+            }
+            (None, Some(limit)) => limit,
+            (None, None) => return Err(ValidationError::new("an unlimited recurrence needs a limit")),
+        };
+        Ok((0..n).map_while(|index| self.series.at(index as i64)).collect())
+    }
+}
@@ -0,0 +1,108 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// The largest distance from 1970 in milliseconds, either way, that a JavaScript `Date` can hold.
+pub(crate) const MAX_DATE_MS: i64 = 8_640_000_000_000_000;
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Creates a new `Unitime` from epoch milliseconds as JavaScript `Date` uses them, within ±8.64e15 ms
+    /// (about 271,821 BCE to 275,760 CE). Values outside that range throw unless `clamp` is `true`, in which
+    /// case they are moved to the nearest end of it. Fractions are dropped, as `Date` does, and `NaN`, as
+    /// from an invalid `Date`, always throws.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromDateMillis(date.getTime());
+    /// const end = Unitime.fromDateMillis(Number.MAX_VALUE, true); // 275760-09-13
+    /// ```
+    #[wasm_bindgen(js_name = "fromDateMillis")]
+    pub fn from_date_millis(ms: f64, clamp: Option<bool>) -> Result<Unitime, ValidationError> {
+        if ms.is_nan() {
+            return Err(ValidationError::new("epoch milliseconds are NaN"));
+        }
+        let max = MAX_DATE_MS as f64;
+        if !clamp.unwrap_or(false) && !(-max..=max).contains(&ms) {
+            return Err(ValidationError::range("epochMil", ms as i64, -MAX_DATE_MS, MAX_DATE_MS));
+        }
+        Ok(Unitime::from_epoch_ms(ms.clamp(-max, max).trunc() as i64))
+    }
+
+    /// Creates a new `Unitime` from a timestamp in epoch milliseconds that a browser API gives, such as
+    /// `File.lastModified` or the `lastModified` of a `File` from a `FileSystemFileHandle`. It is read as
+    /// `fromDateMillis` reads it.
+    /// # Examples
+    /// ```
+    /// const modified = Unitime.fromJsTimestamp((await handle.getFile()).lastModified);
+    /// ```
+    #[wasm_bindgen(js_name = "fromJsTimestamp")]
+    pub fn from_js_timestamp(ms: f64) -> Result<Unitime, ValidationError> {
+        Unitime::from_date_millis(ms, None)
+    }
+
+    /// Get the indices that put `timestamps` in epoch milliseconds in order, oldest first or, if
+    /// `newestFirst` is `true`, newest first, for sorting a large file listing by modification time in
+    /// one call. Equal timestamps keep their order, and `NaN`s go last.
+    /// # Examples
+    /// ```
+    /// const order = Unitime.sortJsTimestamps(files.map(f => f.lastModified), true);
+    /// const sorted = Array.from(order, i => files[i]);
+    /// ```
+    #[wasm_bindgen(js_name = "sortJsTimestamps")]
+    pub fn sort_js_timestamps(timestamps: Vec<f64>, newest_first: Option<bool>) -> Vec<u32> {
+        let mut order: Vec<u32> = (0..timestamps.len() as u32).collect();
+        let newest_first = newest_first.unwrap_or(false);
+        order.sort_by(|&a, &b| {
+            let (a, b) = (timestamps[a as usize], timestamps[b as usize]);
+            match (a.is_nan(), b.is_nan()) {
+                (false, false) if newest_first => b.total_cmp(&a),
+                (false, false) => a.total_cmp(&b),
+                (nan_a, nan_b) => nan_a.cmp(&nan_b),
+            }
+        });
+        order
+    }
+
+    /// Creates a new `Unitime` from a JavaScript `Date`, throwing if it is an invalid date.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromDate(new Date());
+    /// ```
+    #[wasm_bindgen(js_name = "fromDate")]
+    pub fn from_date(date: &js_sys::Date) -> Result<Unitime, ValidationError> {
+        Unitime::from_date_millis(date.get_time(), None)
+    }
+
+    /// Check whether the stored time is within the range of a JavaScript `Date`, ±8.64e15 ms from 1970.
+    /// # Examples
+    /// ```
+    /// if (t.isInDateRange()) new Date(t.epochMil);
+    /// ```
+    #[wasm_bindgen(js_name = "isInDateRange")]
+    pub fn is_in_date_range(&self) -> bool {
+        (-MAX_DATE_MS..=MAX_DATE_MS).contains(&self.epoch_ms())
+    }
+
+    /// Get a new `Unitime` moved to the nearest end of the JavaScript `Date` range if it is outside it.
+    #[wasm_bindgen(js_name = "clampToDateRange")]
+    pub fn clamp_to_date_range(&self) -> Unitime {
+        let max = MAX_DATE_MS as i128 * 1_000_000;
+        Unitime::from_nanos(self.nanos.clamp(-max, max))
+    }
+
+    /// Get the stored time as a JavaScript `Date`, throwing instead of returning an invalid date if it is
+    /// outside the range of `Date`. Milliseconds are rounded down.
+    /// # Examples
+    /// ```
+    /// const d = new Unitime().toDate();
+    /// ```
+    #[wasm_bindgen(js_name = "toDate")]
+    pub fn to_date(&self) -> Result<js_sys::Date, ValidationError> {
+        let ms = self.epoch_ms();
+        if !self.is_in_date_range() {
+            return Err(ValidationError::range("epochMil", ms, -MAX_DATE_MS, MAX_DATE_MS));
+        }
+        Ok(js_sys::Date::new(&JsValue::from_f64(ms as f64)))
+    }
+}
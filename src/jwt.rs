@@ -0,0 +1,65 @@
+use wasm_bindgen::prelude::*;
+
+use crate::Unitime;
+
+/// Outcome of `Unitime.validateJwtTimes`.
+#[wasm_bindgen]
+pub struct JwtTimeCheck {
+    claim: Option<&'static str>,
+    by_seconds: f64,
+}
+
+#[wasm_bindgen]
+impl JwtTimeCheck {
+    /// Whether all temporal claims hold.
+    #[wasm_bindgen(getter)]
+    pub fn valid(&self) -> bool {
+        self.claim.is_none()
+    }
+
+    /// The first claim that failed (`"exp"`, `"nbf"` or `"iat"`), or `undefined` if valid.
+    #[wasm_bindgen(getter)]
+    pub fn claim(&self) -> Option<String> {
+        self.claim.map(String::from)
+    }
+
+    /// How many seconds beyond the leeway the failed claim was missed by, or 0 if valid.
+    #[wasm_bindgen(getter, js_name = "bySeconds")]
+    pub fn by_seconds(&self) -> f64 {
+        self.by_seconds
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Check the standard JWT temporal claims, given in epoch seconds, against the stored time, allowing
+    /// `leeway_seconds` of clock skew (0 by default). The token has expired once the time reaches `exp`, is not valid before `nbf`,
+    /// and must not be issued (`iat`) in the future. Claims that are `undefined` are not checked.
+    /// # Examples
+    /// ```
+    /// const check = new Unitime().validateJwtTimes(payload.exp, payload.iat, payload.nbf, 30);
+    /// if (!check.valid) console.warn(`${check.claim} failed by ${check.bySeconds}s`);
+    /// ```
+    #[wasm_bindgen(js_name = "validateJwtTimes")]
+    pub fn validate_jwt_times(
+        &self,
+        exp: Option<f64>,
+        iat: Option<f64>,
+        nbf: Option<f64>,
+        leeway_seconds: Option<f64>,
+    ) -> JwtTimeCheck {
+        let now = self.epoch_ms() as f64 / 1000.0;
+        let leeway = leeway_seconds.unwrap_or(0.0).max(0.0);
+        let fail = |claim, by_seconds| JwtTimeCheck { claim: Some(claim), by_seconds };
+        if let Some(exp) = exp.filter(|&exp| now >= exp + leeway) {
+            return fail("exp", now - (exp + leeway));
+        }
+        if let Some(nbf) = nbf.filter(|&nbf| now < nbf - leeway) {
+            return fail("nbf", (nbf - leeway) - now);
+        }
+        if let Some(iat) = iat.filter(|&iat| iat > now + leeway) {
+            return fail("iat", iat - (now + leeway));
+        }
+        JwtTimeCheck { claim: None, by_seconds: 0.0 }
+    }
+}
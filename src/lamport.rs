@@ -0,0 +1,97 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::{char_offset, ValidationError};
+
+/// Largest counter value that is still exactly representable as a JS number.
+const MAX_SAFE_COUNTER: u64 = (1 << 53) - 1;
+
+/// The error for a counter value past `MAX_SAFE_COUNTER`.
+fn overflow(time: u64) -> ValidationError {
+    ValidationError::range("time", time.min(i64::MAX as u64) as i64, 0, MAX_SAFE_COUNTER as i64)
+}
+
+/// Lamport logical clock for causal ordering of events across tabs or peers.
+#[wasm_bindgen]
+pub struct LamportClock {
+    time: u64,
+}
+
+impl Default for LamportClock {
+    fn default() -> Self {
+        LamportClock::new(None)
+    }
+}
+
+#[wasm_bindgen]
+impl LamportClock {
+    /// Creates a new `LamportClock`, starting at `start` or 0.
+    /// # Examples
+    /// ```
+    /// const clock = new LamportClock();
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(start: Option<u32>) -> LamportClock {
+        LamportClock { time: start.unwrap_or(0) as u64 }
+    }
+
+    /// Get the current counter value.
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> f64 {
+        self.time as f64
+    }
+
+    /// Advance the clock for a local event and return the new counter value, to be attached to outgoing messages.
+    /// # Examples
+    /// ```
+    /// channel.postMessage({ lamport: clock.tick(), payload });
+    /// ```
+    pub fn tick(&mut self) -> Result<f64, ValidationError> {
+        if self.time >= MAX_SAFE_COUNTER {
+            return Err(overflow(self.time + 1));
+        }
+        self.time += 1;
+        Ok(self.time as f64)
+    }
+
+    /// Merge the counter of a received message and return the new counter value for the receive event.
+    /// # Examples
+    /// ```
+    /// channel.onmessage = (e) => clock.observe(e.data.lamport);
+    /// ```
+    pub fn observe(&mut self, remote: f64) -> Result<f64, ValidationError> {
+        if remote.is_nan() || remote < 0.0 || remote.fract() != 0.0 {
+            return Err(ValidationError::new("remote Lamport time must be a non-negative safe integer"));
+        }
+        if remote > MAX_SAFE_COUNTER as f64 {
+            return Err(overflow(remote as u64));
+        }
+        self.time = self.time.max(remote as u64);
+        self.tick()
+    }
+
+    /// Serialize the clock state, e.g. for `localStorage`.
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        self.time.to_string()
+    }
+
+    /// Restore a clock serialized with `toString`.
+    /// # Examples
+    /// ```
+    /// const clock = LamportClock.fromString(localStorage.getItem("lamport") ?? "0");
+    /// ```
+    #[wasm_bindgen(js_name = "fromString")]
+    pub fn from_string(s: &str) -> Result<LamportClock, ValidationError> {
+        let lead = s.len() - s.trim_start().len();
+        let digits = s.trim();
+        if let Some(at) = digits.bytes().position(|b| !b.is_ascii_digit()).or(digits.is_empty().then_some(0)) {
+            return Err(ValidationError::syntax(format!("invalid Lamport clock state: {s}"), char_offset(s, lead + at)));
+        }
+        // Only digits are left, so parsing fails only past u64::MAX
+        match digits.parse::<u64>() {
+            Ok(time) if time <= MAX_SAFE_COUNTER => Ok(LamportClock { time }),
+            Ok(time) => Err(overflow(time)),
+            Err(_) => Err(overflow(u64::MAX)),
+        }
+    }
+}
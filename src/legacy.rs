@@ -0,0 +1,385 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, days_from_civil, DateTime, MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::parse::Cursor;
+use crate::validation::{char_offset, ValidationError};
+use crate::Unitime;
+
+/// Order of the day, month and year in a numeric date such as `03/04/05`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateOrder {
+    Ymd = "ymd",
+    Dmy = "dmy",
+    Mdy = "mdy",
+}
+
+/// How `ParseOptions.parse` reads messy dates from legacy data: numeric dates in any common order and
+/// separator, two-digit years, month abbreviations, missing components and out-of-range values.
+/// # Examples
+/// ```
+/// const opts = new ParseOptions();
+/// opts.order = "dmy";
+/// opts.pivotYear = 30;
+/// opts.parse("05-JUN-24 7:15pm"); // 2024-06-05 19:15
+/// ```
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ParseOptions {
+    pivot_year: u32,
+    order: DateOrder,
+    rollover: bool,
+    default_year: Option<i32>,
+    default_month: u32,
+    default_day: u32,
+    utc: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::new()
+    }
+}
+
+/// One numeric or named field of a date, before it is known which component it is.
+#[derive(Clone, Copy)]
+struct Field {
+    value: u32,
+    digits: usize,
+    named_month: bool,
+}
+
+fn date_fields(c: &mut Cursor) -> Option<Vec<Field>> {
+    let mut fields = Vec::new();
+    let mut separator = None;
+    loop {
+        let field = if let Some(month) = c.month_abbr() {
+            // Full month names are accepted by skipping the rest of the word.
+            while c.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+                c.eat(c.peek()?);
+            }
+            Field { value: month, digits: 2, named_month: true }
+        } else {
+            let (value, digits) = c.digits(1, 8)?;
+            Field { value, digits, named_month: false }
+        };
+        fields.push(field);
+        if fields.len() == 3 {
+            break;
+        }
+        let mut ahead = c.clone();
+        let sep = match separator {
+            Some(b' ' | b',') if ahead.eat(b' ') || ahead.eat(b',') => b' ',
+            Some(sep) if ahead.eat(sep) => sep,
+            Some(_) => break,
+            None => match ahead.peek() {
+                Some(sep @ (b'-' | b'/' | b'.' | b' ' | b',')) => {
+                    ahead.eat(sep);
+                    sep
+                }
+                _ => break,
+            },
+        };
+        if sep == b',' || sep == b' ' {
+            // Spaces and commas mix freely, as in `June 5, 2024`.
+            while ahead.eat(b',') || ahead.eat(b' ') {}
+        }
+        // A time after a two-field date such as `06/05 10:30` must not be taken for a third field.
+        let mut probe = ahead.clone();
+        if probe.digits(1, 2).is_some() && probe.peek() == Some(b':') {
+            break;
+        }
+        if ahead.peek().is_some_and(|b| b.is_ascii_digit()) || ahead.clone().month_abbr().is_some() {
+            *c = ahead;
+            separator = Some(sep);
+        } else {
+            break;
+        }
+    }
+    Some(fields)
+}
+
+/// Broken-down fields that may still be out of range, so they can be rolled over.
+struct Loose {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    milli: i64,
+}
+
+impl ParseOptions {
+    fn expand_year(&self, field: Field) -> i64 {
+        if field.digits <= 2 {
+            let century = if field.value < self.pivot_year { 2000 } else { 1900 };
+            century + field.value as i64
+        } else {
+            field.value as i64
+        }
+    }
+
+    /// Assign date fields to year, month and day.
+    fn assign(&self, fields: &[Field], now_year: i64) -> Option<(i64, i64, i64)> {
+        let default_year = self.default_year.map_or(now_year, |y| y as i64);
+        let (month_first, day_first) = (self.order == DateOrder::Mdy, self.order == DateOrder::Dmy);
+        match *fields {
+            [f] if f.digits == 8 && !f.named_month => {
+                Some(((f.value / 10_000) as i64, (f.value / 100 % 100) as i64, (f.value % 100) as i64))
+            }
+            [f] if f.digits == 4 && !f.named_month => {
+                Some((f.value as i64, self.default_month as i64, self.default_day as i64))
+            }
+            [a, b] if a.digits >= 3 || b.digits >= 3 => {
+                let (year, month) = if a.digits >= 3 { (a, b) } else { (b, a) };
+                Some((self.expand_year(year), month.value as i64, self.default_day as i64))
+            }
+            [a, b] => {
+                let (month, day) = if b.named_month || (day_first && !a.named_month) { (b, a) } else { (a, b) };
+                Some((default_year, month.value as i64, day.value as i64))
+            }
+            [a, b, c] => {
+                let mut slots = if a.digits >= 3 {
+                    [0, 1, 2]
+                } else if month_first {
+                    [2, 0, 1]
+                } else if day_first {
+                    [2, 1, 0]
+                } else {
+                    [0, 1, 2]
+                };
+                // A long year and a named month go where they stand, swapping places with whatever the order put there.
+                let long_year = fields.iter().position(|f| f.digits >= 3);
+                let named = fields.iter().position(|f| f.named_month);
+                for (component, found) in [(0, long_year), (1, named)] {
+                    if let Some(other) = found.and_then(|found| slots.iter().position(|&s| s == found)) {
+                        slots.swap(component, other);
+                    }
+                }
+                let [year, month, day] = slots.map(|s| [a, b, c][s]);
+                Some((self.expand_year(year), month.value as i64, day.value as i64))
+            }
+            _ => None,
+        }
+    }
+
+    fn read(&self, s: &str) -> Result<(Loose, Option<i64>), ValidationError> {
+        let lead = s.len() - s.trim_start().len();
+        let syntax = |what: &str, c: &Cursor| {
+            ValidationError::syntax(format!("invalid {what}: {s}"), char_offset(s, lead + c.pos()))
+        };
+        let mut c = Cursor::new(s.trim());
+        let mut era = c.era();
+        c.skip_spaces();
+        let mut fields = date_fields(&mut c).ok_or_else(|| syntax("date", &c))?;
+        let era_year = if era.is_some() { 0 } else { fields.len() - 1 };
+        if era.is_none() {
+            let mut ahead = c.clone();
+            ahead.skip_spaces();
+            era = ahead.era();
+            if era.is_some() {
+                c = ahead;
+            }
+        }
+        if era.is_some() {
+            // The number next to the era is the year, written in full.
+            let year = &mut fields[era_year];
+            if year.value == 0 || year.named_month {
+                return Err(syntax("year", &c));
+            }
+            year.digits = year.digits.max(4);
+        }
+        let now_year = DateTime::from_epoch_ms(civil::instant_to_wall(crate::now_ms(), self.utc)).year;
+        let (year, month, day) = self.assign(&fields, now_year).ok_or_else(|| syntax("date", &c))?;
+        // Years before the common era count back from 1 BC, which is year 0.
+        let year = if era == Some(true) { 1 - year } else { year };
+        let mut loose = Loose { year, month, day, hour: 0, minute: 0, second: 0, milli: 0 };
+
+        if c.eat(b'T') || c.peek() == Some(b' ') {
+            c.skip_spaces();
+            if let Some((hour, _)) = c.digits(1, 2) {
+                loose.hour = hour as i64;
+                if c.eat(b':') {
+                    loose.minute = c.fixed(2).ok_or_else(|| syntax("time", &c))? as i64;
+                    if c.eat(b':') {
+                        loose.second = c.fixed(2).ok_or_else(|| syntax("time", &c))? as i64;
+                        if c.eat(b'.') {
+                            loose.milli = c.fraction_ms().ok_or_else(|| syntax("time", &c))? as i64;
+                        }
+                    }
+                }
+                c.skip_spaces();
+                let mut ahead = c.clone();
+                let pm = match ahead.peek().map(|b| b.to_ascii_lowercase()) {
+                    Some(b'a') => Some(false),
+                    Some(b'p') => Some(true),
+                    _ => None,
+                };
+                if let Some(pm) = pm {
+                    ahead.eat(ahead.peek().unwrap_or(0));
+                    if ahead.eat(b'm') || ahead.eat(b'M') {
+                        c = ahead;
+                        if !(1..=12).contains(&loose.hour) {
+                            return Err(ValidationError::range("hour", loose.hour, 1, 12));
+                        }
+                        loose.hour = loose.hour % 12 + if pm { 12 } else { 0 };
+                    }
+                }
+            }
+        }
+        c.skip_spaces();
+        let offset = c.utc_offset();
+        if !c.is_end() {
+            return Err(syntax("date", &c));
+        }
+        Ok((loose, offset))
+    }
+
+    /// Turn loose fields into a wall-clock reading, rolling over or rejecting out-of-range values.
+    fn resolve(&self, f: &Loose) -> Result<i64, ValidationError> {
+        if self.rollover {
+            let months = f.year * 12 + f.month - 1;
+            let days = days_from_civil(months.div_euclid(12), months.rem_euclid(12) as u32 + 1, 1) + f.day - 1;
+            return Ok(days * MS_PER_DAY
+                + f.hour * MS_PER_HOUR
+                + f.minute * MS_PER_MIN
+                + f.second * MS_PER_SEC
+                + f.milli);
+        }
+        let dt = DateTime {
+            year: f.year,
+            month: f.month as u32,
+            day: f.day as u32,
+            hour: f.hour as u32,
+            minute: f.minute as u32,
+            second: f.second as u32,
+            milli: f.milli as u32,
+        };
+        dt.validate()?;
+        Ok(dt.to_epoch_ms())
+    }
+}
+
+#[wasm_bindgen]
+impl ParseOptions {
+    /// Creates a new `ParseOptions` with year-month-day order, the POSIX two-digit year pivot of 69,
+    /// missing years taken from the current year, missing months and days as the first, local time, and errors on
+    /// out-of-range values.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ParseOptions {
+        ParseOptions {
+            pivot_year: 69,
+            order: DateOrder::Ymd,
+            rollover: false,
+            default_year: None,
+            default_month: 1,
+            default_day: 1,
+            utc: false,
+        }
+    }
+
+    /// Get the two-digit year below which years are in the 2000s; the rest are in the 1900s.
+    #[wasm_bindgen(getter, js_name = "pivotYear")]
+    pub fn pivot_year(&self) -> u32 {
+        self.pivot_year
+    }
+
+    /// Set the two-digit year pivot, from 0 (always 19xx) to 100 (always 20xx).
+    #[wasm_bindgen(setter, js_name = "pivotYear")]
+    pub fn set_pivot_year(&mut self, pivot: u32) {
+        self.pivot_year = pivot.min(100);
+    }
+
+    /// Get the order of numeric date fields. Dates starting with a year of three or more digits are
+    /// always read as year-month-day.
+    #[wasm_bindgen(getter)]
+    pub fn order(&self) -> DateOrder {
+        self.order
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_order(&mut self, order: DateOrder) {
+        self.order = order;
+    }
+
+    /// Get whether out-of-range values roll over, e.g. February 30 into March, instead of being errors.
+    #[wasm_bindgen(getter)]
+    pub fn rollover(&self) -> bool {
+        self.rollover
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_rollover(&mut self, rollover: bool) {
+        self.rollover = rollover;
+    }
+
+    /// Get the year used for dates without one, or `undefined` for the current year.
+    #[wasm_bindgen(getter, js_name = "defaultYear")]
+    pub fn default_year(&self) -> Option<i32> {
+        self.default_year
+    }
+
+    #[wasm_bindgen(setter, js_name = "defaultYear")]
+    pub fn set_default_year(&mut self, year: Option<i32>) {
+        self.default_year = year;
+    }
+
+    /// Get the month used for dates that only have a year.
+    #[wasm_bindgen(getter, js_name = "defaultMonth")]
+    pub fn default_month(&self) -> u32 {
+        self.default_month
+    }
+
+    #[wasm_bindgen(setter, js_name = "defaultMonth")]
+    pub fn set_default_month(&mut self, month: u32) {
+        self.default_month = month;
+    }
+
+    /// Get the day used for dates that only have a month and year, or only a year.
+    #[wasm_bindgen(getter, js_name = "defaultDay")]
+    pub fn default_day(&self) -> u32 {
+        self.default_day
+    }
+
+    #[wasm_bindgen(setter, js_name = "defaultDay")]
+    pub fn set_default_day(&mut self, day: u32) {
+        self.default_day = day;
+    }
+
+    /// Get whether values without a UTC offset are read as UTC rather than local time.
+    #[wasm_bindgen(getter)]
+    pub fn utc(&self) -> bool {
+        self.utc
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_utc(&mut self, utc: bool) {
+        self.utc = utc;
+    }
+
+    /// Parse a date with an optional time, 12- or 24-hour, and an optional UTC offset according to these options.
+    /// Dates may be numeric with `-`, `/`, `.` or space between fields, compact `YYYYMMDD`, or use English
+    /// month names; a year alone, a month and year, or a day and month are completed from the defaults.
+    /// An era marker, `BC`, `BCE`, `AD` or `CE` with or without dots, may follow the date or precede
+    /// it; the number next to it is then the year in full, and years BC are stored astronomically, so
+    /// that 1 BC is year 0 and 44 BC is year -43.
+    /// # Examples
+    /// ```
+    /// const opts = new ParseOptions();
+    /// opts.order = "mdy";
+    /// opts.parse("6/5/24");
+    /// opts.parse("Jun 2024");
+    /// opts.parse("15 Mar 44 BC");
+    /// opts.parse("AD 1066-10-14");
+    /// ```
+    pub fn parse(&self, s: &str) -> Result<Unitime, ValidationError> {
+        let (loose, offset) = self.read(s)?;
+        let wall = self.resolve(&loose)?;
+        let ms = match offset {
+            Some(minutes) => wall - minutes * MS_PER_MIN,
+            None => civil::wall_to_instant(wall, self.utc),
+        };
+        Ok(Unitime::from_epoch_ms(ms))
+    }
+}
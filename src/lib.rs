@@ -1,10 +1,264 @@
+use std::cell::Cell;
+
 use wasm_bindgen::prelude::*;
-use web_time::{SystemTime, Duration};
+use web_time::SystemTime;
+
+use crate::clock::{ClockHours, ClockOptions};
+use crate::logger::LogKind;
+use crate::validation::ValidationError;
+
+#[cfg(feature = "timers")]
+mod alarm;
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+pub mod arbitrary;
+mod audio_clock;
+mod bytes;
+#[cfg(feature = "calendar")]
+mod calendar;
+#[cfg(all(feature = "stats", feature = "tz"))]
+mod calendar_buckets;
+#[cfg(feature = "tz")]
+mod calendar_days;
+mod civil;
+mod clock;
+mod codec;
+mod countdown;
+#[cfg(feature = "timers")]
+mod cron;
+mod dial;
+#[cfg(feature = "stats")]
+mod downsample;
+mod duration;
+#[cfg(feature = "format")]
+mod duration_format;
+mod easter;
+#[cfg(feature = "parse")]
+mod edtf;
+mod elapsed;
+mod epoch;
+#[cfg(feature = "stats")]
+mod ewma;
+#[cfg(feature = "parse")]
+mod exif;
+#[cfg(feature = "tz")]
+mod file_age;
+#[cfg(feature = "format")]
+mod format;
+#[cfg(feature = "leap-seconds")]
+mod gps;
+#[cfg(feature = "timers")]
+mod heartbeat;
+mod hlc;
+mod host;
+mod ids;
+mod interval;
+mod intervals;
+#[cfg(feature = "parse")]
+mod iso_interval;
+mod js_date;
+mod jwt;
+mod lamport;
+#[cfg(feature = "parse")]
+mod legacy;
+#[cfg(feature = "locale")]
+mod locale;
+mod logger;
+#[cfg(feature = "parse")]
+mod logtime;
+mod media_clock;
+mod metronome;
+mod month;
+mod moon;
+mod music;
+#[cfg(feature = "parse")]
+mod natural;
+mod occurrence;
+mod otp;
+#[cfg(feature = "panic-hook")]
+mod panic_hook;
+mod parse;
+mod partial_date;
+mod pool;
+mod progress;
+#[cfg(feature = "stats")]
+mod rate;
+#[cfg(feature = "format")]
+mod relative;
+mod replay;
+#[cfg(feature = "parse")]
+mod scanner;
+#[cfg(feature = "timers")]
+mod scheduler;
+#[cfg(feature = "stats")]
+mod series;
+mod shared_clock;
+#[cfg(all(feature = "format", feature = "tz"))]
+mod smart_label;
+#[cfg(feature = "parse")]
+mod sql;
+#[cfg(feature = "stats")]
+mod stats;
+mod stopwatch;
+mod sun;
+#[cfg(feature = "leap-seconds")]
+mod tai;
+mod timecode;
+#[cfg(all(feature = "timers", js))]
+mod timer;
+#[cfg(feature = "parse")]
+mod track;
+#[cfg(feature = "tz")]
+mod tz;
+#[cfg(feature = "tz")]
+mod tzdata;
+#[cfg(feature = "tz")]
+mod tzif;
+#[cfg(feature = "stats")]
+mod uptime;
+mod validation;
+mod week;
+#[cfg(feature = "stats")]
+mod window;
+mod with;
+mod work;
+
+/// Get the current time in epoch milliseconds.
+pub(crate) fn now_ms() -> i64 {
+    Unitime::new().epoch_ms()
+}
+
+/// Get the monotonic clock reading in milliseconds, which on the web is `performance.now()`.
+pub(crate) fn monotonic_ms() -> f64 {
+    let ms = replay::monotonic_ms(host::monotonic_ms);
+    logger::log(LogKind::Clock, || format!("monotonic clock read {ms} ms"));
+    ms
+}
+
+/// Nanoseconds per millisecond, as stored by `Unitime`.
+const NANOS_PER_MILLI: i128 = 1_000_000;
+/// The earliest and latest instants a `Unitime` can hold, chosen so that every instant has whole epoch
+/// milliseconds that fit an `i64`. That is about 292 million years either side of 1970.
+const MIN_NANOS: i128 = i64::MIN as i128 * NANOS_PER_MILLI;
+const MAX_NANOS: i128 = i64::MAX as i128 * NANOS_PER_MILLI;
+
+/// How `Unitime.getElapsedStr` writes the time elapsed.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElapsedStyle {
+    /// "04:12" or "01:04:12", with a minus sign before times in the future.
+    Clock = "clock",
+    /// "4m 12s ago", or "in 4m 12s" for times in the future.
+    Relative = "relative",
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ElapsedStyle {
+    fn default() -> Self {
+        ElapsedStyle::Clock
+    }
+}
 
 /// Unitime is a library for handling time using WebAssembly.
+///
+/// Methods that derive another time from a `Unitime`, such as `fromEpochMil`, `clampToInterval` or
+/// `startOfWeek`, return a new `Unitime` and leave the one they are called on as it was, so calls chain.
+/// Only `refresh` and `setFrom` change a `Unitime` in place, for reusing it in hot loops, and `freeze`
+/// makes them throw, so that a `Unitime` can be shared without copying.
 #[wasm_bindgen]
+#[derive(Debug)]
 pub struct Unitime {
-    time: SystemTime
+    /// Signed nanoseconds since 1970-01-01 UTC, between `MIN_NANOS` and `MAX_NANOS`. Unlike `u64` seconds
+    /// or `u32` seconds this has no year 2038, 2106 or 1970 limit.
+    nanos: i128,
+    /// Whether `refresh` and `setFrom` are refused.
+    frozen: bool,
+}
+
+thread_local! {
+    /// Number of `Unitime`s created and not yet dropped, for `Unitime.liveCount`.
+    static LIVE: Cell<u32> = const { Cell::new(0) };
+}
+
+impl Default for Unitime {
+    fn default() -> Self {
+        Unitime::new()
+    }
+}
+
+impl Drop for Unitime {
+    fn drop(&mut self) {
+        LIVE.with(|live| live.set(live.get().saturating_sub(1)));
+    }
+}
+
+impl Unitime {
+    /// Creates a `Unitime`, not frozen, from nanoseconds since 1970 between `MIN_NANOS` and `MAX_NANOS`.
+    pub(crate) fn from_nanos(nanos: i128) -> Unitime {
+        LIVE.with(|live| live.set(live.get().saturating_add(1)));
+        Unitime { nanos, frozen: false }
+    }
+
+    /// Fail if the `Unitime` is frozen, naming the `method` that would have changed it.
+    fn check_thawed(&self, method: &str) -> Result<(), ValidationError> {
+        if self.frozen {
+            return Err(ValidationError::new(format!("{method} cannot change a frozen Unitime")));
+        }
+        Ok(())
+    }
+
+    /// Creates a `Unitime` from signed epoch milliseconds.
+    pub(crate) fn from_epoch_ms(ms: i64) -> Unitime {
+        Unitime::from_nanos(ms as i128 * NANOS_PER_MILLI)
+    }
+
+    /// Creates a `Unitime` from a possibly fractional count of units of `nanos_per_unit` nanoseconds since
+    /// 1970, moved to the nearest bound if it is out of range. `NaN` gives 1970.
+    pub(crate) fn from_epoch_float(value: f64, nanos_per_unit: i128) -> Unitime {
+        // Whole units are converted separately so that scaling does not round them. Float to integer
+        // casts saturate, so out-of-range values end up at the bounds.
+        let whole = value.floor();
+        let fraction = ((value - whole) * nanos_per_unit as f64).round() as i128;
+        let nanos = (whole as i128).saturating_mul(nanos_per_unit).saturating_add(fraction);
+        Unitime::from_nanos(nanos.clamp(MIN_NANOS, MAX_NANOS))
+    }
+
+    /// Get the stored time as signed epoch milliseconds, rounded down.
+    pub(crate) fn epoch_ms(&self) -> i64 {
+        self.nanos.div_euclid(NANOS_PER_MILLI) as i64
+    }
+
+    /// Get the whole seconds elapsed from the stored time to now, rounded toward zero, so negative only
+    /// when the stored time is at least a second in the future.
+    fn elapsed_secs(&self) -> i64 {
+        ((Unitime::new().nanos - self.nanos) / 1_000_000_000) as i64
+    }
+
+    /// Write `secs` seconds elapsed, negative for a time still to come, as `getElapsedStr` does.
+    fn elapsed_str(secs: i64, style: ElapsedStyle) -> String {
+        if style != ElapsedStyle::Relative {
+            // Automatic hours are always a known option, so this cannot fail
+            let options = ClockOptions { hours: ClockHours::Auto, days: false, millis: false };
+            return clock::clock_string(secs as i128 * 1_000_000_000, &options).unwrap_or_default();
+        }
+        let abs = secs.unsigned_abs();
+        let units = [(abs / 3600, 'h'), (abs % 3600 / 60, 'm'), (abs % 60, 's')];
+        let mut parts: Vec<String> =
+            units.iter().filter(|&&(n, _)| n != 0).map(|(n, unit)| format!("{n}{unit}")).collect();
+        if parts.is_empty() {
+            parts.push("0s".to_string());
+        }
+        let span = parts.join(" ");
+        if secs < 0 {
+            format!("in {span}")
+        } else {
+            format!("{span} ago")
+        }
+    }
+
+    /// Get the wall-clock reading of the stored time as epoch milliseconds, in UTC if `utc` is `true`, else in local time.
+    pub(crate) fn wall_ms(&self, utc: bool) -> i64 {
+        civil::instant_to_wall(self.epoch_ms(), utc)
+    }
 }
 
 #[wasm_bindgen]
@@ -16,21 +270,81 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(constructor)]
     pub fn new() -> Unitime {
-        Unitime { time: SystemTime::now() }
+        let nanos = replay::system_nanos(|| match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(d) => d.as_nanos() as i128,
+            Err(e) => -(e.duration().as_nanos() as i128),
+        });
+        logger::log(LogKind::Clock, || format!("system clock read {} ms", nanos.div_euclid(NANOS_PER_MILLI)));
+        Unitime::from_nanos(nanos)
     }
 
-    /// Creates a new `Unitime` with the given time in epoch milliseconds
+    /// Creates a new `Unitime` with the given time in epoch milliseconds, which may be negative for times
+    /// before 1970 and may have a fraction. The `Unitime` it is called on keeps its own time.
     /// # Examples
     /// ```
-    /// const t = new Unitime.fromEpochMil(1693470768154);
+    /// const t = new Unitime().fromEpochMil(1693470768154);
     /// ```
     #[wasm_bindgen(js_name = "fromEpochMil")]
-    pub fn from_epoch_mil(&mut self, mil: f64) -> Unitime {
-        self.time = SystemTime::UNIX_EPOCH + Duration::from_millis(mil as u64);
-        Unitime { time: self.time }
+    pub fn from_epoch_mil(&self, mil: f64) -> Unitime {
+        Unitime::from_epoch_float(mil, NANOS_PER_MILLI)
     }
 
-    /// Get the total number of elapsed hours since the stored time compared to the current time.
+    /// Set the stored time to the current time in place, rather than creating a new `Unitime`. Throws if
+    /// the `Unitime` is frozen.
+    /// # Examples
+    /// ```
+    /// const frameTime = new Unitime();
+    /// function frame() {
+    ///     frameTime.refresh();
+    ///     draw(frameTime);
+    ///     requestAnimationFrame(frame);
+    /// }
+    /// ```
+    pub fn refresh(&mut self) -> Result<(), ValidationError> {
+        self.check_thawed("refresh")?;
+        self.nanos = Unitime::new().nanos;
+        Ok(())
+    }
+
+    /// Set the stored time to that of `other` in place. Throws if this `Unitime` is frozen; `other` may be.
+    #[wasm_bindgen(js_name = "setFrom")]
+    pub fn set_from(&mut self, other: &Unitime) -> Result<(), ValidationError> {
+        self.check_thawed("setFrom")?;
+        self.nanos = other.nanos;
+        Ok(())
+    }
+
+    /// Stop `refresh` and `setFrom` from changing the `Unitime`, for good, so that code it is handed to
+    /// cannot change it under its owner. Every other method works as before.
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    /// Check whether `freeze` has been called.
+    #[wasm_bindgen(js_name = "isFrozen")]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Get how many `Unitime`s are held in wasm memory, to find code that leaks them. Each one is freed
+    /// by calling `free()`, at the end of a `using` block through `Symbol.dispose`, or, where the host
+    /// has `FinalizationRegistry`, some time after its JS object is garbage collected. Those that are
+    /// never freed grow the wasm heap for as long as the page lives.
+    /// # Examples
+    /// ```
+    /// {
+    ///     using t = new Unitime();
+    ///     render(t);
+    /// } // freed here
+    /// console.assert(Unitime.liveCount() === before);
+    /// ```
+    #[wasm_bindgen(js_name = "liveCount")]
+    pub fn live_count() -> u32 {
+        LIVE.with(Cell::get)
+    }
+
+    /// Get the total number of elapsed hours since the stored time compared to the current time. This and
+    /// the other elapsed getters are negative when the stored time is in the future.
     /// # Examples
     /// ```
     /// const t = new Unitime();
@@ -38,11 +352,7 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(js_name = "getElapsedHours")]
     pub fn get_elapsed_hours(&self) -> i32 {
-        let now : SystemTime = SystemTime::now();
-        let mut elapsed = now.duration_since(self.time).unwrap();
-        let hours = elapsed.as_secs() / 3600;
-        elapsed -= Duration::from_secs(hours * 3600);
-        hours as i32
+        (self.elapsed_secs() / 3600) as i32
     }
 
     /// Get the number of elapsed minutes, not considering the elapsed hours, since the stored time compared to the current time.
@@ -53,13 +363,7 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(js_name = "getElapsedMinutes")]
     pub fn get_elapsed_minutes(&self) -> i32 {
-        let now : SystemTime = SystemTime::now();
-        let mut elapsed = now.duration_since(self.time).unwrap();
-        let hours = elapsed.as_secs() / 3600;
-        elapsed -= Duration::from_secs(hours * 3600);
-        let minutes = elapsed.as_secs() / 60;
-        elapsed -= Duration::from_secs(minutes * 60);
-        minutes as i32
+        (self.elapsed_secs() % 3600 / 60) as i32
     }
 
     /// Get the number of elapsed seconds, not considering the elapsed hours and minutes, since the stored time compared to the current time.
@@ -70,13 +374,7 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(js_name = "getElapsedSeconds")]
     pub fn get_elapsed_seconds(&self) -> i32 {
-        let now : SystemTime = SystemTime::now();
-        let mut elapsed = now.duration_since(self.time).unwrap();
-        let hours = elapsed.as_secs() / 3600;
-        elapsed -= Duration::from_secs(hours * 3600);
-        let minutes = elapsed.as_secs() / 60;
-        elapsed -= Duration::from_secs(minutes * 60);
-        elapsed.as_secs() as i32
+        (self.elapsed_secs() % 60) as i32
     }
 
     /// Get the stored time in epoch milliseconds.
@@ -87,18 +385,18 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(getter, js_name = "epochMil")]
     pub fn get_epoch_mil(&self) -> f64 {
-        self.time.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as f64
+        self.epoch_ms() as f64
     }
 
-    /// Get the stored time in epoch seconds.
+    /// Get the stored time in epoch seconds, with fraction. Use `epochSeconds` for an exact whole number.
     /// # Examples
     /// ```
     /// const t = new Unitime();
     /// const sec = t.epochSec;
     /// ```
     #[wasm_bindgen(getter, js_name = "epochSec")]
-    pub fn get_epoch_sec(&self) -> f32 {
-        self.time.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs_f32()
+    pub fn get_epoch_sec(&self) -> f64 {
+        self.nanos as f64 / 1e9
     }
 
     /// Get the total number of elapsed seconds, including hours and minutes, since the stored time compared to the current time.
@@ -109,13 +407,7 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(js_name = "getTotalElapsedSec")]
     pub fn get_total_elapsed_sec(&self) -> f64 {
-        let now : SystemTime = SystemTime::now();
-        let mut elapsed = now.duration_since(self.time).unwrap();
-        let hours = elapsed.as_secs() / 3600;
-        elapsed -= Duration::from_secs(hours * 3600);
-        let minutes = elapsed.as_secs() / 60;
-        elapsed -= Duration::from_secs(minutes * 60);
-        elapsed.as_secs() as f64 + minutes as f64 * 60.0 + hours as f64 * 3600.0
+        self.elapsed_secs() as f64
     }
 
     /// Get the total number of elapsed minutes, including hours, since the stored time compared to the current time.
@@ -126,48 +418,131 @@ impl Unitime {
     /// ```
     #[wasm_bindgen(js_name = "getTotalElapsedMin")]
     pub fn get_total_elapsed_min(&self) -> f64 {
-        let now : SystemTime = SystemTime::now();
-        let mut elapsed = now.duration_since(self.time).unwrap();
-        let hours = elapsed.as_secs() / 3600;
-        elapsed -= Duration::from_secs(hours * 3600);
-        let minutes = elapsed.as_secs() / 60;
-        elapsed -= Duration::from_secs(minutes * 60);
-        (hours * 60 + minutes) as f64
+        (self.elapsed_secs() / 60) as f64
     }
 
-    /// Get the duration from the stored time to the current time in HH:MM:SS format, where HH is only included if it is greater than 0.
+    /// Get the duration from the stored time to the current time in HH:MM:SS format, where HH is only
+    /// included if it is greater than 0. A stored time in the future gives a negative duration, such as
+    /// "-04:12", or with `style` `"relative"` a phrase such as "in 4m 12s" or "1h 3m ago". Other styles
+    /// give the HH:MM:SS form.
     /// # Examples
     /// ```
     /// const t = new Unitime();
     /// const str = t.getElapsedStr();
+    /// const deadline = Unitime.fromDateMillis(Date.now() + 252000);
+    /// deadline.getElapsedStr(); // "-04:12"
+    /// deadline.getElapsedStr("relative"); // "in 4m 12s"
     /// ```
     #[wasm_bindgen(js_name = "getElapsedStr")]
-    pub fn get_elapsed_str(&self) -> String {
-        let hours = self.get_elapsed_hours();
-        let minutes = self.get_elapsed_minutes();
-        let seconds = self.get_elapsed_seconds();
-
-        let mut result = String::new();
-
-        if hours != 0 {
-            if hours < 10 {
-                result.push_str("0");
-            }
-            result.push_str(&hours.to_string());
-            result.push_str(":");
-        }
+    pub fn get_elapsed_str(&self, style: Option<ElapsedStyle>) -> String {
+        Unitime::elapsed_str(self.elapsed_secs(), style.unwrap_or_default())
+    }
+}
 
-        if minutes < 10 {
-            result.push_str("0");
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds_from_now(secs: i64) -> Unitime {
+        Unitime::from_nanos(Unitime::new().nanos - secs as i128 * 1_000_000_000)
+    }
 
-        result.push_str(&minutes.to_string());
-        result.push_str(":");
-        if seconds < 10 {
-            result.push_str("0");
+    #[test]
+    fn elapsed_str_flips_sign_at_zero() {
+        let clock = |secs| Unitime::elapsed_str(secs, ElapsedStyle::Clock);
+        let relative = |secs| Unitime::elapsed_str(secs, ElapsedStyle::Relative);
+        assert_eq!(clock(-1), "-00:01");
+        assert_eq!(clock(0), "00:00");
+        assert_eq!(clock(1), "00:01");
+        assert_eq!(relative(-1), "in 1s");
+        assert_eq!(relative(0), "0s ago");
+        assert_eq!(relative(1), "1s ago");
+        assert_eq!(clock(-3852), "-01:04:12");
+        assert_eq!(relative(3852), "1h 4m 12s ago");
+    }
+
+    #[test]
+    fn elapsed_str_of_past_and_future_instants() {
+        // The extra half second of lead keeps the future instant at two hours while the test runs
+        let past = seconds_from_now(2 * 3600);
+        let future = Unitime::from_nanos(seconds_from_now(-2 * 3600).nanos + 500_000_000);
+        assert_eq!(past.get_elapsed_str(None), "02:00:00");
+        assert_eq!(past.get_elapsed_str(Some(ElapsedStyle::Relative)), "2h ago");
+        assert_eq!(future.get_elapsed_str(None), "-02:00:00");
+        assert_eq!(future.get_elapsed_str(Some(ElapsedStyle::Relative)), "in 2h");
+    }
+
+    const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+    fn from_seconds(secs: f64) -> Unitime {
+        Unitime::from_epoch_float(secs, NANOS_PER_SEC)
+    }
+
+    fn date_time(time: &Unitime) -> civil::DateTime {
+        civil::DateTime::from_epoch_ms(time.epoch_ms())
+    }
+
+    #[test]
+    fn seconds_past_the_i32_limit_keep_counting() {
+        let last = from_seconds(i32::MAX as f64);
+        let next = from_seconds(i32::MAX as f64 + 1.0);
+        assert_eq!(last.epoch_seconds(), i32::MAX as i64);
+        assert_eq!(next.epoch_seconds(), i32::MAX as i64 + 1);
+        assert_eq!(next.get_epoch_mil(), (i32::MAX as i64 + 1) as f64 * 1000.0);
+        assert_eq!(next.get_epoch_sec(), 2_147_483_648.0);
+        let date = date_time(&next);
+        assert_eq!((date.year, date.month, date.day), (2038, 1, 19));
+        assert_eq!((date.hour, date.minute, date.second), (3, 14, 8));
+    }
+
+    #[test]
+    fn seconds_past_the_u32_limit_keep_counting() {
+        let last = from_seconds(u32::MAX as f64);
+        let next = from_seconds(u32::MAX as f64 + 1.0);
+        assert_eq!(last.epoch_seconds(), u32::MAX as i64);
+        assert_eq!(next.epoch_seconds(), u32::MAX as i64 + 1);
+        assert_eq!(next.epoch_millis(), (u32::MAX as i64 + 1) * 1000);
+        let date = date_time(&next);
+        assert_eq!((date.year, date.month, date.day), (2106, 2, 7));
+        assert_eq!((date.hour, date.minute, date.second), (6, 28, 16));
+    }
+
+    #[test]
+    fn times_before_1970_stay_negative() {
+        assert_eq!(from_seconds(-1.0).epoch_seconds(), -1);
+        assert_eq!(from_seconds(-1.0).epoch_millis(), -1000);
+        assert_eq!(Unitime::from_epoch_float(-0.5, NANOS_PER_MILLI).epoch_millis(), -1);
+        assert_eq!(Unitime::from_epoch_float(-0.5, NANOS_PER_MILLI).epoch_nanos(), -500_000);
+        let first = from_seconds(i32::MIN as f64);
+        assert_eq!(first.epoch_seconds(), i32::MIN as i64);
+        let date = date_time(&first);
+        assert_eq!((date.year, date.month, date.day), (1901, 12, 13));
+        assert_eq!((date.hour, date.minute, date.second), (20, 45, 52));
+    }
+
+    #[test]
+    fn millisecond_limits_are_held_exactly() {
+        for ms in [i64::MIN, -1, 0, u32::MAX as i64 + 1, i64::MAX] {
+            let time = Unitime::from_epoch_ms(ms);
+            assert_eq!(time.epoch_millis(), ms);
+            assert_eq!(time.epoch_nanos(), ms as i128 * NANOS_PER_MILLI);
         }
-        result.push_str(&seconds.to_string());
+        assert_eq!(Unitime::from_epoch_ms(i64::MAX).epoch_seconds(), i64::MAX / 1000);
+        assert_eq!(Unitime::from_epoch_ms(i64::MIN).epoch_seconds(), i64::MIN.div_euclid(1000));
+    }
 
-        result
+    #[test]
+    fn u64_seconds_and_milliseconds_stop_at_the_bounds() {
+        for secs in [u64::MAX as f64, i64::MAX as f64, 1e300, f64::INFINITY] {
+            assert_eq!(from_seconds(secs).epoch_nanos(), MAX_NANOS);
+            assert_eq!(from_seconds(-secs).epoch_nanos(), MIN_NANOS);
+        }
+        for ms in [u64::MAX as f64, i64::MAX as f64] {
+            assert_eq!(Unitime::from_epoch_float(ms, NANOS_PER_MILLI).epoch_millis(), i64::MAX);
+            assert_eq!(Unitime::from_epoch_float(-ms, NANOS_PER_MILLI).epoch_millis(), i64::MIN);
+        }
+        assert_eq!(Unitime::from_epoch_ms(i64::MAX).epoch_micros(), i64::MAX);
+        assert_eq!(Unitime::from_epoch_ms(i64::MIN).epoch_micros(), i64::MIN);
+        assert_eq!(from_seconds(f64::NAN).epoch_nanos(), 0);
     }
 }
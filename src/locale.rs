@@ -0,0 +1,291 @@
+//! Built-in locale data for the formatter: month and weekday names, ordinals and preset date and time patterns.
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Names and ordinal rules for one language.
+#[cfg_attr(not(feature = "format"), allow(dead_code))]
+pub(crate) struct Locale {
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    /// Weekday names starting from Sunday.
+    pub weekdays: [&'static str; 7],
+    pub weekdays_short: [&'static str; 7],
+    /// Write a day of the month as an ordinal the way dates do, e.g. "3rd" in English or "1er" in French.
+    pub ordinal: fn(u32) -> String,
+    /// Format patterns for short, medium, long and full dates.
+    pub date_styles: [&'static str; 4],
+    /// Format pattern for a month and year with no day, as in "June 2024".
+    pub month_year: &'static str,
+    /// Format patterns for short and medium times. Long and full times add the UTC offset.
+    pub time_styles: [&'static str; 2],
+    /// What goes between date and time, for short and medium dates and for long and full dates.
+    pub date_time_separators: [&'static str; 2],
+    /// The word for the day before today, capitalized to stand alone as a label.
+    #[cfg_attr(not(feature = "tz"), allow(dead_code))]
+    pub yesterday: &'static str,
+    /// Names of seconds, minutes, hours, days, weeks, months and years, singular then plural.
+    pub units: [[&'static str; 2]; 7],
+    /// Whether a count of `n` takes the plural, which French and Portuguese only give from 2 up.
+    pub plural: fn(u64) -> bool,
+    /// Unit names inside relative phrases where they differ from `units`, as German datives do.
+    pub relative_units: Option<[[&'static str; 2]; 7]>,
+    /// Phrases for a time in the past and in the future, with `{}` for the count and unit.
+    pub relative: [&'static str; 2],
+    pub just_now: &'static str,
+}
+
+fn english_ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+/// Romance languages only mark the first of the month in dates.
+fn first_only_ordinal(n: u32, suffix: &str) -> String {
+    if n == 1 {
+        format!("1{suffix}")
+    } else {
+        n.to_string()
+    }
+}
+
+const EN: Locale = Locale {
+    months: [
+        "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+        "December",
+    ],
+    months_short: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+    weekdays: ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"],
+    weekdays_short: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+    ordinal: english_ordinal,
+    date_styles: ["%-m/%-d/%y", "%b %-d, %Y", "%B %-d, %Y", "%A, %B %-d, %Y"],
+    month_year: "%B %Y",
+    time_styles: ["%-I:%M %p", "%-I:%M:%S %p"],
+    date_time_separators: [", ", " at "],
+    yesterday: "Yesterday",
+    units: [
+        ["second", "seconds"], ["minute", "minutes"], ["hour", "hours"], ["day", "days"], ["week", "weeks"],
+        ["month", "months"], ["year", "years"],
+    ],
+    plural: |n| n != 1,
+    relative_units: None,
+    relative: ["{} ago", "in {}"],
+    just_now: "just now",
+};
+
+/// German unit names after "vor" and "in", which take the dative plural.
+const DE_DATIVE: [[&str; 2]; 7] = [
+    ["Sekunde", "Sekunden"], ["Minute", "Minuten"], ["Stunde", "Stunden"], ["Tag", "Tagen"], ["Woche", "Wochen"],
+    ["Monat", "Monaten"], ["Jahr", "Jahren"],
+];
+
+const DE: Locale = Locale {
+    months: [
+        "Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November",
+        "Dezember",
+    ],
+    months_short: ["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+    weekdays: ["Sonntag", "Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag"],
+    weekdays_short: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+    ordinal: |n| format!("{n}."),
+    date_styles: ["%d.%m.%y", "%d.%m.%Y", "%-d. %B %Y", "%A, %-d. %B %Y"],
+    month_year: "%B %Y",
+    time_styles: ["%H:%M", "%H:%M:%S"],
+    date_time_separators: [", ", " um "],
+    yesterday: "Gestern",
+    units: [
+        ["Sekunde", "Sekunden"], ["Minute", "Minuten"], ["Stunde", "Stunden"], ["Tag", "Tage"], ["Woche", "Wochen"],
+        ["Monat", "Monate"], ["Jahr", "Jahre"],
+    ],
+    plural: |n| n != 1,
+    relative_units: Some(DE_DATIVE),
+    relative: ["vor {}", "in {}"],
+    just_now: "gerade eben",
+};
+
+const FR: Locale = Locale {
+    months: [
+        "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre",
+        "décembre",
+    ],
+    months_short: [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc.",
+    ],
+    weekdays: ["dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi"],
+    weekdays_short: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+    ordinal: |n| first_only_ordinal(n, "er"),
+    date_styles: ["%d/%m/%Y", "%-d %b %Y", "%-d %B %Y", "%A %-d %B %Y"],
+    month_year: "%B %Y",
+    time_styles: ["%H:%M", "%H:%M:%S"],
+    date_time_separators: [" ", " à "],
+    yesterday: "Hier",
+    units: [
+        ["seconde", "secondes"], ["minute", "minutes"], ["heure", "heures"], ["jour", "jours"], ["semaine", "semaines"],
+        ["mois", "mois"], ["an", "ans"],
+    ],
+    plural: |n| n > 1,
+    relative_units: None,
+    relative: ["il y a {}", "dans {}"],
+    just_now: "à l'instant",
+};
+
+const ES: Locale = Locale {
+    months: [
+        "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre",
+        "diciembre",
+    ],
+    months_short: ["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic"],
+    weekdays: ["domingo", "lunes", "martes", "miércoles", "jueves", "viernes", "sábado"],
+    weekdays_short: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+    ordinal: |n| first_only_ordinal(n, ".º"),
+    date_styles: ["%-d/%-m/%y", "%-d %b %Y", "%-d de %B de %Y", "%A, %-d de %B de %Y"],
+    month_year: "%B de %Y",
+    time_styles: ["%-H:%M", "%-H:%M:%S"],
+    date_time_separators: [", ", ", "],
+    yesterday: "Ayer",
+    units: [
+        ["segundo", "segundos"], ["minuto", "minutos"], ["hora", "horas"], ["día", "días"], ["semana", "semanas"],
+        ["mes", "meses"], ["año", "años"],
+    ],
+    plural: |n| n != 1,
+    relative_units: None,
+    relative: ["hace {}", "dentro de {}"],
+    just_now: "ahora mismo",
+};
+
+const IT: Locale = Locale {
+    months: [
+        "gennaio", "febbraio", "marzo", "aprile", "maggio", "giugno", "luglio", "agosto", "settembre", "ottobre",
+        "novembre", "dicembre",
+    ],
+    months_short: ["gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic"],
+    weekdays: ["domenica", "lunedì", "martedì", "mercoledì", "giovedì", "venerdì", "sabato"],
+    weekdays_short: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+    ordinal: |n| first_only_ordinal(n, "º"),
+    date_styles: ["%d/%m/%y", "%-d %b %Y", "%-d %B %Y", "%A %-d %B %Y"],
+    month_year: "%B %Y",
+    time_styles: ["%H:%M", "%H:%M:%S"],
+    date_time_separators: [", ", " alle ore "],
+    yesterday: "Ieri",
+    units: [
+        ["secondo", "secondi"], ["minuto", "minuti"], ["ora", "ore"], ["giorno", "giorni"], ["settimana", "settimane"],
+        ["mese", "mesi"], ["anno", "anni"],
+    ],
+    plural: |n| n != 1,
+    relative_units: None,
+    relative: ["{} fa", "tra {}"],
+    just_now: "proprio ora",
+};
+
+const NL: Locale = Locale {
+    months: [
+        "januari", "februari", "maart", "april", "mei", "juni", "juli", "augustus", "september", "oktober", "november",
+        "december",
+    ],
+    months_short: ["jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec"],
+    weekdays: ["zondag", "maandag", "dinsdag", "woensdag", "donderdag", "vrijdag", "zaterdag"],
+    weekdays_short: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+    ordinal: |n| format!("{n}e"),
+    date_styles: ["%d-%m-%Y", "%-d %b %Y", "%-d %B %Y", "%A %-d %B %Y"],
+    month_year: "%B %Y",
+    time_styles: ["%H:%M", "%H:%M:%S"],
+    date_time_separators: [", ", " om "],
+    yesterday: "Gisteren",
+    units: [
+        ["seconde", "seconden"], ["minuut", "minuten"], ["uur", "uur"], ["dag", "dagen"], ["week", "weken"],
+        ["maand", "maanden"], ["jaar", "jaar"],
+    ],
+    plural: |n| n != 1,
+    relative_units: None,
+    relative: ["{} geleden", "over {}"],
+    just_now: "zojuist",
+};
+
+const PT: Locale = Locale {
+    months: [
+        "janeiro", "fevereiro", "março", "abril", "maio", "junho", "julho", "agosto", "setembro", "outubro", "novembro",
+        "dezembro",
+    ],
+    months_short: ["jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez"],
+    weekdays: ["domingo", "segunda-feira", "terça-feira", "quarta-feira", "quinta-feira", "sexta-feira", "sábado"],
+    weekdays_short: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+    ordinal: |n| first_only_ordinal(n, "º"),
+    date_styles: ["%d/%m/%Y", "%-d de %b de %Y", "%-d de %B de %Y", "%A, %-d de %B de %Y"],
+    month_year: "%B de %Y",
+    time_styles: ["%H:%M", "%H:%M:%S"],
+    date_time_separators: [", ", " às "],
+    yesterday: "Ontem",
+    units: [
+        ["segundo", "segundos"], ["minuto", "minutos"], ["hora", "horas"], ["dia", "dias"], ["semana", "semanas"],
+        ["mês", "meses"], ["ano", "anos"],
+    ],
+    plural: |n| n > 1,
+    relative_units: None,
+    relative: ["há {}", "em {}"],
+    just_now: "agora mesmo",
+};
+
+impl Locale {
+    /// Look up a locale by BCP 47 tag such as "en-GB" or "pt_BR", going by the language alone.
+    /// Languages without built-in data, and a missing tag, get English.
+    pub fn get(tag: Option<&str>) -> &'static Locale {
+        let language = tag.and_then(|t| t.split(['-', '_']).next()).unwrap_or("en").to_ascii_lowercase();
+        match language.as_str() {
+            "de" => &DE,
+            "fr" => &FR,
+            "es" => &ES,
+            "it" => &IT,
+            "nl" => &NL,
+            "pt" => &PT,
+            _ => &EN,
+        }
+    }
+}
+
+/// How long the names returned by `monthNames` and `weekdayNames` are, as in the options of `Intl.DateTimeFormat`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameWidth {
+    /// A single letter, e.g. "J" for January.
+    Narrow = "narrow",
+    Short = "short",
+    Long = "long",
+}
+
+fn names(long: &[&str], short: &[&str], width: Option<NameWidth>) -> Result<Vec<String>, ValidationError> {
+    match width.unwrap_or(NameWidth::Long) {
+        NameWidth::Long => Ok(long.iter().map(|n| n.to_string()).collect()),
+        NameWidth::Short => Ok(short.iter().map(|n| n.to_string()).collect()),
+        NameWidth::Narrow => Ok(long.iter().map(|n| n.chars().take(1).flat_map(char::to_uppercase).collect()).collect()),
+        _ => Err(ValidationError::new("unknown name width")),
+    }
+}
+
+/// Get the names of the months from January in `locale` (English by default), the same ones `format` uses.
+/// # Examples
+/// ```
+/// const headers = monthNames("de", "short"); // ["Jan", "Feb", "Mär", ...]
+/// ```
+#[wasm_bindgen(js_name = "monthNames")]
+pub fn month_names(locale: Option<String>, width: Option<NameWidth>) -> Result<Vec<String>, ValidationError> {
+    let locale = Locale::get(locale.as_deref());
+    names(&locale.months, &locale.months_short, width)
+}
+
+/// Get the names of the weekdays from Sunday in `locale` (English by default), the same ones `format` uses.
+/// # Examples
+/// ```
+/// const headers = weekdayNames("fr", "narrow"); // ["D", "L", "M", "M", "J", "V", "S"]
+/// ```
+#[wasm_bindgen(js_name = "weekdayNames")]
+pub fn weekday_names(locale: Option<String>, width: Option<NameWidth>) -> Result<Vec<String>, ValidationError> {
+    let locale = Locale::get(locale.as_deref());
+    names(&locale.weekdays, &locale.weekdays_short, width)
+}
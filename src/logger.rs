@@ -0,0 +1,70 @@
+use std::cell::{Cell, RefCell};
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::Unitime;
+
+/// What a message passed to the logger is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LogKind {
+    /// A read of the system or monotonic clock.
+    Clock,
+    /// A clock correction, such as one from a server, put into use.
+    Sync,
+    /// The offset of an IANA zone looked up.
+    #[cfg_attr(not(feature = "tz"), allow(dead_code))]
+    Tz,
+    /// Input a parser could not read.
+    Parse,
+}
+
+impl LogKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            LogKind::Clock => "clock",
+            LogKind::Sync => "sync",
+            LogKind::Tz => "tz",
+            LogKind::Parse => "parse",
+        }
+    }
+}
+
+thread_local! {
+    static LOGGER: RefCell<Option<Function>> = const { RefCell::new(None) };
+    /// Whether the logger is running, so that the clock reads and lookups it makes itself go unlogged.
+    static LOGGING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Pass the message `message` makes to the logger, if one is set. Nothing is formatted without one.
+pub(crate) fn log(kind: LogKind, message: impl FnOnce() -> String) {
+    if LOGGING.with(Cell::get) {
+        return;
+    }
+    let Some(logger) = LOGGER.with(|logger| logger.borrow().clone()) else { return };
+    LOGGING.with(|logging| logging.set(true));
+    // A logger that throws must not break the time math it reports on.
+    let _ = logger.call2(&JsValue::NULL, &kind.as_str().into(), &message().into());
+    LOGGING.with(|logging| logging.set(false));
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Set a function that is called with a kind and a message as Unitime works, to find out in
+    /// production why a countdown or label shows what it does, or remove it by passing nothing. The kind
+    /// is `"clock"` for each read of the system or monotonic clock, `"sync"` for each clock correction
+    /// put into use, `"tz"` for each lookup of a time zone offset, and `"parse"` for each input a parser
+    /// could not read. Clock reads are frequent, so the logger should be cheap or filter them. What it
+    /// throws is ignored.
+    /// # Examples
+    /// ```
+    /// Unitime.setLogger((kind, message) => {
+    ///     if (kind !== "clock") console.debug(`[unitime ${kind}] ${message}`);
+    /// });
+    /// Unitime.setLogger(); // stop logging
+    /// ```
+    #[wasm_bindgen(js_name = "setLogger")]
+    pub fn set_logger(logger: Option<Function>) {
+        LOGGER.with(|cell| *cell.borrow_mut() = logger);
+    }
+}
@@ -0,0 +1,142 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, DateTime, MS_PER_DAY, MS_PER_MIN};
+use crate::parse::Cursor;
+use crate::validation::{char_offset, ValidationError};
+use crate::Unitime;
+
+/// Read the `hh:mm:ss[.fff]` part shared by the log formats into `dt`.
+fn parse_clock(c: &mut Cursor, dt: &mut DateTime) -> Option<()> {
+    dt.hour = c.fixed(2)?;
+    if !c.eat(b':') {
+        return None;
+    }
+    dt.minute = c.fixed(2)?;
+    if !c.eat(b':') {
+        return None;
+    }
+    dt.second = c.fixed(2)?;
+    if c.eat(b'.') {
+        dt.milli = c.fraction_ms()?;
+    }
+    Some(())
+}
+
+/// Parse a BSD syslog timestamp such as `Jun  1 12:03:04`. The format carries no year, so `year` is filled in.
+pub(crate) fn parse_syslog(c: &mut Cursor, year: i64) -> Option<DateTime> {
+    let month = c.month_abbr()?;
+    if !matches!(c.peek(), Some(b' ')) {
+        return None;
+    }
+    c.skip_spaces();
+    let day = c.digits(1, 2)?.0;
+    if !c.eat(b' ') {
+        return None;
+    }
+    let mut dt = DateTime { year, month, day, hour: 0, minute: 0, second: 0, milli: 0 };
+    parse_clock(c, &mut dt)?;
+    Some(dt)
+}
+
+/// Parse an Apache Common Log Format timestamp such as `01/Jun/2024:12:03:04 +0200`, without the brackets.
+/// Returns the wall-clock fields and the UTC offset in minutes, if present.
+pub(crate) fn parse_clf(c: &mut Cursor) -> Option<(DateTime, Option<i64>)> {
+    let day = c.fixed(2)?;
+    if !c.eat(b'/') {
+        return None;
+    }
+    let month = c.month_abbr()?;
+    if !c.eat(b'/') {
+        return None;
+    }
+    let year = c.fixed(4)? as i64;
+    if !c.eat(b':') {
+        return None;
+    }
+    let mut dt = DateTime { year, month, day, hour: 0, minute: 0, second: 0, milli: 0 };
+    parse_clock(c, &mut dt)?;
+    let offset = if c.eat(b' ') { c.utc_offset() } else { None };
+    Some((dt, offset))
+}
+
+/// Resolve a year-less syslog timestamp against `now`, stepping back a year when the
+/// result would lie in the future so that December entries read in January stay in the past.
+pub(crate) fn resolve_syslog(c: &mut Cursor, now_wall_ms: i64) -> Option<i64> {
+    let mut dt = parse_syslog(c, DateTime::from_epoch_ms(now_wall_ms).year)?;
+    if !dt.is_valid() || dt.to_epoch_ms() > now_wall_ms + MS_PER_DAY {
+        dt.year -= 1;
+    }
+    dt.is_valid().then(|| dt.to_epoch_ms())
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Creates a new `Unitime` from a BSD syslog timestamp such as `Jun  1 12:03:04`, ignoring the rest of the line.
+    /// Syslog timestamps have no year, so the given `year` is used, or else the most recent year that does not put the time in the future.
+    /// The timestamp is read as UTC if `utc` is `true`, else as local time.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromSyslog("Jun  1 12:03:04 host sshd[42]: session opened", false);
+    /// const t2 = Unitime.fromSyslog("Jun  1 12:03:04", true, 2024);
+    /// ```
+    #[wasm_bindgen(js_name = "fromSyslog")]
+    pub fn from_syslog(s: &str, utc: bool, year: Option<i32>) -> Result<Unitime, ValidationError> {
+        let lead = s.len() - s.trim_start().len();
+        let syntax = |c: &Cursor| {
+            ValidationError::syntax(format!("invalid syslog timestamp: {s}"), char_offset(s, lead + c.pos()))
+        };
+        let mut c = Cursor::new(s.trim_start());
+        let wall = match year {
+            Some(year) => {
+                let dt = parse_syslog(&mut c, year as i64).ok_or_else(|| syntax(&c))?;
+                dt.validate()?;
+                dt.to_epoch_ms()
+            }
+            None => {
+                let now = crate::now_ms();
+                match resolve_syslog(&mut c, civil::instant_to_wall(now, utc)) {
+                    Some(wall) => wall,
+                    // Parse again to find the cause. A time valid in the leap year 2000 can only have
+                    // failed as February 29 in a recent year that is not a leap year.
+                    None => {
+                        let mut c = Cursor::new(s.trim_start());
+                        let dt = parse_syslog(&mut c, 2000).ok_or_else(|| syntax(&c))?;
+                        dt.validate()?;
+                        return Err(ValidationError::range("day", dt.day as i64, 1, 28));
+                    }
+                }
+            }
+        };
+        if !(c.is_end() || matches!(c.peek(), Some(b' ' | b'\t'))) {
+            return Err(syntax(&c));
+        }
+        Ok(Unitime::from_epoch_ms(civil::wall_to_instant(wall, utc)))
+    }
+
+    /// Creates a new `Unitime` from an Apache Common Log Format timestamp such as `[01/Jun/2024:12:03:04 +0200]`.
+    /// The brackets are optional and, if present, the timestamp may be embedded in a full log line.
+    /// A timestamp without an offset is read as UTC.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromCLF('127.0.0.1 - - [01/Jun/2024:12:03:04 +0200] "GET / HTTP/1.1" 200');
+    /// ```
+    #[wasm_bindgen(js_name = "fromCLF")]
+    pub fn from_clf(s: &str) -> Result<Unitime, ValidationError> {
+        let (start, bracketed) = match s.find('[') {
+            Some(i) => (i + 1, true),
+            None => (s.len() - s.trim_start().len(), false),
+        };
+        let inner = if bracketed { &s[start..] } else { s.trim() };
+        let syntax = |c: &Cursor| {
+            ValidationError::syntax(format!("invalid CLF timestamp: {s}"), char_offset(s, start + c.pos()))
+        };
+        let mut c = Cursor::new(inner);
+        let (dt, offset) = parse_clf(&mut c).ok_or_else(|| syntax(&c))?;
+        dt.validate()?;
+        let closed = if bracketed { c.eat(b']') } else { c.is_end() };
+        if !closed {
+            return Err(syntax(&c));
+        }
+        Ok(Unitime::from_epoch_ms(dt.to_epoch_ms() - offset.unwrap_or(0) * MS_PER_MIN))
+    }
+}
@@ -0,0 +1,137 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Media position of a `<video>` or `<audio>` element tied to the wall clock, for subtitle and
+/// annotation tools that turn media positions into real timestamps and back. Feed it the element's
+/// `currentTime` with `sync`, on `timeupdate` or whenever it is read, and tell it about `ratechange`,
+/// `pause` and `play`; between syncs it runs on at the playback rate. Every method taking `now` uses the
+/// current time in epoch milliseconds if it is omitted.
+/// # Examples
+/// ```
+/// const clock = new MediaClock();
+/// video.ontimeupdate = () => clock.sync(video.currentTime);
+/// video.onratechange = () => clock.setPlaybackRate(video.playbackRate);
+/// video.onpause = () => clock.pause();
+/// video.onplay = () => clock.play();
+/// const note = { at: new Unitime(), position: clock.currentTime() };
+/// ```
+#[wasm_bindgen]
+pub struct MediaClock {
+    /// Media position in seconds and the epoch millisecond it was at, once synced.
+    anchor: Option<(f64, f64)>,
+    rate: f64,
+    paused: bool,
+}
+
+fn now_or(now: Option<f64>) -> f64 {
+    now.unwrap_or_else(|| Unitime::new().get_epoch_mil())
+}
+
+impl MediaClock {
+    /// Media seconds that pass per wall-clock millisecond.
+    fn speed(&self) -> f64 {
+        if self.paused {
+            0.0
+        } else {
+            self.rate / 1000.0
+        }
+    }
+
+    /// Media position at epoch millisecond `ms`.
+    fn position(&self, ms: f64) -> Option<f64> {
+        self.anchor.map(|(position, at)| position + (ms - at) * self.speed())
+    }
+
+    /// Move the anchor to `now` before the rate or pause state changes, so earlier positions stay put.
+    fn re_anchor(&mut self, now: Option<f64>) {
+        if self.anchor.is_some() {
+            let now = now_or(now);
+            self.anchor = self.position(now).map(|position| (position, now));
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl MediaClock {
+    /// Creates a new `MediaClock` playing at `playbackRate`, 1 by default, not yet synced.
+    #[wasm_bindgen(constructor)]
+    pub fn new(playback_rate: Option<f64>) -> Result<MediaClock, ValidationError> {
+        let mut clock = MediaClock { anchor: None, rate: 1.0, paused: false };
+        clock.set_playback_rate(playback_rate.unwrap_or(1.0), None)?;
+        Ok(clock)
+    }
+
+    /// Record that the media was at `mediaTime` seconds at `now`.
+    pub fn sync(&mut self, media_time: f64, now: Option<f64>) -> Result<(), ValidationError> {
+        if !media_time.is_finite() || media_time < 0.0 {
+            return Err(ValidationError::new("media time must be a non-negative number of seconds"));
+        }
+        self.anchor = Some((media_time, now_or(now)));
+        Ok(())
+    }
+
+    /// Change the playback rate at `now`, keeping the positions before it where they were. Negative
+    /// rates play backward.
+    #[wasm_bindgen(js_name = "setPlaybackRate")]
+    pub fn set_playback_rate(&mut self, rate: f64, now: Option<f64>) -> Result<(), ValidationError> {
+        if !rate.is_finite() {
+            return Err(ValidationError::new("playback rate must be a finite number"));
+        }
+        self.re_anchor(now);
+        self.rate = rate;
+        Ok(())
+    }
+
+    /// Get the playback rate.
+    #[wasm_bindgen(getter, js_name = "playbackRate")]
+    pub fn playback_rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Stop the media position at `now` until `play`.
+    pub fn pause(&mut self, now: Option<f64>) {
+        self.re_anchor(now);
+        self.paused = true;
+    }
+
+    /// Let the media position run on from `now` at the playback rate.
+    pub fn play(&mut self, now: Option<f64>) {
+        self.re_anchor(now);
+        self.paused = false;
+    }
+
+    /// Whether the media position is stopped by `pause`.
+    #[wasm_bindgen(getter)]
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Get the media position in seconds at `now`, or `undefined` before the first `sync`.
+    #[wasm_bindgen(js_name = "currentTime")]
+    pub fn current_time(&self, now: Option<f64>) -> Option<f64> {
+        self.position(now_or(now))
+    }
+
+    /// Get the media position in seconds at the wall-clock instant `at`, assuming the playback rate and
+    /// pause state stay as they are, or `undefined` before the first `sync`.
+    #[wasm_bindgen(js_name = "mediaTimeAt")]
+    pub fn media_time_at(&self, at: &Unitime) -> Option<f64> {
+        self.position(at.get_epoch_mil())
+    }
+
+    /// Get the wall-clock instant at which the media reaches `mediaTime` seconds, assuming the playback
+    /// rate and pause state stay as they are. Positions before the last sync give instants before it.
+    /// While paused, or before the first `sync`, there is none and this is `undefined`.
+    /// # Examples
+    /// ```
+    /// const shownAt = clock.instantAt(cue.startTime);
+    /// ```
+    #[wasm_bindgen(js_name = "instantAt")]
+    pub fn instant_at(&self, media_time: f64) -> Option<Unitime> {
+        let speed = self.speed();
+        let (position, at) = self.anchor.filter(|_| speed != 0.0)?;
+        Some(Unitime::from_epoch_float(at + (media_time - position) / speed, crate::NANOS_PER_MILLI))
+    }
+}
@@ -0,0 +1,145 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Longest look-ahead a `Metronome` takes, in milliseconds.
+const MAX_LOOK_AHEAD_MS: f64 = 60_000.0;
+
+/// Most beats one `schedule` call returns.
+const MAX_BEATS_PER_SCHEDULE: usize = 1000;
+
+/// Beat scheduler for Web Audio. Each call to `schedule` returns the beats falling inside the look-ahead
+/// window on the `performance.now()` clock. Beat times are computed from a fixed anchor, not by adding up
+/// intervals, so they never drift.
+#[wasm_bindgen]
+pub struct Metronome {
+    beat_ms: f64,
+    beats_per_bar: u32,
+    look_ahead_ms: f64,
+    /// Time and number of a beat that every other beat time is derived from, once started.
+    anchor: Option<(f64, u64)>,
+    next_beat: u64,
+    last_batch: Vec<u32>,
+}
+
+fn beat_ms(bpm: f64) -> Result<f64, ValidationError> {
+    if !bpm.is_finite() || bpm <= 0.0 {
+        return Err(ValidationError::new("tempo must be positive"));
+    }
+    Ok(60_000.0 / bpm)
+}
+
+impl Metronome {
+    fn beat_time(&self, beat: u64) -> Option<f64> {
+        self.anchor
+            .map(|(time, number)| time + (beat as f64 - number as f64) * self.beat_ms)
+    }
+}
+
+#[wasm_bindgen]
+impl Metronome {
+    /// Creates a new, stopped `Metronome` at `bpm` beats per minute, scheduling `look_ahead_ms` ahead,
+    /// up to a minute, with `beats_per_bar` beats in a bar (4 by default).
+    /// # Examples
+    /// ```
+    /// const m = new Metronome(120, 100);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(bpm: f64, look_ahead_ms: f64, beats_per_bar: Option<u32>) -> Result<Metronome, ValidationError> {
+        if !(look_ahead_ms > 0.0 && look_ahead_ms <= MAX_LOOK_AHEAD_MS) {
+            return Err(ValidationError::new("look-ahead must be positive and at most a minute"));
+        }
+        Ok(Metronome {
+            beat_ms: beat_ms(bpm)?,
+            beats_per_bar: beats_per_bar.unwrap_or(4).max(1),
+            look_ahead_ms,
+            anchor: None,
+            next_beat: 0,
+            last_batch: Vec::new(),
+        })
+    }
+
+    /// Start with the first beat at `at` on the `performance.now()` clock, or now if omitted.
+    pub fn start(&mut self, at: Option<f64>) {
+        self.anchor = Some((at.unwrap_or_else(crate::monotonic_ms), 0));
+        self.next_beat = 0;
+    }
+
+    /// Stop scheduling beats.
+    pub fn stop(&mut self) {
+        self.anchor = None;
+    }
+
+    /// Whether the metronome has been started.
+    #[wasm_bindgen(getter)]
+    pub fn running(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Get the tempo in beats per minute.
+    #[wasm_bindgen(getter)]
+    pub fn bpm(&self) -> f64 {
+        60_000.0 / self.beat_ms
+    }
+
+    /// Change the tempo from the next unscheduled beat on, keeping already scheduled beats in place.
+    #[wasm_bindgen(js_name = "setBpm")]
+    pub fn set_bpm(&mut self, bpm: f64) -> Result<(), ValidationError> {
+        let new_beat_ms = beat_ms(bpm)?;
+        if let Some(time) = self.beat_time(self.next_beat) {
+            self.anchor = Some((time, self.next_beat));
+        }
+        self.beat_ms = new_beat_ms;
+        Ok(())
+    }
+
+    /// Get the time of the next beat not yet returned by `schedule`, or `undefined` if stopped.
+    #[wasm_bindgen(getter, js_name = "nextBeatTime")]
+    pub fn next_beat_time(&self) -> Option<f64> {
+        self.beat_time(self.next_beat)
+    }
+
+    /// Get the times on the `performance.now()` clock of the beats that start before `now` (the current time
+    /// if omitted) plus the look-ahead and have not been returned before. Beats that were missed entirely,
+    /// e.g. while the tab was in the background, are skipped rather than returned late. At most 1000 beats
+    /// are returned at a time, leaving the rest for the next call.
+    /// # Examples
+    /// ```
+    /// setInterval(() => {
+    ///   for (const t of m.schedule()) playClick(toAudioContextTime(t, ctx.currentTime, ctx.baseLatency));
+    /// }, 25);
+    /// ```
+    pub fn schedule(&mut self, now: Option<f64>) -> Vec<f64> {
+        self.last_batch.clear();
+        let Some((anchor_time, anchor_beat)) = self.anchor else {
+            return Vec::new();
+        };
+        let now = now.unwrap_or_else(crate::monotonic_ms);
+        let first_pending = ((now - anchor_time) / self.beat_ms).ceil();
+        if first_pending > (self.next_beat - anchor_beat) as f64 {
+            self.next_beat = anchor_beat.saturating_add(first_pending as u64);
+        }
+        let horizon = now + self.look_ahead_ms;
+        let mut times = Vec::new();
+        while times.len() < MAX_BEATS_PER_SCHEDULE {
+            let Some(t) = self.beat_time(self.next_beat).filter(|&t| t < horizon) else {
+                break;
+            };
+            times.push(t);
+            self.last_batch.push((self.next_beat % self.beats_per_bar as u64) as u32 + 1);
+            self.next_beat = self.next_beat.saturating_add(1);
+        }
+        times
+    }
+
+    /// Get the position within the bar, starting at 1 for the downbeat, of each beat returned by the last `schedule` call.
+    /// # Examples
+    /// ```
+    /// const times = m.schedule();
+    /// m.lastBeatNumbers.forEach((n, i) => playClick(times[i], n === 1));
+    /// ```
+    #[wasm_bindgen(getter, js_name = "lastBeatNumbers")]
+    pub fn last_beat_numbers(&self) -> Vec<u32> {
+        self.last_batch.clone()
+    }
+}
@@ -0,0 +1,172 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{civil_from_days, days_from_civil, days_in_month, weekday, wall_to_instant, MS_PER_DAY};
+use crate::validation::{check_date, ValidationError};
+use crate::Unitime;
+
+/// Midnight starting the day `days` after 1970-01-01, in local time unless `utc` is `true`, or the
+/// nearer end of the range of `Unitime` for days beyond it.
+pub(crate) fn midnight(days: i64, utc: Option<bool>) -> Unitime {
+    Unitime::from_epoch_ms(wall_to_instant(days.saturating_mul(MS_PER_DAY), utc.unwrap_or(false)))
+}
+
+/// One day of the grid `monthGrid` gives.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct GridDay {
+    /// Days since 1970-01-01.
+    days: i64,
+    in_month: bool,
+}
+
+#[wasm_bindgen]
+impl GridDay {
+    /// Get the year.
+    #[wasm_bindgen(getter)]
+    pub fn year(&self) -> i32 {
+        civil_from_days(self.days).0 as i32
+    }
+
+    /// Get the month, from 1 for January.
+    #[wasm_bindgen(getter)]
+    pub fn month(&self) -> u32 {
+        civil_from_days(self.days).1
+    }
+
+    /// Get the day of the month, starting at 1.
+    #[wasm_bindgen(getter)]
+    pub fn day(&self) -> u32 {
+        civil_from_days(self.days).2
+    }
+
+    /// Get the day of the week, from 0 for Sunday.
+    #[wasm_bindgen(getter)]
+    pub fn weekday(&self) -> u32 {
+        weekday(self.days) as u32
+    }
+
+    /// Check whether the day belongs to the month of the grid rather than the one before or after.
+    #[wasm_bindgen(getter, js_name = "inMonth")]
+    pub fn in_month(&self) -> bool {
+        self.in_month
+    }
+
+    /// Get midnight at the start of the day, in local time unless `utc` is `true`.
+    #[wasm_bindgen(js_name = "toUnitime")]
+    pub fn to_unitime(&self, utc: Option<bool>) -> Unitime {
+        midnight(self.days, utc)
+    }
+}
+
+/// The first day shown for `month` of `year` in weeks starting on `week_start`, and the number of weeks
+/// the month spans.
+fn grid_start(year: i32, month: u32, week_start: Option<u32>) -> Result<(i64, i64), ValidationError> {
+    check_date(year as i64, month, 1)?;
+    let week_start = week_start.unwrap_or(0);
+    if week_start > 6 {
+        return Err(ValidationError::range("weekStart", week_start as i64, 0, 6));
+    }
+    let first = days_from_civil(year as i64, month, 1);
+    let lead = (weekday(first) as i64 - week_start as i64).rem_euclid(7);
+    let weeks = (lead + days_in_month(year as i64, month) as i64 + 6) / 7;
+    Ok((first - lead, weeks))
+}
+
+/// Get the days to show for `month` of `year` in a calendar widget, as 6 weeks of 7 days starting on
+/// `weekStart`, from 0 for Sunday (the default) to 6. Days before and after the month fill the first
+/// and last weeks and are marked as not `inMonth`. The grid is always 6 weeks so that the widget keeps
+/// its height from month to month; `weeksInMonth` says how many of them the month reaches.
+/// # Examples
+/// ```
+/// for (const week of monthGrid(2024, 6, 1)) {
+///     row(week.map(d => cell(d.day, d.inMonth)));
+/// }
+/// monthGrid(2024, 6, 1)[0][0].day; // 27, Monday May 27
+/// ```
+#[wasm_bindgen(js_name = "monthGrid")]
+pub fn month_grid(year: i32, month: u32, week_start: Option<u32>) -> Result<Array, ValidationError> {
+    let (start, _) = grid_start(year, month, week_start)?;
+    let first = days_from_civil(year as i64, month, 1);
+    let month_days = first..first + days_in_month(year as i64, month) as i64;
+    Ok((0..6)
+        .map(|week| {
+            (0..7)
+                .map(|d| {
+                    let days = start + week * 7 + d;
+                    JsValue::from(GridDay { days, in_month: month_days.contains(&days) })
+                })
+                .collect::<Array>()
+        })
+        .collect())
+}
+
+/// Get the number of weeks starting on `weekStart`, from 0 for Sunday (the default) to 6, that `month`
+/// of `year` touches: 4 to 6, the rows of its `monthGrid` that hold days of the month.
+/// # Examples
+/// ```
+/// weeksInMonth(2024, 6); // 6
+/// weeksInMonth(2024, 6, 1); // 5
+/// weeksInMonth(2026, 2); // 4
+/// ```
+#[wasm_bindgen(js_name = "weeksInMonth")]
+pub fn weeks_in_month(year: i32, month: u32, week_start: Option<u32>) -> Result<u32, ValidationError> {
+    Ok(grid_start(year, month, week_start)?.1 as u32)
+}
+
+/// Check a weekday, from 0 for Sunday to 6 for Saturday.
+fn check_weekday(weekday: u32) -> Result<(), ValidationError> {
+    if weekday > 6 {
+        return Err(ValidationError::range("weekday", weekday as i64, 0, 6));
+    }
+    Ok(())
+}
+
+/// Get midnight starting the `n`th `weekday`, from 0 for Sunday to 6 for Saturday, of `month` in `year`,
+/// in local time unless `utc` is `true`, as in rules such as "the third Thursday". Fails if the month
+/// has fewer than `n` of them, as it does for most fifth weekdays.
+/// # Examples
+/// ```
+/// nthWeekdayOfMonth(2024, 11, 4, 4); // Thanksgiving, Thursday November 28
+/// nthWeekdayOfMonth(2024, 1, 1, 3, true); // Martin Luther King Jr. Day, January 15
+/// ```
+#[wasm_bindgen(js_name = "nthWeekdayOfMonth")]
+pub fn nth_weekday_of_month(
+    year: i32,
+    month: u32,
+    weekday: u32,
+    n: u32,
+    utc: Option<bool>,
+) -> Result<Unitime, ValidationError> {
+    check_date(year as i64, month, 1)?;
+    check_weekday(weekday)?;
+    let first = days_from_civil(year as i64, month, 1);
+    let first_match = (weekday as i64 - self::weekday(first) as i64).rem_euclid(7);
+    let count = (days_in_month(year as i64, month) as i64 - first_match + 6) / 7;
+    if n == 0 || n as i64 > count {
+        return Err(ValidationError::range("n", n as i64, 1, count));
+    }
+    let days = first + first_match + 7 * (n as i64 - 1);
+    Ok(midnight(days, utc))
+}
+
+/// Get midnight starting the last `weekday`, from 0 for Sunday to 6 for Saturday, of `month` in `year`,
+/// in local time unless `utc` is `true`.
+/// # Examples
+/// ```
+/// lastWeekdayOfMonth(2024, 5, 1); // Memorial Day, Monday May 27
+/// lastWeekdayOfMonth(2024, 10, 0, true); // Sunday October 27, when summer time ends in the EU
+/// ```
+#[wasm_bindgen(js_name = "lastWeekdayOfMonth")]
+pub fn last_weekday_of_month(
+    year: i32,
+    month: u32,
+    weekday: u32,
+    utc: Option<bool>,
+) -> Result<Unitime, ValidationError> {
+    check_date(year as i64, month, 1)?;
+    check_weekday(weekday)?;
+    let last = days_from_civil(year as i64, month, days_in_month(year as i64, month));
+    let days = last - (self::weekday(last) as i64 - weekday as i64).rem_euclid(7);
+    Ok(midnight(days, utc))
+}
@@ -0,0 +1,92 @@
+use wasm_bindgen::prelude::*;
+
+use crate::sun::julian_century;
+use crate::Unitime;
+
+/// One of the eight named phases of the moon.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LunarPhase {
+    New = "new",
+    WaxingCrescent = "waxing-crescent",
+    FirstQuarter = "first-quarter",
+    WaxingGibbous = "waxing-gibbous",
+    Full = "full",
+    WaningGibbous = "waning-gibbous",
+    LastQuarter = "last-quarter",
+    WaningCrescent = "waning-crescent",
+}
+
+const PHASES: [LunarPhase; 8] = [
+    LunarPhase::New,
+    LunarPhase::WaxingCrescent,
+    LunarPhase::FirstQuarter,
+    LunarPhase::WaxingGibbous,
+    LunarPhase::Full,
+    LunarPhase::WaningGibbous,
+    LunarPhase::LastQuarter,
+    LunarPhase::WaningCrescent,
+];
+
+/// Phase of the moon at one instant.
+#[wasm_bindgen]
+pub struct MoonPhase {
+    fraction: f64,
+    illumination: f64,
+}
+
+#[wasm_bindgen]
+impl MoonPhase {
+    /// Get how far through the lunar cycle the moon is, from 0 at new moon through 0.5 at full moon to just under 1.
+    #[wasm_bindgen(getter)]
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Get the illuminated fraction of the moon's disc, from 0 to 1.
+    #[wasm_bindgen(getter)]
+    pub fn illumination(&self) -> f64 {
+        self.illumination
+    }
+
+    /// Get the named phase. New, quarter and full moon each cover the eighth of the cycle centred on them.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> LunarPhase {
+        PHASES[((self.fraction * 8.0).round() as usize) % 8]
+    }
+
+    /// Whether the illuminated part is growing.
+    #[wasm_bindgen(getter)]
+    pub fn waxing(&self) -> bool {
+        self.fraction < 0.5
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the phase of the moon at the stored instant, from the low-precision lunar theory in Meeus'
+    /// *Astronomical Algorithms*. The fraction is good to about an hour of the cycle.
+    /// # Examples
+    /// ```
+    /// const moon = new Unitime().moonPhase();
+    /// label.textContent = `${moon.name} (${Math.round(moon.illumination * 100)}%)`;
+    /// ```
+    #[wasm_bindgen(js_name = "moonPhase")]
+    pub fn moon_phase(&self) -> MoonPhase {
+        let t = julian_century(self.epoch_ms() as f64);
+        let d = (297.8501921 + 445_267.1114034 * t).to_radians();
+        let m = (357.5291092 + 35_999.0502909 * t).to_radians();
+        let mp = (134.9633964 + 477_198.8675055 * t).to_radians();
+        // Elongation of the moon from the sun, i.e. 180° minus the phase angle.
+        let elongation = d.to_degrees() + 6.289 * mp.sin() - 2.100 * m.sin()
+            + 1.274 * (2.0 * d - mp).sin()
+            + 0.658 * (2.0 * d).sin()
+            + 0.214 * (2.0 * mp).sin()
+            + 0.110 * d.sin();
+        let elongation = elongation.rem_euclid(360.0);
+        MoonPhase {
+            fraction: elongation / 360.0,
+            illumination: (1.0 - elongation.to_radians().cos()) / 2.0,
+        }
+    }
+}
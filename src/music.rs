@@ -0,0 +1,174 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::UniDuration;
+use crate::validation::ValidationError;
+
+fn check_sample_rate(sample_rate: f64) -> Result<(), ValidationError> {
+    if !sample_rate.is_finite() || sample_rate <= 0.0 {
+        return Err(ValidationError::new("sample rate must be positive"));
+    }
+    Ok(())
+}
+
+#[wasm_bindgen]
+impl UniDuration {
+    /// Creates a new `UniDuration` of `samples` audio samples at `sample_rate` Hz.
+    /// # Examples
+    /// ```
+    /// const d = UniDuration.fromSamples(buffer.length, buffer.sampleRate);
+    /// ```
+    #[wasm_bindgen(js_name = "fromSamples")]
+    pub fn from_samples(samples: f64, sample_rate: f64) -> Result<UniDuration, ValidationError> {
+        check_sample_rate(sample_rate)?;
+        Ok(UniDuration::from_nanos((samples * 1e9 / sample_rate).round() as i64))
+    }
+
+    /// Get the number of whole audio samples at `sample_rate` Hz that fit in the duration.
+    /// # Examples
+    /// ```
+    /// const frames = UniDuration.fromSeconds(2).toSamples(48000); // 96000
+    /// ```
+    #[wasm_bindgen(js_name = "toSamples")]
+    pub fn to_samples(&self, sample_rate: f64) -> Result<f64, ValidationError> {
+        check_sample_rate(sample_rate)?;
+        Ok((self.nanos() as f64 * sample_rate / 1e9 + 1e-6).floor())
+    }
+}
+
+/// Position on a musical grid: 1-based bar and beat, plus how far into the beat, from 0 to 1.
+#[wasm_bindgen]
+pub struct MusicalPosition {
+    /// The bar, starting at 1.
+    pub bar: f64,
+    /// The beat within the bar, starting at 1.
+    pub beat: u32,
+    /// How far into the beat, from 0 to 1.
+    pub fraction: f64,
+}
+
+/// Tempo and time signature defining a musical grid that starts with bar 1 at time 0.
+#[wasm_bindgen]
+pub struct Tempo {
+    bpm: f64,
+    beats_per_bar: u32,
+    beat_unit: u32,
+}
+
+impl Tempo {
+    fn beat_ms(&self) -> f64 {
+        60_000.0 / self.bpm
+    }
+
+    fn next_after(&self, t: f64, origin: f64, step: f64) -> f64 {
+        let n = ((t - origin) / step).floor() + 1.0;
+        origin + n * step
+    }
+}
+
+#[wasm_bindgen]
+impl Tempo {
+    /// Creates a new `Tempo` of `bpm` beats per minute in a `beats_per_bar`/`beat_unit` time signature, 4/4 by default.
+    /// # Examples
+    /// ```
+    /// const waltz = new Tempo(90, 3, 4);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(bpm: f64, beats_per_bar: Option<u32>, beat_unit: Option<u32>) -> Result<Tempo, ValidationError> {
+        if !bpm.is_finite() || bpm <= 0.0 {
+            return Err(ValidationError::new("tempo must be positive"));
+        }
+        let beats_per_bar = beats_per_bar.unwrap_or(4);
+        let beat_unit = beat_unit.unwrap_or(4);
+        if beats_per_bar == 0 {
+            return Err(ValidationError::range("beatsPerBar", 0, 1, u32::MAX as i64));
+        }
+        if !beat_unit.is_power_of_two() {
+            return Err(ValidationError::new("invalid time signature: the beat unit must be a power of two"));
+        }
+        Ok(Tempo { bpm, beats_per_bar, beat_unit })
+    }
+
+    /// Get the tempo in beats per minute.
+    #[wasm_bindgen(getter)]
+    pub fn bpm(&self) -> f64 {
+        self.bpm
+    }
+
+    /// Get the time signature as `[beatsPerBar, beatUnit]`.
+    #[wasm_bindgen(getter, js_name = "timeSignature")]
+    pub fn time_signature(&self) -> Vec<u32> {
+        vec![self.beats_per_bar, self.beat_unit]
+    }
+
+    /// Get the length of one beat.
+    #[wasm_bindgen(getter, js_name = "beatDuration")]
+    pub fn beat_duration(&self) -> UniDuration {
+        UniDuration::new(self.beat_ms())
+    }
+
+    /// Get the length of one bar.
+    #[wasm_bindgen(getter, js_name = "barDuration")]
+    pub fn bar_duration(&self) -> UniDuration {
+        UniDuration::new(self.beat_ms() * self.beats_per_bar as f64)
+    }
+
+    /// Get the number of beats, with fraction, elapsed after `duration`.
+    #[wasm_bindgen(js_name = "beatsIn")]
+    pub fn beats_in(&self, duration: &UniDuration) -> f64 {
+        duration.millis() / self.beat_ms()
+    }
+
+    /// Get the bar and beat playing after `duration`.
+    /// # Examples
+    /// ```
+    /// const pos = tempo.positionAt(UniDuration.fromSeconds(ctx.currentTime - start));
+    /// label.textContent = `${pos.bar}.${pos.beat}`;
+    /// ```
+    #[wasm_bindgen(js_name = "positionAt")]
+    pub fn position_at(&self, duration: &UniDuration) -> MusicalPosition {
+        let beats = self.beats_in(duration);
+        let whole = beats.floor();
+        let per_bar = self.beats_per_bar as f64;
+        MusicalPosition {
+            bar: (whole / per_bar).floor() + 1.0,
+            beat: whole.rem_euclid(per_bar) as u32 + 1,
+            fraction: beats - whole,
+        }
+    }
+
+    /// Get the time at which the 1-based `bar` and `beat` start, optionally partway in by `fraction` of a beat.
+    #[wasm_bindgen(js_name = "durationAt")]
+    pub fn duration_at(&self, bar: f64, beat: u32, fraction: Option<f64>) -> Result<UniDuration, ValidationError> {
+        if beat == 0 || beat > self.beats_per_bar {
+            return Err(ValidationError::range("beat", beat as i64, 1, self.beats_per_bar as i64));
+        }
+        if !bar.is_finite() {
+            return Err(ValidationError::new("bar must be a finite number"));
+        }
+        let beats = (bar - 1.0) * self.beats_per_bar as f64 + (beat - 1) as f64 + fraction.unwrap_or(0.0);
+        Ok(UniDuration::new(beats * self.beat_ms()))
+    }
+
+    /// Get the time in milliseconds of the first beat strictly after `t`, with the grid starting at `origin` (0 by default).
+    /// # Examples
+    /// ```
+    /// const next = tempo.nextBeatAfter(performance.now(), startTime);
+    /// ```
+    #[wasm_bindgen(js_name = "nextBeatAfter")]
+    pub fn next_beat_after(&self, t: f64, origin: Option<f64>) -> f64 {
+        self.next_after(t, origin.unwrap_or(0.0), self.beat_ms())
+    }
+
+    /// Get the time in milliseconds of the first bar line strictly after `t`, with the grid starting at `origin` (0 by default).
+    #[wasm_bindgen(js_name = "nextBarAfter")]
+    pub fn next_bar_after(&self, t: f64, origin: Option<f64>) -> f64 {
+        self.next_after(t, origin.unwrap_or(0.0), self.beat_ms() * self.beats_per_bar as f64)
+    }
+
+    /// Get the tempo in samples per beat at `sample_rate` Hz.
+    #[wasm_bindgen(js_name = "samplesPerBeat")]
+    pub fn samples_per_beat(&self, sample_rate: f64) -> Result<f64, ValidationError> {
+        check_sample_rate(sample_rate)?;
+        Ok(sample_rate * 60.0 / self.bpm)
+    }
+}
@@ -0,0 +1,202 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, weekday, DateTime, MAX_YEAR, MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::validation::{check_year, ValidationError};
+use crate::Unitime;
+
+const WEEKDAYS: [&str; 7] = ["sunday", "monday", "tuesday", "wednesday", "thursday", "friday", "saturday"];
+const NUMBER_WORDS: [&str; 13] =
+    ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve"];
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Millis(i64),
+    Months(i64),
+}
+
+fn unit(word: &str) -> Option<Unit> {
+    let word = word.strip_suffix('s').filter(|w| w.len() > 1).unwrap_or(word);
+    Some(match word {
+        "sec" | "second" => Unit::Millis(MS_PER_SEC),
+        "min" | "minute" => Unit::Millis(MS_PER_MIN),
+        "hr" | "hour" => Unit::Millis(MS_PER_HOUR),
+        "day" => Unit::Millis(MS_PER_DAY),
+        "week" => Unit::Millis(7 * MS_PER_DAY),
+        "fortnight" => Unit::Millis(14 * MS_PER_DAY),
+        "month" => Unit::Months(1),
+        "year" => Unit::Months(12),
+        _ => return None,
+    })
+}
+
+fn weekday_of(word: &str) -> Option<i64> {
+    WEEKDAYS.iter().position(|d| *d == word || (word.len() >= 3 && d.starts_with(word))).map(|d| d as i64)
+}
+
+fn count(word: &str) -> Option<i64> {
+    match word {
+        "a" | "an" => Some(1),
+        _ => word.parse().ok().or_else(|| NUMBER_WORDS.iter().position(|n| *n == word).map(|n| n as i64)),
+    }
+}
+
+/// Read a time of day such as `5pm`, `5 pm`, `5:30am`, `17:30`, `noon` or `midnight` from the start of
+/// `words`, returning milliseconds into the day and the number of words used.
+fn time_of_day(words: &[&str]) -> Option<(i64, usize)> {
+    let first = *words.first()?;
+    match first {
+        "noon" | "midday" => return Some((12 * MS_PER_HOUR, 1)),
+        "midnight" => return Some((0, 1)),
+        _ => {}
+    }
+    let (clock, meridiem, used) = if let Some(clock) = first.strip_suffix("am") {
+        (clock, Some(false), 1)
+    } else if let Some(clock) = first.strip_suffix("pm") {
+        (clock, Some(true), 1)
+    } else {
+        match words.get(1) {
+            Some(&"am") => (first, Some(false), 2),
+            Some(&"pm") => (first, Some(true), 2),
+            _ => (first, None, 1),
+        }
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) if m.len() == 2 => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    let hour = match meridiem {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        None if hour < 24 => hour,
+        _ => return None,
+    };
+    (minute < 60).then_some((hour as i64 * MS_PER_HOUR + minute as i64 * MS_PER_MIN, used))
+}
+
+/// Move the wall-clock reading `wall` by `n` of `unit`. Months and years keep the day of the month where
+/// they can and otherwise clamp it to the end of the month. Moves past the years `Unitime` holds fail.
+fn shift(wall: i64, n: i64, unit: Unit) -> Result<i64, ValidationError> {
+    let shifted = match unit {
+        Unit::Millis(ms) => n.checked_mul(ms).and_then(|ms| wall.checked_add(ms)),
+        Unit::Months(months) => n.checked_mul(months).and_then(|months| civil::checked_add_months(wall, months)),
+    };
+    let year = |wall| DateTime::from_epoch_ms(wall).year;
+    shifted.filter(|&wall| year(wall).abs() <= MAX_YEAR).ok_or_else(|| {
+        // The year reached may not fit the calculation, so it is only worked out roughly for the error.
+        let years = match unit {
+            Unit::Millis(ms) => n as f64 * ms as f64 / (365.2425 * MS_PER_DAY as f64),
+            Unit::Months(months) => n as f64 * months as f64 / 12.0,
+        };
+        ValidationError::range("year", (year(wall) as f64 + years) as i64, -MAX_YEAR, MAX_YEAR)
+    })
+}
+
+/// Interpret `text` relative to the wall-clock reading `now`, returning the wall-clock reading it names.
+fn parse_natural(text: &str, now: i64) -> Result<i64, ValidationError> {
+    let text = text.to_ascii_lowercase().replace(',', " ");
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return Err(ValidationError::new("empty date text"));
+    }
+    let today = now.div_euclid(MS_PER_DAY);
+    let mut day: Option<i64> = None;
+    let mut time: Option<i64> = None;
+    let mut shifts: Vec<(i64, Unit)> = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        let next = words.get(i + 1).copied();
+        i += 1;
+        match word {
+            "now" | "on" | "the" => {}
+            "today" => day = Some(today),
+            "tomorrow" => day = Some(today + 1),
+            "yesterday" => day = Some(today - 1),
+            "tonight" => {
+                day = Some(today);
+                time = time.or(Some(20 * MS_PER_HOUR));
+            }
+            "at" => {
+                let (t, used) =
+                    time_of_day(&words[i..]).ok_or_else(|| ValidationError::new("expected a time after \"at\""))?;
+                time = Some(t);
+                i += used;
+            }
+            "in" => {
+                let n = next.and_then(count).ok_or_else(|| ValidationError::new("expected a number after \"in\""))?;
+                let u = words.get(i + 1).and_then(|w| unit(w));
+                let u = u.ok_or_else(|| ValidationError::new("expected a unit of time"))?;
+                shifts.push((n, u));
+                i += 2;
+            }
+            "next" | "last" | "this" => {
+                let next =
+                    next.ok_or_else(|| ValidationError::new(format!("expected a day or unit after \"{word}\"")))?;
+                let sign = match word {
+                    "next" => 1,
+                    "last" => -1,
+                    _ => 0,
+                };
+                if let Some(target) = weekday_of(next) {
+                    let current = weekday(today) as i64;
+                    let delta = match sign {
+                        1 => (target - current - 1).rem_euclid(7) + 1,
+                        -1 => -((current - target - 1).rem_euclid(7) + 1),
+                        _ => (target - current).rem_euclid(7),
+                    };
+                    day = Some(today + delta);
+                } else if let Some(u) = unit(next) {
+                    shifts.push((sign, u));
+                } else {
+                    return Err(ValidationError::new(format!("unrecognized word \"{next}\"")));
+                }
+                i += 1;
+            }
+            _ => {
+                if let Some(target) = weekday_of(word) {
+                    day = Some(today + (target - weekday(today) as i64).rem_euclid(7));
+                } else if let (Some(n), Some(u), Some("ago")) =
+                    (count(word), next.and_then(unit), words.get(i + 1).copied())
+                {
+                    shifts.push((-n, u));
+                    i += 2;
+                } else if let Some((t, used)) = time_of_day(&words[i - 1..]) {
+                    time = Some(t);
+                    i += used - 1;
+                } else {
+                    return Err(ValidationError::new(format!("unrecognized word \"{word}\"")));
+                }
+            }
+        }
+    }
+    let mut wall = day.map_or(now, |d| d * MS_PER_DAY);
+    for (n, u) in shifts {
+        wall = shift(wall, n, u)?;
+    }
+    if let Some(t) = time {
+        wall = wall.div_euclid(MS_PER_DAY) * MS_PER_DAY + t;
+    }
+    Ok(wall)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Creates a new `Unitime` from everyday English such as "tomorrow at 5pm", "next tuesday", "in 3 hours",
+    /// "2 days ago", "last week" or "noon", relative to `reference` in epoch milliseconds (now if omitted).
+    /// Times of day are read in local time unless `utc` is `true`. A day without a time means its midnight;
+    /// a weekday on its own means the next one, today included, while "next" and "last" skip today.
+    /// # Examples
+    /// ```
+    /// const due = Unitime.parseNatural("tomorrow at 5pm");
+    /// const t = Unitime.parseNatural("in 2 weeks", Date.now());
+    /// ```
+    #[wasm_bindgen(js_name = "parseNatural")]
+    pub fn parse_natural(text: &str, reference: Option<f64>, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        let utc = utc.unwrap_or(false);
+        let reference = reference.map_or_else(crate::now_ms, |ms| ms as i64);
+        let year = DateTime::from_epoch_ms(reference).year;
+        check_year(year)?;
+        let wall = parse_natural(text, civil::instant_to_wall(reference, utc))?;
+        Ok(Unitime::from_epoch_ms(civil::wall_to_instant(wall, utc)))
+    }
+}
@@ -0,0 +1,82 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, weekday, MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN};
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Check a recurring wall-clock time, returning it in milliseconds after midnight along with its weekdays
+/// as a bit set from bit 0 for Sunday. Without `weekdays` every day counts.
+pub(crate) fn wall_time_rule(
+    hour: u32,
+    minute: u32,
+    weekdays: Option<Vec<u32>>,
+) -> Result<(i64, u8), ValidationError> {
+    if hour > 23 {
+        return Err(ValidationError::range("hour", hour as i64, 0, 23));
+    }
+    if minute > 59 {
+        return Err(ValidationError::range("minute", minute as i64, 0, 59));
+    }
+    let time = hour as i64 * MS_PER_HOUR + minute as i64 * MS_PER_MIN;
+    let Some(weekdays) = weekdays else {
+        return Ok((time, 0x7f));
+    };
+    let mut mask = 0;
+    for day in weekdays {
+        if day > 6 {
+            return Err(ValidationError::range("weekday", day as i64, 0, 6));
+        }
+        mask |= 1 << day;
+    }
+    if mask == 0 {
+        return Err(ValidationError::new("at least one weekday is required"));
+    }
+    Ok((time, mask))
+}
+
+/// The instants `next_checked_occurrence` takes, leaving a week and a day's time between the last and
+/// the end of the range of epoch milliseconds.
+const FIRST_AFTER: i64 = (i64::MIN / MS_PER_DAY + 1) * MS_PER_DAY;
+const LAST_AFTER: i64 = (i64::MAX / MS_PER_DAY - 8) * MS_PER_DAY;
+
+/// The first instant after `after` at which the wall clock reads `time` milliseconds after midnight on one
+/// of the weekdays in `mask`, or `None` if `mask` holds none or there is none before the end of time.
+pub(crate) fn next_occurrence(after: i64, time: i64, mask: u8, utc: bool) -> Option<i64> {
+    let today = civil::instant_to_wall(after, utc).div_euclid(MS_PER_DAY);
+    (today..=today.saturating_add(7))
+        .filter(|&day| mask & (1 << weekday(day)) != 0)
+        .filter_map(|day| Some(civil::wall_to_instant(day.checked_mul(MS_PER_DAY)?.checked_add(time)?, utc)))
+        .find(|&t| t > after)
+}
+
+/// `next_occurrence` for a `mask` that `wall_time_rule` has checked, failing if `after` is within a
+/// week of either end of the range of epoch milliseconds.
+pub(crate) fn next_checked_occurrence(after: i64, time: i64, mask: u8, utc: bool) -> Result<i64, ValidationError> {
+    if !(FIRST_AFTER..=LAST_AFTER).contains(&after) {
+        return Err(ValidationError::range("time", after, FIRST_AFTER, LAST_AFTER));
+    }
+    next_occurrence(after, time, mask, utc).ok_or_else(|| ValidationError::internal("no weekday to recur on"))
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the next time after the stored time at which the clock reads `hour`:`minute`, in local time
+    /// unless `utc` is `true`. If `weekdays` is given, from 0 for Sunday to 6 for Saturday, only those
+    /// days count. This covers "every day at 9:00" and "weekdays at 8:30" reminders.
+    /// # Examples
+    /// ```
+    /// const reminder = new Unitime().nextOccurrenceOf(9, 0);
+    /// const standup = new Unitime().nextOccurrenceOf(9, 30, [1, 2, 3, 4, 5]);
+    /// ```
+    #[wasm_bindgen(js_name = "nextOccurrenceOf")]
+    pub fn next_occurrence_of(
+        &self,
+        hour: u32,
+        minute: u32,
+        weekdays: Option<Vec<u32>>,
+        utc: Option<bool>,
+    ) -> Result<Unitime, ValidationError> {
+        let (time, mask) = wall_time_rule(hour, minute, weekdays)?;
+        Ok(Unitime::from_epoch_ms(next_checked_occurrence(self.epoch_ms(), time, mask, utc.unwrap_or(false))?))
+    }
+}
@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Time step of RFC 6238 authenticator codes when none is given.
+const DEFAULT_STEP_SECONDS: f64 = 30.0;
+
+/// Check a TOTP step, returning it in milliseconds.
+fn check_step(step_seconds: Option<f64>) -> Result<i64, ValidationError> {
+    let step = step_seconds.unwrap_or(DEFAULT_STEP_SECONDS);
+    if step.is_nan() || step < 1.0 {
+        return Err(ValidationError::new("TOTP step must be at least one second"));
+    }
+    if step > (i64::MAX / 1000) as f64 {
+        return Err(ValidationError::range("stepSeconds", step as i64, 1, i64::MAX / 1000));
+    }
+    Ok((step * 1000.0) as i64)
+}
+
+impl Unitime {
+    /// Milliseconds from `t0_seconds`, the epoch if omitted, to the stored time.
+    fn ms_since_t0(&self, t0_seconds: Option<f64>) -> Result<i64, ValidationError> {
+        let t0 = t0_seconds.unwrap_or(0.0);
+        if !t0.is_finite() {
+            return Err(ValidationError::new("TOTP start time must be a finite number of seconds"));
+        }
+        let out_of_range = || ValidationError::range("t0Seconds", t0 as i64, i64::MIN / 1000, i64::MAX / 1000);
+        if !((i64::MIN / 1000) as f64..=(i64::MAX / 1000) as f64).contains(&t0) {
+            return Err(out_of_range());
+        }
+        self.epoch_ms().checked_sub((t0 * 1000.0) as i64).ok_or_else(out_of_range)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the RFC 6238 TOTP counter for the stored time, i.e. the number of whole `step_seconds` steps
+    /// (30 by default) since `t0_seconds` (the epoch by default). Add a server clock offset to the time
+    /// beforehand if codes must match a server.
+    /// # Examples
+    /// ```
+    /// const counter = new Unitime().totpCounter();
+    /// ```
+    #[wasm_bindgen(js_name = "totpCounter")]
+    pub fn totp_counter(&self, step_seconds: Option<f64>, t0_seconds: Option<f64>) -> Result<f64, ValidationError> {
+        let step_ms = check_step(step_seconds)?;
+        Ok(self.ms_since_t0(t0_seconds)?.div_euclid(step_ms) as f64)
+    }
+
+    /// Get the seconds, with fraction, from the stored time until the next TOTP step starts, for driving countdown rings.
+    /// # Examples
+    /// ```
+    /// const left = new Unitime().secondsUntilNextStep(30);
+    /// ring.style.setProperty("--progress", 1 - left / 30);
+    /// ```
+    #[wasm_bindgen(js_name = "secondsUntilNextStep")]
+    pub fn seconds_until_next_step(
+        &self,
+        step_seconds: Option<f64>,
+        t0_seconds: Option<f64>,
+    ) -> Result<f64, ValidationError> {
+        let step_ms = check_step(step_seconds)?;
+        let into_step = self.ms_since_t0(t0_seconds)?.rem_euclid(step_ms);
+        Ok((step_ms - into_step) as f64 / 1000.0)
+    }
+}
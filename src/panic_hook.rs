@@ -0,0 +1,8 @@
+use wasm_bindgen::prelude::*;
+
+/// Send the message and location of any Rust panic to `console.error` instead of the bare
+/// `RuntimeError: unreachable` the host would report.
+#[wasm_bindgen(start)]
+fn start() {
+    console_error_panic_hook::set_once();
+}
@@ -0,0 +1,152 @@
+//! Small byte cursor used by the lenient text parsers.
+
+// The parsers sit behind the `parse` and `calendar` features, so without them some of this goes unused.
+#![cfg_attr(not(all(feature = "calendar", feature = "parse")), allow(dead_code))]
+
+#[derive(Clone)]
+pub(crate) struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(s: &'a str) -> Cursor<'a> {
+        Cursor { bytes: s.as_bytes(), pos: 0 }
+    }
+
+    /// Number of bytes consumed so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Consume `c` if it is the next byte.
+    pub fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume `s` if the input continues with it.
+    pub fn eat_str(&mut self, s: &str) -> bool {
+        if self.bytes.get(self.pos..).is_some_and(|rest| rest.starts_with(s.as_bytes())) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Read between `min` and `max` ASCII digits, returning the value and the number of digits read.
+    pub fn digits(&mut self, min: usize, max: usize) -> Option<(u32, usize)> {
+        let start = self.pos;
+        let mut value: u32 = 0;
+        while self.pos - start < max {
+            match self.peek() {
+                Some(c @ b'0'..=b'9') => {
+                    value = value * 10 + (c - b'0') as u32;
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+        let count = self.pos - start;
+        if count < min {
+            self.pos = start;
+            return None;
+        }
+        Some((value, count))
+    }
+
+    /// Read exactly `n` digits.
+    pub fn fixed(&mut self, n: usize) -> Option<u32> {
+        self.digits(n, n).map(|(v, _)| v)
+    }
+
+    /// Read a fraction of a second after the decimal point and return it in milliseconds.
+    /// Digits beyond millisecond precision are consumed and truncated.
+    pub fn fraction_ms(&mut self) -> Option<u32> {
+        let (value, count) = self.digits(1, 9)?;
+        let ms = match count {
+            1 => value * 100,
+            2 => value * 10,
+            n => value / 10u32.pow(n as u32 - 3),
+        };
+        Some(ms)
+    }
+
+    /// Read an English three-letter month abbreviation, case-insensitively, returning the month number.
+    pub fn month_abbr(&mut self) -> Option<u32> {
+        let word = self.bytes.get(self.pos..self.pos + 3)?;
+        let month = MONTH_ABBRS
+            .iter()
+            .position(|m| m.as_bytes().eq_ignore_ascii_case(word))?;
+        self.pos += 3;
+        Some(month as u32 + 1)
+    }
+
+    /// Read an era marker, `BC`, `BCE`, `AD` or `CE` in any case and with or without dots, as in `B.C.`,
+    /// returning whether it is before the common era.
+    pub fn era(&mut self) -> Option<bool> {
+        for (marker, bc) in [("BCE", true), ("BC", true), ("CE", false), ("AD", false)] {
+            let mut ahead = self.clone();
+            let dotted = marker.bytes().all(|letter| {
+                let matched = ahead.peek().is_some_and(|b| b.eq_ignore_ascii_case(&letter));
+                ahead.pos += matched as usize;
+                ahead.eat(b'.');
+                matched
+            });
+            if dotted && !ahead.peek().is_some_and(|b| b.is_ascii_alphanumeric()) {
+                *self = ahead;
+                return Some(bc);
+            }
+        }
+        None
+    }
+
+    /// Read a UTC offset in `Z`, `+hh`, `+hhmm` or `+hh:mm` form and return it in minutes.
+    pub fn utc_offset(&mut self) -> Option<i64> {
+        let start = self.pos;
+        if self.eat(b'Z') || self.eat(b'z') {
+            return Some(0);
+        }
+        let sign = match self.peek() {
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return None,
+        };
+        self.pos += 1;
+        let parsed = self.fixed(2).and_then(|hours| {
+            let colon = self.eat(b':');
+            let minutes = match self.fixed(2) {
+                Some(m) => m,
+                None if !colon => 0,
+                None => return None,
+            };
+            (hours < 24 && minutes < 60).then_some(hours as i64 * 60 + minutes as i64)
+        });
+        if parsed.is_none() {
+            self.pos = start;
+        }
+        parsed.map(|m| sign * m)
+    }
+}
+
+pub(crate) const MONTH_ABBRS: [&str; 12] =
+    ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
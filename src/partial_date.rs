@@ -0,0 +1,185 @@
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_from_civil, days_in_month, year_str};
+use crate::interval::UniInterval;
+use crate::month::midnight;
+use crate::parse::Cursor;
+use crate::validation::{char_offset, check_date, check_year, ValidationError};
+
+/// How much of a `PartialDate` is known.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DatePrecision {
+    Year = "year",
+    Month = "month",
+    Day = "day",
+}
+
+/// Date known only to the year, the month or the day, such as "2024", "June 2024" or "2024-06-05", as
+/// found in bibliographies and family trees. Comparisons treat a date as the whole span it could be,
+/// so "2024" is neither before nor after "June 2024". Years are astronomical, so 1 BC is year 0.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialDate {
+    year: i64,
+    month: Option<u32>,
+    /// Only known when the month is.
+    day: Option<u32>,
+}
+
+impl PartialDate {
+    pub(crate) fn from_parts(year: i64, month: Option<u32>, day: Option<u32>) -> Result<PartialDate, ValidationError> {
+        match (month, day) {
+            (None, Some(_)) => return Err(ValidationError::new("a day needs a month")),
+            (Some(month), day) => check_date(year, month, day.unwrap_or(1))?,
+            (None, None) => check_year(year)?,
+        }
+        Ok(PartialDate { year, month, day })
+    }
+
+    /// The first and last day the date could be, in days since 1970-01-01.
+    pub(crate) fn day_range(&self) -> (i64, i64) {
+        let (first_month, last_month) = self.month.map_or((1, 12), |month| (month, month));
+        let last_day = days_in_month(self.year, last_month);
+        let first = days_from_civil(self.year, first_month, self.day.unwrap_or(1));
+        (first, days_from_civil(self.year, last_month, self.day.unwrap_or(last_day)))
+    }
+
+    /// Read `YYYY`, `YYYY-MM` or `YYYY-MM-DD` from `c`, with a sign before years outside 0 to 9999.
+    pub(crate) fn read_iso(c: &mut Cursor) -> Option<(i64, Option<u32>, Option<u32>)> {
+        let sign = if c.eat(b'-') {
+            -1
+        } else {
+            c.eat(b'+');
+            1
+        };
+        let year = sign * c.digits(4, 9)?.0 as i64;
+        if !c.eat(b'-') {
+            return Some((year, None, None));
+        }
+        let month = c.fixed(2)?;
+        if !c.eat(b'-') {
+            return Some((year, Some(month), None));
+        }
+        Some((year, Some(month), Some(c.fixed(2)?)))
+    }
+
+    fn parse(s: &str) -> Result<PartialDate, ValidationError> {
+        let text = s.trim();
+        let lead = s.len() - s.trim_start().len();
+        let mut c = Cursor::new(text);
+        let fields = if let Some(month) = c.month_abbr() {
+            // "Jun 2024" or "June 2024".
+            while c.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+                c.eat(c.peek().unwrap_or(0));
+            }
+            c.skip_spaces();
+            c.digits(1, 9).map(|(year, _)| (year as i64, Some(month), None))
+        } else {
+            PartialDate::read_iso(&mut c)
+        };
+        match fields {
+            Some((year, month, day)) if c.is_end() => PartialDate::from_parts(year, month, day),
+            _ => Err(ValidationError::syntax(format!("invalid partial date: {s}"), char_offset(s, lead + c.pos()))),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl PartialDate {
+    /// Creates a new `PartialDate` for `year`, or a month of it if `month` is given, or a day of that
+    /// month if `day` is given too.
+    /// # Examples
+    /// ```
+    /// const published = new PartialDate(1998, 6); // June 1998
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(year: i32, month: Option<u32>, day: Option<u32>) -> Result<PartialDate, ValidationError> {
+        PartialDate::from_parts(year as i64, month, day)
+    }
+
+    /// Read a partial date written `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, or as an English month name or
+    /// abbreviation and a year, such as `June 2024`.
+    /// # Examples
+    /// ```
+    /// PartialDate.fromString("1871").precision; // "year"
+    /// PartialDate.fromString("Jun 1871").toString(); // "1871-06"
+    /// ```
+    #[wasm_bindgen(js_name = "fromString")]
+    pub fn from_string(s: &str) -> Result<PartialDate, ValidationError> {
+        PartialDate::parse(s)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn year(&self) -> i32 {
+        self.year as i32
+    }
+
+    /// Get the month from 1 for January, if known.
+    #[wasm_bindgen(getter)]
+    pub fn month(&self) -> Option<u32> {
+        self.month
+    }
+
+    /// Get the day of the month, if known.
+    #[wasm_bindgen(getter)]
+    pub fn day(&self) -> Option<u32> {
+        self.day
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn precision(&self) -> DatePrecision {
+        match (self.month, self.day) {
+            (None, _) => DatePrecision::Year,
+            (Some(_), None) => DatePrecision::Month,
+            (Some(_), Some(_)) => DatePrecision::Day,
+        }
+    }
+
+    /// Compare with another partial date, returning -1 if this one ends before `other` starts, 1 if it
+    /// starts after `other` ends, and 0 if they overlap, as "2024" and "June 2024" do.
+    /// # Examples
+    /// ```
+    /// PartialDate.fromString("1871").compare(PartialDate.fromString("1872-03")); // -1
+    /// PartialDate.fromString("1871").compare(PartialDate.fromString("1871-03")); // 0
+    /// ```
+    pub fn compare(&self, other: &PartialDate) -> i32 {
+        let ((first, last), (other_first, other_last)) = (self.day_range(), other.day_range());
+        match (last.cmp(&other_first), first.cmp(&other_last)) {
+            (Ordering::Less, _) => -1,
+            (_, Ordering::Greater) => 1,
+            _ => 0,
+        }
+    }
+
+    /// Check whether every day `other` could be lies within this date, as June 2024 lies in 2024.
+    pub fn contains(&self, other: &PartialDate) -> bool {
+        let ((first, last), (other_first, other_last)) = (self.day_range(), other.day_range());
+        first <= other_first && other_last <= last
+    }
+
+    /// Get the span of time the date could be, from midnight starting its first day to the end of its
+    /// last day, in local time unless `utc` is `true`.
+    /// # Examples
+    /// ```
+    /// const span = PartialDate.fromString("2024-06").toInterval(true);
+    /// span.contains(new Unitime()); // whether it is June 2024 in UTC
+    /// ```
+    #[wasm_bindgen(js_name = "toInterval")]
+    pub fn to_interval(&self, utc: Option<bool>) -> Result<UniInterval, ValidationError> {
+        let (first, last) = self.day_range();
+        UniInterval::from_nanos(midnight(first, utc).nanos, midnight(last + 1, utc).nanos - 1)
+    }
+
+    /// Get the date in ISO 8601 form to its precision, such as "2024", "2024-06" or "2024-06-05".
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        let mut out = year_str(self.year);
+        for part in [self.month, self.day].into_iter().flatten() {
+            out.push_str(&format!("-{part:02}"));
+        }
+        out
+    }
+}
@@ -0,0 +1,86 @@
+use js_sys::{Function, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Call the method `name` of the `Unitime` JS object `t` without arguments.
+fn call(t: &JsValue, name: &str) -> Result<JsValue, ValidationError> {
+    let not_unitime = || ValidationError::new("a UnitimePool only takes Unitime objects");
+    let method: Function =
+        Reflect::get(t, &name.into()).ok().and_then(|m| m.dyn_into().ok()).ok_or_else(not_unitime)?;
+    method.call0(t).map_err(|_| ValidationError::new(format!("{name} failed on a pooled Unitime")))
+}
+
+/// Keeps `Unitime` objects to hand out again, so that code creating thousands of short-lived times a
+/// second, such as a timestamp every frame, neither allocates wasm memory nor creates JS objects for
+/// each. A rented `Unitime` is reset to the current time and stays the caller's until it is given back
+/// with `release`, after which the caller must not use it.
+/// # Examples
+/// ```
+/// const pool = new UnitimePool();
+/// function frame() {
+///     const now = pool.rent();
+///     draw(now);
+///     pool.release(now);
+///     requestAnimationFrame(frame);
+/// }
+/// ```
+#[wasm_bindgen]
+pub struct UnitimePool {
+    free: Vec<JsValue>,
+    capacity: usize,
+}
+
+#[wasm_bindgen]
+impl UnitimePool {
+    /// Creates a new, empty `UnitimePool` that keeps up to `capacity` given-back objects, 1024 by
+    /// default. Objects given back beyond that are freed.
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: Option<u32>) -> UnitimePool {
+        UnitimePool { free: Vec::new(), capacity: capacity.unwrap_or(1024) as usize }
+    }
+
+    /// Get a `Unitime` holding the current time, reusing one given back if there is one.
+    #[wasm_bindgen(unchecked_return_type = "Unitime")]
+    pub fn rent(&mut self) -> Result<JsValue, ValidationError> {
+        match self.free.pop() {
+            Some(t) => {
+                call(&t, "refresh")?;
+                Ok(t)
+            }
+            None => Ok(Unitime::new().into()),
+        }
+    }
+
+    /// Give back a `Unitime` that `rent` handed out, or any other that is not frozen, for later rents.
+    pub fn release(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Unitime")] t: JsValue,
+    ) -> Result<(), ValidationError> {
+        if call(&t, "isFrozen")?.is_truthy() {
+            return Err(ValidationError::new("a frozen Unitime cannot go back to a UnitimePool"));
+        }
+        if self.free.len() < self.capacity {
+            self.free.push(t);
+        } else {
+            call(&t, "free")?;
+        }
+        Ok(())
+    }
+
+    /// Get the number of given-back objects waiting to be rented.
+    #[wasm_bindgen(getter)]
+    pub fn available(&self) -> u32 {
+        self.free.len() as u32
+    }
+
+    /// Free every object waiting to be rented.
+    pub fn clear(&mut self) -> Result<(), ValidationError> {
+        for t in self.free.drain(..) {
+            call(&t, "free")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, days_from_civil, DateTime, MS_PER_DAY};
+use crate::Unitime;
+
+impl Unitime {
+    /// Fraction of the way from one wall-clock boundary to the next. The boundaries are converted to instants
+    /// separately, so days with a daylight saving change are 23 or 25 hours long as they are on the clock.
+    /// Boundaries past the range of `Unitime` are taken to be at its ends.
+    fn fraction_between(&self, start_wall: i64, end_wall: i64, utc: bool) -> f64 {
+        let start = civil::wall_to_instant(start_wall, utc);
+        let end = civil::wall_to_instant(end_wall, utc);
+        (self.epoch_ms().saturating_sub(start) as f64 / end.saturating_sub(start) as f64).clamp(0.0, 1.0)
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get how far the stored time is through its day, from 0 at midnight to just under 1, in local time unless `utc` is `true`.
+    /// # Examples
+    /// ```
+    /// const f = new Unitime().fractionOfDay();
+    /// ring.style.setProperty("--progress", f);
+    /// ```
+    #[wasm_bindgen(js_name = "fractionOfDay")]
+    pub fn fraction_of_day(&self, utc: Option<bool>) -> f64 {
+        let utc = utc.unwrap_or(false);
+        let start = self.wall_ms(utc).div_euclid(MS_PER_DAY).saturating_mul(MS_PER_DAY);
+        self.fraction_between(start, start.saturating_add(MS_PER_DAY), utc)
+    }
+
+    /// Get how far the stored time is through its year, from 0 at New Year to just under 1, in local time unless `utc` is `true`.
+    /// # Examples
+    /// ```
+    /// const percent = Math.floor(new Unitime().fractionOfYear() * 100);
+    /// ```
+    #[wasm_bindgen(js_name = "fractionOfYear")]
+    pub fn fraction_of_year(&self, utc: Option<bool>) -> f64 {
+        let utc = utc.unwrap_or(false);
+        let year = DateTime::from_epoch_ms(self.wall_ms(utc)).year;
+        let start = days_from_civil(year, 1, 1).saturating_mul(MS_PER_DAY);
+        let end = days_from_civil(year + 1, 1, 1).saturating_mul(MS_PER_DAY);
+        self.fraction_between(start, end, utc)
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+struct Threshold {
+    id: u32,
+    per_second: f64,
+    window_ms: f64,
+    callback: Function,
+    /// Whether the rate was above the threshold when last checked.
+    above: bool,
+}
+
+/// Event rate detector for anomaly badges and spam protection. Feed it event timestamps and ask for
+/// the rate over any trailing window up to the longest one it keeps, or register thresholds that call
+/// back as the rate crosses them. Every method taking `now` uses the current time in epoch milliseconds
+/// if it is omitted.
+/// # Examples
+/// ```
+/// const clicks = new RateMonitor(60000);
+/// clicks.addThreshold(5, 1000, (rate, above) => button.disabled = above);
+/// button.onclick = () => clicks.record();
+/// ```
+#[wasm_bindgen]
+pub struct RateMonitor {
+    max_window_ms: f64,
+    events: VecDeque<f64>,
+    thresholds: Vec<Threshold>,
+    next_id: u32,
+}
+
+impl RateMonitor {
+    fn evict(&mut self, now: f64) {
+        let cutoff = now - self.max_window_ms;
+        while self.events.front().is_some_and(|&t| t <= cutoff) {
+            self.events.pop_front();
+        }
+    }
+
+    /// Events per second in the window of `window_ms` ending at `now`.
+    fn rate(&self, window_ms: f64, now: f64) -> f64 {
+        let cutoff = now - window_ms;
+        let count = self.events.iter().rev().skip_while(|&&t| t > now).take_while(|&&t| t > cutoff).count();
+        count as f64 * 1000.0 / window_ms
+    }
+
+    /// Call back for every threshold the rate has crossed since it was last checked.
+    fn check_thresholds(&mut self, now: f64) {
+        let mut crossed = Vec::new();
+        for i in 0..self.thresholds.len() {
+            let rate = self.rate(self.thresholds[i].window_ms, now);
+            let threshold = &mut self.thresholds[i];
+            let above = rate > threshold.per_second;
+            if above != threshold.above {
+                threshold.above = above;
+                crossed.push((threshold.callback.clone(), rate, above));
+            }
+        }
+        for (callback, rate, above) in crossed {
+            let _ = callback.call2(&JsValue::NULL, &JsValue::from_f64(rate), &JsValue::from_bool(above));
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RateMonitor {
+    /// Creates a new `RateMonitor` keeping events for the trailing `maxWindowMs` milliseconds, the
+    /// longest window it can report on.
+    #[wasm_bindgen(constructor)]
+    pub fn new(max_window_ms: f64) -> Result<RateMonitor, ValidationError> {
+        if !(max_window_ms > 0.0 && max_window_ms.is_finite()) {
+            return Err(ValidationError::new("rate window must be positive"));
+        }
+        Ok(RateMonitor { max_window_ms, events: VecDeque::new(), thresholds: Vec::new(), next_id: 0 })
+    }
+
+    /// Record an event at `t` in epoch milliseconds, or now if omitted, and call back for any thresholds
+    /// crossed. Events must be recorded in chronological order; an event earlier than the latest one is
+    /// ignored.
+    pub fn record(&mut self, t: Option<f64>) {
+        let t = t.unwrap_or_else(|| crate::now_ms() as f64);
+        if t.is_nan() || self.events.back().is_some_and(|&last| t < last) {
+            return;
+        }
+        self.events.push_back(t);
+        self.evict(t);
+        self.check_thresholds(t);
+    }
+
+    /// Get the events per second over the trailing `windowMs` milliseconds ending at `now`, or over the
+    /// longest window kept if omitted or longer.
+    /// # Examples
+    /// ```
+    /// badge.textContent = `${(clicks.currentRate(60000) * 60).toFixed(0)}/min`;
+    /// ```
+    #[wasm_bindgen(js_name = "currentRate")]
+    pub fn current_rate(&mut self, window_ms: Option<f64>, now: Option<f64>) -> Result<f64, ValidationError> {
+        let window_ms = window_ms.map_or(self.max_window_ms, |w| w.min(self.max_window_ms));
+        if window_ms.is_nan() || window_ms <= 0.0 {
+            return Err(ValidationError::new("rate window must be positive"));
+        }
+        let now = now.unwrap_or_else(|| crate::now_ms() as f64);
+        self.evict(now);
+        Ok(self.rate(window_ms, now))
+    }
+
+    /// Call `callback(rate, above)` whenever the events per second over the trailing `windowMs`
+    /// milliseconds rises above `perSecond`, with `above` `true`, or falls back to it or below, with
+    /// `above` `false`. Returns an id for `removeThreshold`. Rates only fall as time passes, so call
+    /// `check()` periodically to notice that while no events arrive.
+    #[wasm_bindgen(js_name = "addThreshold")]
+    pub fn add_threshold(
+        &mut self,
+        per_second: f64,
+        window_ms: f64,
+        callback: Function,
+    ) -> Result<u32, ValidationError> {
+        if !(window_ms > 0.0 && window_ms <= self.max_window_ms) {
+            return Err(ValidationError::new("threshold window must be positive and within the longest window kept"));
+        }
+        if per_second.is_nan() {
+            return Err(ValidationError::new("threshold rate must be a number"));
+        }
+        self.next_id += 1;
+        self.thresholds.push(Threshold { id: self.next_id, per_second, window_ms, callback, above: false });
+        Ok(self.next_id)
+    }
+
+    /// Remove the threshold with id `id`, returning whether there was one.
+    #[wasm_bindgen(js_name = "removeThreshold")]
+    pub fn remove_threshold(&mut self, id: u32) -> bool {
+        let before = self.thresholds.len();
+        self.thresholds.retain(|t| t.id != id);
+        self.thresholds.len() != before
+    }
+
+    /// Re-evaluate the thresholds at `now`, calling back for those crossed since the last event.
+    pub fn check(&mut self, now: Option<f64>) {
+        let now = now.unwrap_or_else(|| crate::now_ms() as f64);
+        self.evict(now);
+        self.check_thresholds(now);
+    }
+
+    /// Remove all recorded events, leaving the thresholds.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
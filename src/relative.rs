@@ -0,0 +1,323 @@
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN, MS_PER_SEC};
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::locale::Locale;
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// A unit that `UniDuration.describe` counts in.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeUnit {
+    Second = "second",
+    Minute = "minute",
+    Hour = "hour",
+    Day = "day",
+    Week = "week",
+    /// The average Gregorian month, 30.436875 days.
+    Month = "month",
+    /// The average Gregorian year, 365.2425 days.
+    Year = "year",
+}
+
+/// Each unit from the smallest, with its length in milliseconds.
+const UNITS: [(TimeUnit, i64); 7] = [
+    (TimeUnit::Second, MS_PER_SEC),
+    (TimeUnit::Minute, MS_PER_MIN),
+    (TimeUnit::Hour, MS_PER_HOUR),
+    (TimeUnit::Day, MS_PER_DAY),
+    (TimeUnit::Week, 7 * MS_PER_DAY),
+    (TimeUnit::Month, 2_629_746_000),
+    (TimeUnit::Year, 31_556_952_000),
+];
+
+impl TimeUnit {
+    /// Position in `UNITS` and in the unit names of a `Locale`.
+    fn index(self) -> Result<usize, ValidationError> {
+        UNITS.iter().position(|&(unit, _)| unit == self).ok_or_else(|| ValidationError::new("unknown time unit"))
+    }
+
+    /// The largest unit of which `ms` holds at least one, or seconds for less than a second.
+    fn largest_in(ms: i64) -> TimeUnit {
+        UNITS.iter().rev().find(|&&(_, len)| ms.unsigned_abs() >= len as u64).map_or(TimeUnit::Second, |u| u.0)
+    }
+}
+
+/// The non-negative `ms` in units of even length `len`, rounded half up.
+fn rounded(ms: i64, len: i64) -> i64 {
+    ms / len + (ms % len >= len / 2) as i64
+}
+
+/// Write `count` of the unit at `index` in `locale` with its names `units`, such as "1 minute" or
+/// "2 minutes".
+fn count_str(count: i64, index: usize, units: &[[&str; 2]; 7], locale: &Locale) -> String {
+    let name = units[index][(locale.plural)(count.unsigned_abs()) as usize];
+    format!("{count} {name}")
+}
+
+/// Where `Unitime.relativeLabel` moves from one unit to the next, as with Moment's
+/// `relativeTimeThreshold`. Each threshold is a count of the smaller unit: with `minutes` at 45, 44
+/// minutes are "44 minutes ago" and 45 minutes are "1 hour ago". Set them for every label with
+/// `Unitime.setRelativeThresholds`.
+/// # Examples
+/// ```
+/// const thresholds = new RelativeThresholds();
+/// thresholds.justNow = 10;
+/// thresholds.minutes = 60;
+/// Unitime.setRelativeThresholds(thresholds);
+/// ```
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelativeThresholds {
+    just_now: u32,
+    seconds: u32,
+    minutes: u32,
+    hours: u32,
+    days: u32,
+    months: u32,
+}
+
+impl Default for RelativeThresholds {
+    fn default() -> Self {
+        RelativeThresholds::new()
+    }
+}
+
+const MOMENT_THRESHOLDS: RelativeThresholds =
+    RelativeThresholds { just_now: 45, seconds: 45, minutes: 45, hours: 22, days: 26, months: 11 };
+
+thread_local! {
+    static THRESHOLDS: RefCell<RelativeThresholds> = const { RefCell::new(MOMENT_THRESHOLDS) };
+}
+
+impl RelativeThresholds {
+    /// The unit index and rounded count that a time `ms` away is labelled with, or `None` for "just now".
+    fn pick(&self, ms: i64) -> Option<(usize, i64)> {
+        let ms = ms.saturating_abs();
+        let count = |index: usize| rounded(ms, UNITS[index].1);
+        if count(0) < self.just_now as i64 {
+            return None;
+        }
+        // Months and years skip weeks, as Moment does.
+        let steps = [(0, self.seconds), (1, self.minutes), (2, self.hours), (3, self.days), (5, self.months)];
+        let index = steps.iter().find(|&&(index, limit)| count(index) < limit as i64).map_or(6, |step| step.0);
+        Some((index, count(index).max(1)))
+    }
+
+    /// Write the label of a time `ms` before now, or after it if negative, in `locale`.
+    fn write(&self, ms: i64, locale: &Locale) -> String {
+        match self.pick(ms) {
+            None => locale.just_now.to_string(),
+            Some((index, count)) => {
+                let span = count_str(count, index, locale.relative_units.as_ref().unwrap_or(&locale.units), locale);
+                locale.relative[(ms < 0) as usize].replace("{}", &span)
+            }
+        }
+    }
+
+    /// What the label of a time `ms` away shows: the unit and count, and whether it is in the future.
+    fn label(&self, ms: i64) -> Option<(usize, i64, bool)> {
+        self.pick(ms).map(|(index, count)| (index, count, ms < 0))
+    }
+
+    /// Milliseconds from `ms` to the next distance from now at which the label could change: where the
+    /// rounded count of the unit shown steps, where a threshold is crossed, or where a time in the future
+    /// becomes one in the past.
+    fn next_point(&self, ms: i64) -> i64 {
+        let distance = ms.saturating_abs();
+        // The distance at which `rounded` first reaches `count` of the unit at `index`.
+        let reaches = |index: usize, count: i64| count.saturating_mul(UNITS[index].1) - UNITS[index].1 / 2;
+        let mut points: Vec<i64> = [
+            (0, self.just_now),
+            (0, self.seconds),
+            (1, self.minutes),
+            (2, self.hours),
+            (3, self.days),
+            (5, self.months),
+        ]
+        .iter()
+        .map(|&(index, limit)| reaches(index, limit as i64))
+        .collect();
+        if let Some((index, _)) = self.pick(ms) {
+            let count = rounded(distance, UNITS[index].1);
+            points.extend([reaches(index, count), reaches(index, count.saturating_add(1))]);
+        }
+        if ms >= 0 {
+            points.into_iter().filter(|&p| p > distance).min().map_or(i64::MAX, |p| p - distance)
+        } else {
+            // Going toward the present, the label changes just under a point.
+            points.into_iter().filter(|&p| 0 < p && p <= distance).max().map_or(distance, |p| distance - p + 1)
+        }
+    }
+
+    /// Milliseconds from `ms` until the label changes. Not every point that `next_point` finds changes
+    /// it, such as when a count of 0 rounds up to 1 anyway.
+    fn next_change(&self, ms: i64) -> i64 {
+        let label = self.label(ms);
+        let mut t = ms;
+        for _ in 0..8 {
+            t = t.saturating_add(self.next_point(t));
+            if self.label(t) != label {
+                break;
+            }
+        }
+        t.saturating_sub(ms)
+    }
+}
+
+#[wasm_bindgen]
+impl RelativeThresholds {
+    /// Creates a new `RelativeThresholds` with Moment's defaults: "just now" under 45 seconds, minutes
+    /// under 45, hours under 22, days under 26 and months under 11, then years.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> RelativeThresholds {
+        MOMENT_THRESHOLDS
+    }
+
+    /// Get the seconds under which a time is "just now". At 0 even the present gets a count of seconds.
+    #[wasm_bindgen(getter, js_name = "justNow")]
+    pub fn just_now(&self) -> u32 {
+        self.just_now
+    }
+
+    #[wasm_bindgen(setter, js_name = "justNow")]
+    pub fn set_just_now(&mut self, seconds: u32) {
+        self.just_now = seconds;
+    }
+
+    /// Get the seconds under which a time is labelled in seconds, once it is not "just now".
+    #[wasm_bindgen(getter)]
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_seconds(&mut self, seconds: u32) {
+        self.seconds = seconds;
+    }
+
+    /// Get the minutes under which a time is labelled in minutes.
+    #[wasm_bindgen(getter)]
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_minutes(&mut self, minutes: u32) {
+        self.minutes = minutes;
+    }
+
+    /// Get the hours under which a time is labelled in hours.
+    #[wasm_bindgen(getter)]
+    pub fn hours(&self) -> u32 {
+        self.hours
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_hours(&mut self, hours: u32) {
+        self.hours = hours;
+    }
+
+    /// Get the days under which a time is labelled in days.
+    #[wasm_bindgen(getter)]
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_days(&mut self, days: u32) {
+        self.days = days;
+    }
+
+    /// Get the months under which a time is labelled in months rather than years.
+    #[wasm_bindgen(getter)]
+    pub fn months(&self) -> u32 {
+        self.months
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_months(&mut self, months: u32) {
+        self.months = months;
+    }
+}
+
+#[wasm_bindgen]
+impl UniDuration {
+    /// Describe the duration as a whole number of `unit`, rounded toward zero, with the unit's name in
+    /// the singular or plural as `locale`, English by default, needs it. Without `unit` it uses the
+    /// largest unit the duration holds at least one of. Months and years are the Gregorian averages.
+    /// # Examples
+    /// ```
+    /// new UniDuration(60_000).describe(); // "1 minute"
+    /// new UniDuration(150_000).describe(); // "2 minutes"
+    /// new UniDuration(5_400_000).describe("minute", "de"); // "90 Minuten"
+    /// new UniDuration(1_000).describe("minute", "fr"); // "0 minute"
+    /// ```
+    pub fn describe(&self, unit: Option<TimeUnit>, locale: Option<String>) -> Result<String, ValidationError> {
+        let ms = self.nanos() / NANOS_PER_MILLI;
+        let index = unit.unwrap_or_else(|| TimeUnit::largest_in(ms)).index()?;
+        let locale = Locale::get(locale.as_deref());
+        Ok(count_str(ms / UNITS[index].1, index, &locale.units, locale))
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Label the stored time relative to `now` in epoch milliseconds, or the current time if omitted,
+    /// the way feeds do, as "just now", "5 minutes ago" or "in 2 hours" in `locale`, English by default.
+    /// The count is rounded to the nearest whole unit, and the unit is chosen by the thresholds given to
+    /// `setRelativeThresholds`.
+    /// # Examples
+    /// ```
+    /// post.createdAt.relativeLabel(); // "3 hours ago"
+    /// post.createdAt.relativeLabel("de"); // "vor 3 Stunden"
+    /// ```
+    #[wasm_bindgen(js_name = "relativeLabel")]
+    pub fn relative_label(&self, locale: Option<String>, now: Option<f64>) -> String {
+        let ms = now.map_or_else(crate::now_ms, |ms| ms as i64).saturating_sub(self.epoch_ms());
+        THRESHOLDS.with(|t| t.borrow().write(ms, Locale::get(locale.as_deref())))
+    }
+
+    /// Label every time in `epochsMs` as `relativeLabel` does, all against the same `now` in epoch
+    /// milliseconds, or the current time if omitted, in one call rather than one per row.
+    /// # Examples
+    /// ```
+    /// const labels = Unitime.relativeLabels(posts.map(p => p.createdAt), "en");
+    /// posts.forEach((p, i) => (p.label = labels[i]));
+    /// ```
+    #[wasm_bindgen(js_name = "relativeLabels")]
+    pub fn relative_labels(epochs_ms: Vec<f64>, locale: Option<String>, now: Option<f64>) -> Vec<String> {
+        let now = now.map_or_else(crate::now_ms, |ms| ms as i64);
+        let locale = Locale::get(locale.as_deref());
+        THRESHOLDS.with(|t| {
+            let thresholds = t.borrow();
+            epochs_ms.iter().map(|&ms| thresholds.write(now.saturating_sub(ms as i64), locale)).collect()
+        })
+    }
+
+    /// Get how long until `relativeLabel` for `now` in epoch milliseconds, or the current time if
+    /// omitted, would change, so that a feed can re-render each label once when it changes rather than
+    /// on a timer every second.
+    /// # Examples
+    /// ```
+    /// const schedule = () => {
+    ///     el.textContent = t.relativeLabel();
+    ///     setTimeout(schedule, t.nextLabelChangeIn().milliseconds);
+    /// };
+    /// ```
+    #[wasm_bindgen(js_name = "nextLabelChangeIn")]
+    pub fn next_label_change_in(&self, now: Option<f64>) -> UniDuration {
+        let ms = now.map_or_else(crate::now_ms, |ms| ms as i64).saturating_sub(self.epoch_ms());
+        let change = THRESHOLDS.with(|t| t.borrow().next_change(ms));
+        UniDuration::from_nanos(change.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Set where `relativeLabel` moves from one unit to the next, for every label from then on.
+    #[wasm_bindgen(js_name = "setRelativeThresholds")]
+    pub fn set_relative_thresholds(thresholds: &RelativeThresholds) {
+        THRESHOLDS.with(|t| *t.borrow_mut() = thresholds.clone());
+    }
+}
@@ -0,0 +1,121 @@
+//! Recording of clock reads and playing them back, so that a bug in time-dependent code seen once can
+//! be reproduced exactly in a test.
+
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Unitime, NANOS_PER_MILLI};
+
+/// What happens to reads of the system and monotonic clocks.
+enum Tape {
+    /// They read the clocks.
+    Off,
+    /// They read the clocks and their readings, in milliseconds, are kept in order.
+    Recording(Vec<f64>),
+    /// They give the readings of a recording in order instead, repeating the last once they run out.
+    Replaying { reads: Vec<f64>, next: usize },
+}
+
+thread_local! {
+    static TAPE: RefCell<Tape> = const { RefCell::new(Tape::Off) };
+}
+
+/// The next reading of a replay, or `None` to read the clock.
+fn replayed() -> Option<f64> {
+    TAPE.with(|tape| match &mut *tape.borrow_mut() {
+        Tape::Replaying { reads, next } => {
+            let read = reads.get(*next).or(reads.last()).copied();
+            *next += 1;
+            read
+        }
+        _ => None,
+    })
+}
+
+fn record(ms: f64) {
+    TAPE.with(|tape| {
+        if let Tape::Recording(reads) = &mut *tape.borrow_mut() {
+            reads.push(ms);
+        }
+    });
+}
+
+/// Read the system clock in epoch nanoseconds with `live`, unless a replay gives the reading.
+pub(crate) fn system_nanos(live: impl FnOnce() -> i128) -> i128 {
+    if let Some(ms) = replayed() {
+        return (ms * NANOS_PER_MILLI as f64) as i128;
+    }
+    let nanos = live();
+    record(nanos as f64 / NANOS_PER_MILLI as f64);
+    nanos
+}
+
+/// Read the monotonic clock in milliseconds with `live`, unless a replay gives the reading.
+pub(crate) fn monotonic_ms(live: impl FnOnce() -> f64) -> f64 {
+    if let Some(ms) = replayed() {
+        return ms;
+    }
+    let ms = live();
+    record(ms);
+    ms
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Start keeping every read of the system and monotonic clocks, such as `new Unitime()`, in order,
+    /// until `stopRecording`. The clocks are still read as usual. A replay under way ends.
+    /// # Examples
+    /// ```
+    /// Unitime.recordClock();
+    /// runCountdown();
+    /// const reads = Unitime.stopRecording();
+    /// report({ reads: JSON.stringify(Array.from(reads)) });
+    /// ```
+    #[wasm_bindgen(js_name = "recordClock")]
+    pub fn record_clock() {
+        TAPE.with(|tape| *tape.borrow_mut() = Tape::Recording(Vec::new()));
+    }
+
+    /// Stop recording and get the clock readings in milliseconds in the order they were made, as a
+    /// `Float64Array` that is empty if nothing was being recorded.
+    #[wasm_bindgen(js_name = "stopRecording")]
+    pub fn stop_recording() -> Vec<f64> {
+        TAPE.with(|tape| match tape.replace(Tape::Off) {
+            Tape::Recording(reads) => reads,
+            other => {
+                *tape.borrow_mut() = other;
+                Vec::new()
+            }
+        })
+    }
+
+    /// Make reads of the system and monotonic clocks give `reads`, as `stopRecording` gave them, in
+    /// order instead of reading the clocks, until `stopReplay`. Once they run out, the last is given
+    /// again, so the time stands still rather than jumping to the real clock. The same code doing the
+    /// same calls sees the same times as when they were recorded. A recording under way ends.
+    /// # Examples
+    /// ```
+    /// Unitime.replayClock(JSON.parse(report.reads));
+    /// runCountdown(); // shows what the user saw
+    /// Unitime.stopReplay();
+    /// ```
+    #[wasm_bindgen(js_name = "replayClock")]
+    pub fn replay_clock(reads: Vec<f64>) {
+        TAPE.with(|tape| *tape.borrow_mut() = Tape::Replaying { reads, next: 0 });
+    }
+
+    /// Stop replaying and go back to reading the clocks. Get how many readings were left unused, or
+    /// minus how many more reads there were than readings, so 0 means the code read the clocks exactly
+    /// as often as when it was recorded. Without a replay under way, 0.
+    #[wasm_bindgen(js_name = "stopReplay")]
+    pub fn stop_replay() -> i32 {
+        TAPE.with(|tape| match tape.replace(Tape::Off) {
+            Tape::Replaying { reads, next } => (reads.len() as i64 - next as i64) as i32,
+            other => {
+                *tape.borrow_mut() = other;
+                0
+            }
+        })
+    }
+}
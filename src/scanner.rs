@@ -0,0 +1,187 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, MS_PER_MIN};
+use crate::logtime::{parse_clf, resolve_syslog};
+use crate::parse::Cursor;
+use crate::sql::parse_date_time;
+
+/// Bytes kept back at the end of a chunk so that timestamps split across chunks are still found.
+/// Must be longer than the longest recognized timestamp.
+const CARRY: usize = 64;
+
+/// Scanner that finds timestamps in log text, fed either as one string or as consecutive chunks.
+/// Recognizes ISO 8601 / SQL datetimes (with optional offset), BSD syslog and Apache CLF timestamps.
+/// Match offsets and lengths are in UTF-16 code units from the start of the stream, as used by JS strings.
+#[wasm_bindgen]
+pub struct TimestampScanner {
+    utc: bool,
+    now_wall: i64,
+    pending: String,
+    pending_offset: usize,
+    prev: Option<u8>,
+    offsets: Vec<u32>,
+    lengths: Vec<u32>,
+    epochs: Vec<f64>,
+}
+
+impl TimestampScanner {
+    /// Convert a wall-clock reading without an explicit offset to an instant.
+    fn resolve_wall(&self, wall: i64) -> i64 {
+        civil::wall_to_instant(wall, self.utc)
+    }
+
+    /// Try every known format at the start of `s`, returning the byte length and epoch milliseconds of a match.
+    fn match_at(&self, s: &str) -> Option<(usize, i64)> {
+        let first = *s.as_bytes().first()?;
+        let mut c = Cursor::new(s);
+        let (len, ms) = if first.is_ascii_digit() {
+            if let Some(dt) = parse_date_time(&mut c).filter(|dt| dt.is_valid()) {
+                let ms = match c.utc_offset() {
+                    Some(offset) => dt.to_epoch_ms() - offset * MS_PER_MIN,
+                    None => self.resolve_wall(dt.to_epoch_ms()),
+                };
+                (c.pos(), ms)
+            } else {
+                c = Cursor::new(s);
+                let (dt, offset) = parse_clf(&mut c).filter(|(dt, _)| dt.is_valid())?;
+                let ms = match offset {
+                    Some(offset) => dt.to_epoch_ms() - offset * MS_PER_MIN,
+                    None => self.resolve_wall(dt.to_epoch_ms()),
+                };
+                (c.pos(), ms)
+            }
+        } else {
+            let wall = resolve_syslog(&mut c, self.now_wall)?;
+            (c.pos(), self.resolve_wall(wall))
+        };
+        match s.as_bytes().get(len) {
+            Some(b) if b.is_ascii_alphanumeric() => None,
+            _ => Some((len, ms)),
+        }
+    }
+
+    /// Scan the pending text up to byte `limit`, then drop the scanned part.
+    fn scan_pending(&mut self, limit: usize) {
+        let mut i = 0;
+        let mut units = 0;
+        while i < limit {
+            let rest = &self.pending[i..];
+            let at_boundary = match if i == 0 { self.prev } else { Some(self.pending.as_bytes()[i - 1]) } {
+                Some(b) => !b.is_ascii_alphanumeric(),
+                None => true,
+            };
+            if at_boundary {
+                if let Some((len, ms)) = self.match_at(rest) {
+                    self.offsets.push((self.pending_offset + units) as u32);
+                    self.lengths.push(len as u32);
+                    self.epochs.push(ms as f64);
+                    // Timestamps are ASCII, so bytes and UTF-16 units agree.
+                    i += len;
+                    units += len;
+                    continue;
+                }
+            }
+            let Some(ch) = rest.chars().next() else { break };
+            i += ch.len_utf8();
+            units += ch.len_utf16();
+        }
+        if i > 0 {
+            self.prev = Some(self.pending.as_bytes()[i - 1]);
+        }
+        self.pending.drain(..i);
+        self.pending_offset += units;
+    }
+}
+
+#[wasm_bindgen]
+impl TimestampScanner {
+    /// Creates a new `TimestampScanner`. Timestamps without an offset are read as UTC if `utc` is `true`, else as local time.
+    /// # Examples
+    /// ```
+    /// const scanner = new TimestampScanner(true);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(utc: bool) -> TimestampScanner {
+        let now = crate::now_ms();
+        TimestampScanner {
+            utc,
+            now_wall: civil::instant_to_wall(now, utc),
+            pending: String::new(),
+            pending_offset: 0,
+            prev: None,
+            offsets: Vec::new(),
+            lengths: Vec::new(),
+            epochs: Vec::new(),
+        }
+    }
+
+    /// Feed the next chunk of text. Timestamps near the end of the chunk are held back until more text or `finish()` arrives.
+    /// Returns the total number of timestamps found so far.
+    /// # Examples
+    /// ```
+    /// const scanner = new TimestampScanner(true);
+    /// scanner.push(chunk1);
+    /// scanner.push(chunk2);
+    /// scanner.finish();
+    /// ```
+    pub fn push(&mut self, chunk: &str) -> usize {
+        self.pending.push_str(chunk);
+        if self.pending.len() > CARRY {
+            let mut limit = self.pending.len() - CARRY;
+            while !self.pending.is_char_boundary(limit) {
+                limit -= 1;
+            }
+            self.scan_pending(limit);
+        }
+        self.epochs.len()
+    }
+
+    /// Scan any text still held back. Returns the total number of timestamps found.
+    pub fn finish(&mut self) -> usize {
+        self.scan_pending(self.pending.len());
+        self.epochs.len()
+    }
+
+    /// Scan a whole string at once. Returns the total number of timestamps found.
+    /// # Examples
+    /// ```
+    /// const scanner = new TimestampScanner(true);
+    /// const count = scanner.scan(logText);
+    /// const epochs = scanner.epochs;
+    /// ```
+    pub fn scan(&mut self, text: &str) -> usize {
+        self.push(text);
+        self.finish()
+    }
+
+    /// Get the number of timestamps found so far.
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> usize {
+        self.epochs.len()
+    }
+
+    /// Get the start offset of each found timestamp.
+    #[wasm_bindgen(getter)]
+    pub fn offsets(&self) -> Vec<u32> {
+        self.offsets.clone()
+    }
+
+    /// Get the length of each found timestamp.
+    #[wasm_bindgen(getter)]
+    pub fn lengths(&self) -> Vec<u32> {
+        self.lengths.clone()
+    }
+
+    /// Get the epoch milliseconds of each found timestamp.
+    #[wasm_bindgen(getter)]
+    pub fn epochs(&self) -> Vec<f64> {
+        self.epochs.clone()
+    }
+
+    /// Forget the found timestamps, keeping the stream position, so results can be collected per batch.
+    pub fn clear(&mut self) {
+        self.offsets.clear();
+        self.lengths.clear();
+        self.epochs.clear();
+    }
+}
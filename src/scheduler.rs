@@ -0,0 +1,297 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::rc::Rc;
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::cron::Cron;
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::occurrence::{next_checked_occurrence, next_occurrence, wall_time_rule};
+use crate::validation::{check_date_ms, ValidationError};
+use crate::Unitime;
+
+/// When a job fires again after firing at `at`, given that it is now `now`.
+enum Rule {
+    Once,
+    Every(i64),
+    Daily { time: i64, mask: u8, utc: bool },
+    Cron(Cron, bool),
+}
+
+impl Rule {
+    /// The next time to fire after `now`, for a job last due at `at`.
+    fn next(&self, at: i64, now: i64) -> Option<i64> {
+        match self {
+            Rule::Once => None,
+            Rule::Every(interval) => {
+                let intervals = (now.saturating_sub(at).max(0) / interval).saturating_add(1);
+                Some(at.saturating_add(intervals.saturating_mul(*interval)))
+            }
+            &Rule::Daily { time, mask, utc } => next_occurrence(now, time, mask, utc),
+            Rule::Cron(cron, utc) => cron.next(now, *utc),
+        }
+    }
+}
+
+struct Job {
+    at: i64,
+    rule: Rule,
+    callback: Function,
+}
+
+#[derive(Default)]
+struct State {
+    /// Pending fire times and job ids, earliest first. Entries of cancelled or moved jobs are left in
+    /// place and skipped when they come up.
+    queue: BinaryHeap<Reverse<(i64, u32)>>,
+    jobs: HashMap<u32, Job>,
+    next_id: u32,
+    timer: Option<JsValue>,
+    #[cfg(js)]
+    tick: Option<Closure<dyn FnMut()>>,
+}
+
+impl State {
+    /// Drop stale entries from the front of the queue and get the earliest live one.
+    fn peek(&mut self) -> Option<(i64, u32)> {
+        while let Some(&Reverse((at, id))) = self.queue.peek() {
+            if self.jobs.get(&id).is_some_and(|job| job.at == at) {
+                return Some((at, id));
+            }
+            self.queue.pop();
+        }
+        None
+    }
+}
+
+/// Run every job that is due, scheduling the next run of recurring ones, then arm the timer. Callbacks
+/// run with no borrow held, so they may add and cancel jobs.
+fn fire_due(state: &Rc<RefCell<State>>) {
+    let now = crate::now_ms();
+    let mut due = Vec::new();
+    {
+        let mut s = state.borrow_mut();
+        while let Some((at, id)) = s.peek().filter(|&(at, _)| at <= now) {
+            s.queue.pop();
+            let Some(job) = s.jobs.get_mut(&id) else { continue };
+            due.push((id, job.callback.clone()));
+            match job.rule.next(at, now) {
+                Some(next) => {
+                    job.at = next;
+                    s.queue.push(Reverse((next, id)));
+                }
+                None => {
+                    s.jobs.remove(&id);
+                }
+            }
+        }
+    }
+    for (id, callback) in due {
+        // An exception in one job must not keep the others from running.
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from(id));
+    }
+    arm(state);
+}
+
+/// Replace the pending timeout with one for the earliest job.
+#[cfg(js)]
+fn arm(state: &Rc<RefCell<State>>) {
+    let mut s = state.borrow_mut();
+    if let Some(timer) = s.timer.take() {
+        crate::timer::clear_timeout(&timer);
+    }
+    let Some((next, _)) = s.peek() else {
+        return;
+    };
+    let weak = Rc::downgrade(state);
+    let tick = s.tick.get_or_insert_with(|| {
+        Closure::new(move || {
+            if let Some(state) = weak.upgrade() {
+                state.borrow_mut().timer = None;
+                fire_due(&state);
+            }
+        })
+    });
+    s.timer = crate::timer::set_timeout(tick, next - crate::now_ms());
+}
+
+/// There are no timers outside a JS host; `check` still runs due jobs.
+#[cfg(not(js))]
+fn arm(state: &Rc<RefCell<State>>) {
+    state.borrow_mut().timer = None;
+}
+
+/// An upcoming run of a `Scheduler` job, as listed by `nextJobs`.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ScheduledJob {
+    id: u32,
+    at: i64,
+}
+
+#[wasm_bindgen]
+impl ScheduledJob {
+    /// Get the id of the job.
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Get the time the job runs.
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> Unitime {
+        Unitime::from_epoch_ms(self.at)
+    }
+}
+
+/// Jobs of every kind, one-shot, delayed, interval, daily and cron, kept in one queue ordered by their
+/// next run and driven by a single `setTimeout`. Callbacks get the job id. Like `AlarmClock`, the
+/// scheduler wakes at least once a minute to check the time, so jobs run soon after the page resumes
+/// from sleep, each once however many of its runs were missed.
+/// # Examples
+/// ```
+/// const jobs = new Scheduler();
+/// jobs.cron("0,15,30,45 9-17 * * mon-fri", id => syncInbox());
+/// jobs.after(UniDuration.fromSeconds(30), id => hideToast());
+/// for (const job of jobs.nextJobs(5)) console.log(job.id, job.time.toString());
+/// ```
+#[wasm_bindgen]
+pub struct Scheduler {
+    state: Rc<RefCell<State>>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::new()
+    }
+}
+
+impl Scheduler {
+    fn add(&self, at: i64, rule: Rule, callback: Function) -> u32 {
+        let id = {
+            let mut s = self.state.borrow_mut();
+            s.next_id += 1;
+            let id = s.next_id;
+            s.jobs.insert(id, Job { at, rule, callback });
+            s.queue.push(Reverse((at, id)));
+            id
+        };
+        arm(&self.state);
+        id
+    }
+}
+
+#[wasm_bindgen]
+impl Scheduler {
+    /// Creates a new `Scheduler` with no jobs.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Scheduler {
+        Scheduler { state: Rc::default() }
+    }
+
+    /// Add a job that runs once at `time`, right away if it has passed. Returns its id.
+    pub fn at(&self, time: &Unitime, callback: Function) -> u32 {
+        self.add(time.epoch_ms(), Rule::Once, callback)
+    }
+
+    /// Add a job that runs once, `delay` from now. Returns its id.
+    pub fn after(&self, delay: &UniDuration, callback: Function) -> u32 {
+        let at = crate::now_ms().saturating_add(delay.nanos().div_euclid(NANOS_PER_MILLI));
+        self.add(at, Rule::Once, callback)
+    }
+
+    /// Add a job that runs every `interval`, first at `first` in epoch milliseconds, within the range of
+    /// a JavaScript `Date`, or one interval from now. Returns its id.
+    pub fn every(&self, interval: &UniDuration, callback: Function, first: Option<f64>) -> Result<u32, ValidationError> {
+        let interval_ms = interval.nanos() / NANOS_PER_MILLI;
+        if interval_ms < 1 {
+            return Err(ValidationError::new("job interval must be at least 1 ms"));
+        }
+        let at = match first {
+            Some(ms) => check_date_ms("first", ms)?,
+            None => crate::now_ms() + interval_ms,
+        };
+        Ok(self.add(at, Rule::Every(interval_ms), callback))
+    }
+
+    /// Add a job that runs whenever the clock reads `hour`:`minute`, in local time unless `utc` is
+    /// `true`, on every day or only on `weekdays` from 0 for Sunday. Returns its id.
+    pub fn daily(
+        &self,
+        hour: u32,
+        minute: u32,
+        callback: Function,
+        weekdays: Option<Vec<u32>>,
+        utc: Option<bool>,
+    ) -> Result<u32, ValidationError> {
+        let (time, mask) = wall_time_rule(hour, minute, weekdays)?;
+        let utc = utc.unwrap_or(false);
+        let at = next_checked_occurrence(crate::now_ms(), time, mask, utc)?;
+        Ok(self.add(at, Rule::Daily { time, mask, utc }, callback))
+    }
+
+    /// Add a job that runs at the times of a five-field cron expression, `minute hour day month
+    /// weekday`, read in local time unless `utc` is `true`. Fields take `*`, lists, ranges, steps and
+    /// month and weekday names, and `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` stand for
+    /// the usual expressions. Returns its id.
+    /// # Examples
+    /// ```
+    /// jobs.cron("0 9 * * 1", id => sendDigest()); // Mondays at 9:00
+    /// jobs.cron("@monthly", id => rotateKeys(), true);
+    /// ```
+    pub fn cron(&self, expr: &str, callback: Function, utc: Option<bool>) -> Result<u32, ValidationError> {
+        let cron = Cron::parse(expr)?;
+        let utc = utc.unwrap_or(false);
+        let at = cron.next(crate::now_ms(), utc).ok_or_else(|| ValidationError::new("cron expression never matches"))?;
+        Ok(self.add(at, Rule::Cron(cron, utc), callback))
+    }
+
+    /// Remove the job with id `id`, returning whether there was one.
+    pub fn cancel(&self, id: u32) -> bool {
+        let removed = self.state.borrow_mut().jobs.remove(&id).is_some();
+        arm(&self.state);
+        removed
+    }
+
+    /// Remove every job.
+    pub fn clear(&self) {
+        {
+            let mut s = self.state.borrow_mut();
+            s.jobs.clear();
+            s.queue.clear();
+        }
+        arm(&self.state);
+    }
+
+    /// Run every job that is due now and re-arm the timer, e.g. when the page becomes visible again.
+    pub fn check(&self) {
+        fire_due(&self.state);
+    }
+
+    /// Get the next `n` runs across all jobs in time order, with recurring jobs listed once per run,
+    /// for previews such as "upcoming tasks".
+    #[wasm_bindgen(js_name = "nextJobs")]
+    pub fn next_jobs(&self, n: usize) -> Vec<ScheduledJob> {
+        let s = self.state.borrow();
+        let mut queue: BinaryHeap<_> = s.jobs.iter().map(|(&id, job)| Reverse((job.at, id))).collect();
+        let mut runs = Vec::with_capacity(n.min(s.jobs.len() * 8));
+        while runs.len() < n {
+            let Some(Reverse((at, id))) = queue.pop() else {
+                break;
+            };
+            runs.push(ScheduledJob { id, at });
+            if let Some(next) = s.jobs[&id].rule.next(at, at) {
+                queue.push(Reverse((next, id)));
+            }
+        }
+        runs
+    }
+
+    /// Get the number of jobs.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.state.borrow().jobs.len()
+    }
+}
@@ -0,0 +1,289 @@
+use js_sys::Float64Array;
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Sorted column of timestamps in epoch milliseconds, stored contiguously for fast lookups.
+/// Timestamps are kept as `f64` so they can be shared with JS as a `Float64Array` without conversion.
+#[wasm_bindgen]
+pub struct TimeSeriesIndex {
+    times: Vec<f64>,
+}
+
+/// Upper bound on the number of buckets a single aggregation may produce.
+pub(crate) const MAX_BUCKETS: usize = 1 << 24;
+
+/// How the values falling into one bucket are combined by `TimeSeriesIndex.bucketAggregate`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    Sum = "sum",
+    Avg = "avg",
+    Min = "min",
+    Max = "max",
+}
+
+/// Check that `times` holds ascending numbers, none earlier than `after`.
+fn is_ascending(times: &[f64], after: f64) -> bool {
+    !times.iter().any(|t| t.is_nan())
+        && times.first().is_none_or(|&t| t >= after)
+        && times.windows(2).all(|w| w[0] <= w[1])
+}
+
+#[wasm_bindgen]
+impl TimeSeriesIndex {
+    /// Creates a new, empty `TimeSeriesIndex`, optionally reserving room for `capacity` timestamps if there
+    /// is that much memory.
+    /// # Examples
+    /// ```
+    /// const index = new TimeSeriesIndex(1024);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: Option<usize>) -> TimeSeriesIndex {
+        let mut times = Vec::new();
+        // The capacity is only a hint, so an index that does not fit still starts empty
+        let _ = times.try_reserve_exact(capacity.unwrap_or(0));
+        TimeSeriesIndex { times }
+    }
+
+    /// Creates a new `TimeSeriesIndex` from an array of ascending epoch milliseconds.
+    /// # Examples
+    /// ```
+    /// const index = TimeSeriesIndex.fromArray(new Float64Array([1000, 2000, 3000]));
+    /// ```
+    #[wasm_bindgen(js_name = "fromArray")]
+    pub fn from_array(times: Vec<f64>) -> Result<TimeSeriesIndex, ValidationError> {
+        if !is_ascending(&times, f64::NEG_INFINITY) {
+            return Err(ValidationError::new("timestamps must be ascending numbers"));
+        }
+        Ok(TimeSeriesIndex { times })
+    }
+
+    /// Append a timestamp, which must not be earlier than the last one.
+    /// # Examples
+    /// ```
+    /// index.push(Date.now());
+    /// ```
+    pub fn push(&mut self, t: f64) -> Result<(), ValidationError> {
+        if !is_ascending(&[t], self.times.last().copied().unwrap_or(f64::NEG_INFINITY)) {
+            return Err(ValidationError::new("timestamp is earlier than the last one in the index"));
+        }
+        self.times.push(t);
+        Ok(())
+    }
+
+    /// Append an array of ascending timestamps, none earlier than the last one already stored.
+    pub fn extend(&mut self, times: Vec<f64>) -> Result<(), ValidationError> {
+        let last = self.times.last().copied().unwrap_or(f64::NEG_INFINITY);
+        if !is_ascending(&times, last) {
+            return Err(ValidationError::new(
+                "timestamps must be ascending and not earlier than the last one in the index",
+            ));
+        }
+        self.times.extend_from_slice(&times);
+        Ok(())
+    }
+
+    /// Get the number of stored timestamps.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Get the timestamp at index `i`, or `undefined` if out of range.
+    pub fn get(&self, i: usize) -> Option<f64> {
+        self.times.get(i).copied()
+    }
+
+    /// Get the earliest stored timestamp, or `undefined` if empty.
+    #[wasm_bindgen(getter)]
+    pub fn first(&self) -> Option<f64> {
+        self.times.first().copied()
+    }
+
+    /// Get the latest stored timestamp, or `undefined` if empty.
+    #[wasm_bindgen(getter)]
+    pub fn last(&self) -> Option<f64> {
+        self.times.last().copied()
+    }
+
+    /// Get the index of the first timestamp at or after `t`, which is `length` if there is none.
+    /// # Examples
+    /// ```
+    /// const i = index.indexOfAtOrAfter(Date.now() - 60000);
+    /// ```
+    #[wasm_bindgen(js_name = "indexOfAtOrAfter")]
+    pub fn index_of_at_or_after(&self, t: f64) -> usize {
+        self.times.partition_point(|&x| x < t)
+    }
+
+    /// Get the index of the last timestamp at or before `t`, which is -1 if there is none.
+    #[wasm_bindgen(js_name = "indexOfAtOrBefore")]
+    pub fn index_of_at_or_before(&self, t: f64) -> i32 {
+        self.times.partition_point(|&x| x <= t) as i32 - 1
+    }
+
+    /// Get a new `TimeSeriesIndex` holding the timestamps in the half-open interval `[start, end)`.
+    /// # Examples
+    /// ```
+    /// const lastHour = index.slice(Date.now() - 3600000, Date.now());
+    /// ```
+    pub fn slice(&self, start: f64, end: f64) -> TimeSeriesIndex {
+        let from = self.index_of_at_or_after(start);
+        let to = self.index_of_at_or_after(end).max(from);
+        TimeSeriesIndex { times: self.times[from..to].to_vec() }
+    }
+
+    /// Get a copy of the timestamps as a `Float64Array`.
+    #[wasm_bindgen(js_name = "toArray")]
+    pub fn to_array(&self) -> Vec<f64> {
+        self.times.clone()
+    }
+
+    /// Get a zero-copy `Float64Array` view into the stored timestamps.
+    /// The view is only valid until the index is modified or WebAssembly memory grows, so copy it if it needs to be kept.
+    /// # Examples
+    /// ```
+    /// const view = index.view();
+    /// chart.draw(view);
+    /// ```
+    pub fn view(&self) -> Float64Array {
+        // SAFETY: the caller is told the view must not outlive the next mutation or allocation.
+        unsafe { Float64Array::view(&self.times) }
+    }
+
+    /// Remove all stored timestamps.
+    pub fn clear(&mut self) {
+        self.times.clear();
+    }
+
+    /// Get the start time of every bucket spanned by the stored timestamps, for use as the x axis of
+    /// `bucketCounts` and `bucketAggregate`. Buckets are `bucketMs` wide and aligned to `origin`, which defaults to the epoch;
+    /// pass e.g. a local midnight to align daily buckets to the local day, or use `calendarBucketStarts` for
+    /// days, weeks and months that follow the clocks.
+    /// # Examples
+    /// ```
+    /// const starts = index.bucketStarts(60000);
+    /// ```
+    #[wasm_bindgen(js_name = "bucketStarts")]
+    pub fn bucket_starts(&self, bucket_ms: f64, origin: Option<f64>) -> Result<Vec<f64>, ValidationError> {
+        let origin = origin.unwrap_or(0.0);
+        let (first, count) = self.bucket_range(bucket_ms, origin)?;
+        Ok((0..count).map(|i| origin + (first + i as f64) * bucket_ms).collect())
+    }
+
+    /// Count the timestamps falling into each bucket, as laid out by `bucketStarts`.
+    /// # Examples
+    /// ```
+    /// const counts = index.bucketCounts(60000);
+    /// ```
+    #[wasm_bindgen(js_name = "bucketCounts")]
+    pub fn bucket_counts(&self, bucket_ms: f64, origin: Option<f64>) -> Result<Vec<u32>, ValidationError> {
+        let origin = origin.unwrap_or(0.0);
+        let (first, count) = self.bucket_range(bucket_ms, origin)?;
+        Ok(count_buckets(&self.times, count, |t| bucket_of(t, bucket_ms, origin, first)))
+    }
+
+    /// Combine `values`, one per stored timestamp, within each bucket as laid out by `bucketStarts`.
+    /// Empty buckets are 0 for `"sum"` and `NaN` otherwise, so they show up as gaps when plotted.
+    /// # Examples
+    /// ```
+    /// const avgLatency = index.bucketAggregate(latencies, 60000, "avg");
+    /// ```
+    #[wasm_bindgen(js_name = "bucketAggregate")]
+    pub fn bucket_aggregate(
+        &self,
+        values: Vec<f64>,
+        bucket_ms: f64,
+        op: Aggregation,
+        origin: Option<f64>,
+    ) -> Result<Vec<f64>, ValidationError> {
+        self.check_aggregation(&values, op)?;
+        let origin = origin.unwrap_or(0.0);
+        let (first, count) = self.bucket_range(bucket_ms, origin)?;
+        Ok(aggregate_buckets(&self.times, &values, op, count, |t| bucket_of(t, bucket_ms, origin, first)))
+    }
+}
+
+impl TimeSeriesIndex {
+    /// Get the stored timestamps.
+    #[cfg_attr(not(feature = "tz"), allow(dead_code))]
+    pub(crate) fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Check that there is one of `values` per stored timestamp and that `op` is known.
+    pub(crate) fn check_aggregation(&self, values: &[f64], op: Aggregation) -> Result<(), ValidationError> {
+        if values.len() != self.times.len() {
+            return Err(ValidationError::new("expected exactly one value per timestamp"));
+        }
+        if !matches!(op, Aggregation::Sum | Aggregation::Avg | Aggregation::Min | Aggregation::Max) {
+            return Err(ValidationError::new("unknown aggregation, expected sum, avg, min or max"));
+        }
+        Ok(())
+    }
+
+    /// Get the index of the first bucket, counted from `origin`, and the number of buckets spanned by the stored timestamps.
+    fn bucket_range(&self, bucket_ms: f64, origin: f64) -> Result<(f64, usize), ValidationError> {
+        if bucket_ms.is_nan() || bucket_ms <= 0.0 || !origin.is_finite() {
+            return Err(ValidationError::new("bucket size must be positive and origin finite"));
+        }
+        match (self.times.first(), self.times.last()) {
+            (Some(&first), Some(&last)) => {
+                let first = ((first - origin) / bucket_ms).floor();
+                let last = ((last - origin) / bucket_ms).floor();
+                if last - first >= MAX_BUCKETS as f64 {
+                    let buckets = (last - first + 1.0).min(i64::MAX as f64) as i64;
+                    return Err(ValidationError::range("buckets", buckets, 1, MAX_BUCKETS as i64));
+                }
+                Ok((first, (last - first) as usize + 1))
+            }
+            _ => Ok((0.0, 0)),
+        }
+    }
+}
+
+/// Count the `times` in each of `count` buckets, with `bucket` giving the bucket of a time.
+pub(crate) fn count_buckets(times: &[f64], count: usize, bucket: impl Fn(f64) -> usize) -> Vec<u32> {
+    let mut counts = vec![0u32; count];
+    for &t in times {
+        counts[bucket(t)] += 1;
+    }
+    counts
+}
+
+/// Combine `values`, one per time in `times`, with `op` in each of `count` buckets, with `bucket` giving
+/// the bucket of a time. Empty buckets are 0 for `"sum"` and `NaN` otherwise.
+pub(crate) fn aggregate_buckets(
+    times: &[f64],
+    values: &[f64],
+    op: Aggregation,
+    count: usize,
+    bucket: impl Fn(f64) -> usize,
+) -> Vec<f64> {
+    let mut acc = vec![f64::NAN; count];
+    let mut counts = vec![0u32; count];
+    for (&t, &v) in times.iter().zip(values) {
+        let b = bucket(t);
+        acc[b] = match (op, counts[b]) {
+            (_, 0) => v,
+            (Aggregation::Sum | Aggregation::Avg, _) => acc[b] + v,
+            (Aggregation::Min, _) => acc[b].min(v),
+            (_, _) => acc[b].max(v),
+        };
+        counts[b] += 1;
+    }
+    for (a, &n) in acc.iter_mut().zip(&counts) {
+        match op {
+            Aggregation::Sum if n == 0 => *a = 0.0,
+            Aggregation::Avg if n > 0 => *a /= n as f64,
+            _ => {}
+        }
+    }
+    acc
+}
+
+/// Index of the bucket holding `t`, relative to the first bucket.
+fn bucket_of(t: f64, bucket_ms: f64, origin: f64, first: f64) -> usize {
+    (((t - origin) / bucket_ms).floor() - first) as usize
+}
@@ -0,0 +1,105 @@
+use js_sys::{Atomics, BigInt64Array, SharedArrayBuffer};
+use wasm_bindgen::prelude::*;
+
+use crate::logger::{log, LogKind};
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Slots of the shared buffer: the correction to the local clock in milliseconds, and how many times it
+/// has been written.
+const OFFSET: u32 = 0;
+const VERSION: u32 = 1;
+const BYTES: u32 = 16;
+
+/// A clock correction kept in a `SharedArrayBuffer`, so the main thread and every worker agree on the
+/// corrected time, e.g. after syncing with a server, without passing messages. One "time authority"
+/// creates the clock and writes the correction, and other threads read it from the shared buffer. Only
+/// the difference to the local clock is stored, which all threads of a page share, so the authority
+/// writes only when the correction changes. The page must be cross-origin isolated to use
+/// `SharedArrayBuffer`.
+#[wasm_bindgen]
+pub struct SharedClock {
+    cells: BigInt64Array,
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        SharedClock::new()
+    }
+}
+
+impl SharedClock {
+    fn load(&self, slot: u32) -> i64 {
+        // Adding 0 reads atomically. `Atomics::load_bigint` passes the index as a `BigInt`, which hosts reject.
+        Atomics::add_bigint(&self.cells, slot, 0).unwrap_or(0)
+    }
+}
+
+#[wasm_bindgen]
+impl SharedClock {
+    /// Creates a new `SharedClock` with its own shared buffer and no correction, for the time authority.
+    /// # Examples
+    /// ```
+    /// const clock = new SharedClock();
+    /// worker.postMessage({ clock: clock.buffer });
+    /// clock.setTime(serverTimeMs + roundTripMs / 2);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SharedClock {
+        SharedClock { cells: BigInt64Array::new(&SharedArrayBuffer::new(BYTES)) }
+    }
+
+    /// Creates a `SharedClock` reading the buffer of another thread's clock.
+    /// # Examples
+    /// ```
+    /// onmessage = e => {
+    ///     const clock = SharedClock.fromBuffer(e.data.clock);
+    ///     const now = clock.now();
+    /// };
+    /// ```
+    #[wasm_bindgen(js_name = "fromBuffer")]
+    pub fn from_buffer(buffer: &SharedArrayBuffer) -> Result<SharedClock, ValidationError> {
+        if buffer.byte_length() < BYTES {
+            return Err(ValidationError::new(format!("a shared clock buffer needs at least {BYTES} bytes")));
+        }
+        Ok(SharedClock { cells: BigInt64Array::new(buffer) })
+    }
+
+    /// Get the shared buffer, to post to workers.
+    #[wasm_bindgen(getter)]
+    pub fn buffer(&self) -> SharedArrayBuffer {
+        self.cells.buffer().unchecked_into()
+    }
+
+    /// Set the correction so that the current time reads `epochMs`, the corrected epoch in milliseconds.
+    #[wasm_bindgen(js_name = "setTime")]
+    pub fn set_time(&self, epoch_ms: f64) {
+        self.set_offset(epoch_ms - crate::now_ms() as f64);
+    }
+
+    /// Set the correction in milliseconds that is added to the local clock.
+    #[wasm_bindgen(js_name = "setOffset")]
+    pub fn set_offset(&self, ms: f64) {
+        log(LogKind::Sync, || format!("shared clock offset set to {} ms", ms.round()));
+        let _ = Atomics::store_bigint(&self.cells, OFFSET, ms.round() as i64);
+        let _ = Atomics::add_bigint(&self.cells, VERSION, 1);
+    }
+
+    /// Get the correction in milliseconds that is added to the local clock.
+    #[wasm_bindgen(getter)]
+    pub fn offset(&self) -> f64 {
+        self.load(OFFSET) as f64
+    }
+
+    /// Get how many times the correction has been set, which is 0 until the authority first syncs.
+    /// Readers can compare it with an earlier value to notice a new sync.
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> f64 {
+        self.load(VERSION) as f64
+    }
+
+    /// Get the current corrected time.
+    pub fn now(&self) -> Unitime {
+        Unitime::from_epoch_ms(crate::now_ms().saturating_add(self.load(OFFSET)))
+    }
+}
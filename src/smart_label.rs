@@ -0,0 +1,69 @@
+use wasm_bindgen::prelude::*;
+
+use crate::calendar_days::zoned_midnight;
+use crate::civil::MS_PER_MIN;
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::format::render;
+use crate::locale::Locale;
+use crate::tz::zoned_wall;
+use crate::validation::ValidationError;
+use crate::week::WeekRule;
+use crate::Unitime;
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Label the stored time the way message lists do, on the wall clock of the IANA zone `zone`, or of
+    /// the local zone if omitted, compared with `now` in epoch milliseconds, or the current time if
+    /// omitted: the short time for today, such as "14:32" or "2:32 PM", then "Yesterday", then the
+    /// weekday name for the rest of the past week, and the short date for anything older or in a later
+    /// day. Names and patterns are those of `locale`, English by default.
+    /// # Examples
+    /// ```
+    /// message.sentAt.smartLabel("de", "Europe/Berlin"); // "14:32", "Gestern", "Montag" or "03.06.24"
+    /// ```
+    #[wasm_bindgen(js_name = "smartLabel")]
+    pub fn smart_label(
+        &self,
+        locale: Option<String>,
+        zone: Option<String>,
+        now: Option<f64>,
+    ) -> Result<String, ValidationError> {
+        let locale = Locale::get(locale.as_deref());
+        let ms = self.epoch_ms();
+        let wall = zoned_wall(ms, zone.as_deref())?;
+        let pattern = match self.days_from_now(zone, now)? {
+            0 => locale.time_styles[0],
+            -1 => return Ok(locale.yesterday.to_string()),
+            -6..=-2 => "%A",
+            _ => locale.date_styles[0],
+        };
+        render(pattern, wall, (wall - ms) / MS_PER_MIN, locale, WeekRule::Iso)
+    }
+
+    /// Get how long until `smartLabel` for `now` in epoch milliseconds, or the current time if omitted,
+    /// would change on the wall clock of the IANA zone `zone`, or of the local zone if omitted: at the
+    /// coming midnight while the stored time is within the past week, or at the start of its day if it
+    /// is a later day. Gives `undefined` for older times, whose short date never changes.
+    /// # Examples
+    /// ```
+    /// const wait = t.nextSmartLabelChangeIn();
+    /// if (wait) setTimeout(rerender, wait.millis);
+    /// ```
+    #[wasm_bindgen(js_name = "nextSmartLabelChangeIn")]
+    pub fn next_smart_label_change_in(
+        &self,
+        zone: Option<String>,
+        now: Option<f64>,
+    ) -> Result<Option<UniDuration>, ValidationError> {
+        let now = Unitime::from_epoch_ms(now.map_or_else(crate::now_ms, |ms| ms as i64));
+        let zone = zone.as_deref();
+        let (today, day) = (now.zoned_days(zone)?, self.zoned_days(zone)?);
+        let change = match day - today {
+            1.. => zoned_midnight(day, zone)?,
+            -6..=0 => zoned_midnight(today + 1, zone)?,
+            _ => return Ok(None),
+        };
+        let ms = change.epoch_ms() - now.epoch_ms();
+        Ok(Some(UniDuration::from_nanos(ms.saturating_mul(NANOS_PER_MILLI))))
+    }
+}
@@ -0,0 +1,102 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, DateTime};
+use crate::parse::Cursor;
+use crate::validation::{char_offset, ValidationError};
+use crate::Unitime;
+
+/// Read a `YYYY-MM-DD` date with an optional `[ T]HH:MM[:SS[.fff]]` time. If what follows the date
+/// does not form a valid time, only the date is consumed.
+pub(crate) fn parse_date_time(c: &mut Cursor) -> Option<DateTime> {
+    let year = c.fixed(4)? as i64;
+    if !c.eat(b'-') {
+        return None;
+    }
+    let month = c.fixed(2)?;
+    if !c.eat(b'-') {
+        return None;
+    }
+    let day = c.fixed(2)?;
+    let mut dt = DateTime { year, month, day, hour: 0, minute: 0, second: 0, milli: 0 };
+
+    let mut timed = c.clone();
+    if parse_time(&mut timed, &mut dt).is_some() {
+        *c = timed;
+    } else {
+        dt = DateTime { year, month, day, hour: 0, minute: 0, second: 0, milli: 0 };
+    }
+    Some(dt)
+}
+
+fn parse_time(c: &mut Cursor, dt: &mut DateTime) -> Option<()> {
+    if !c.eat(b'T') {
+        if !matches!(c.peek(), Some(b' ')) {
+            return None;
+        }
+        c.skip_spaces();
+    }
+    dt.hour = c.digits(1, 2)?.0;
+    if !c.eat(b':') {
+        return None;
+    }
+    dt.minute = c.fixed(2)?;
+    if c.eat(b':') {
+        dt.second = c.fixed(2)?;
+        if c.eat(b'.') {
+            dt.milli = c.fraction_ms()?;
+        }
+    }
+    Some(())
+}
+
+fn parse_sql(s: &str) -> Result<DateTime, ValidationError> {
+    let trimmed = s.trim();
+    let lead = s.len() - s.trim_start().len();
+    let mut c = Cursor::new(trimmed);
+    let syntax = |c: &Cursor| {
+        ValidationError::syntax(format!("invalid SQL datetime: {s}"), char_offset(s, lead + c.pos()))
+    };
+    let dt = parse_date_time(&mut c).ok_or_else(|| syntax(&c))?;
+    if !c.is_end() {
+        return Err(syntax(&c));
+    }
+    dt.validate()?;
+    Ok(dt)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the stored time as an SQL datetime literal in `YYYY-MM-DD HH:MM:SS[.fff]` format.
+    /// The milliseconds are only included if they are not zero. The time is rendered in UTC unless `utc` is `false`.
+    /// # Examples
+    /// ```
+    /// const t = new Unitime();
+    /// const utc = t.toSQLString();
+    /// const local = t.toSQLString(false);
+    /// ```
+    #[wasm_bindgen(js_name = "toSQLString")]
+    pub fn to_sql_string(&self, utc: Option<bool>) -> String {
+        let dt = DateTime::from_epoch_ms(self.wall_ms(utc.unwrap_or(true)));
+        let mut result = format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second
+        );
+        if dt.milli != 0 {
+            result.push_str(&format!(".{:03}", dt.milli));
+        }
+        result
+    }
+
+    /// Creates a new `Unitime` from an SQL datetime literal without a time zone, as produced by MySQL and Postgres.
+    /// Accepts `YYYY-MM-DD`, `YYYY-MM-DD HH:MM`, `YYYY-MM-DD HH:MM:SS` and a fractional second of up to 9 digits,
+    /// with either a space or `T` between date and time. The value is read as UTC if `utc` is `true`, else as local time.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromSQLString("2023-08-31 08:32:48.154", true);
+    /// ```
+    #[wasm_bindgen(js_name = "fromSQLString")]
+    pub fn from_sql_string(s: &str, utc: bool) -> Result<Unitime, ValidationError> {
+        let dt = parse_sql(s)?;
+        Ok(Unitime::from_epoch_ms(civil::wall_to_instant(dt.to_epoch_ms(), utc)))
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Durations at or below this many milliseconds are counted in the zero bucket of the digest.
+const MIN_TRACKED_MS: f64 = 1e-6;
+
+/// Accumulator for latency-style durations, keeping exact min/max/mean/stddev and a compact
+/// logarithmic digest (as in DDSketch) from which percentiles are estimated within a fixed relative error.
+#[wasm_bindgen]
+pub struct DurationStats {
+    gamma_ln: f64,
+    buckets: BTreeMap<i32, u64>,
+    zeros: u64,
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl DurationStats {
+    fn bucket_value(&self, index: i32) -> f64 {
+        // Midpoint of the bucket (gamma^(i-1), gamma^i] in relative terms.
+        let gamma = self.gamma_ln.exp();
+        2.0 * (self.gamma_ln * index as f64).exp() / (gamma + 1.0)
+    }
+
+    fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 || q.is_nan() {
+            return None;
+        }
+        let q = q.clamp(0.0, 1.0);
+        if q == 0.0 {
+            return Some(self.min);
+        }
+        if q == 1.0 {
+            return Some(self.max);
+        }
+        let rank = (q * (self.count - 1) as f64) as u64;
+        if rank < self.zeros {
+            return Some(self.min.max(0.0));
+        }
+        let mut seen = self.zeros;
+        for (&index, &n) in &self.buckets {
+            seen += n;
+            if seen > rank {
+                return Some(self.bucket_value(index).clamp(self.min, self.max));
+            }
+        }
+        Some(self.max)
+    }
+}
+
+#[wasm_bindgen]
+impl DurationStats {
+    /// Creates a new, empty `DurationStats` whose percentiles are accurate to within `relative_accuracy`
+    /// of the true value, 1% by default.
+    /// # Examples
+    /// ```
+    /// const latency = new DurationStats();
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(relative_accuracy: Option<f64>) -> Result<DurationStats, ValidationError> {
+        let a = relative_accuracy.unwrap_or(0.01);
+        if a.is_nan() || a <= 0.0 || a >= 1.0 {
+            return Err(ValidationError::new("relative accuracy must be in (0, 1)"));
+        }
+        Ok(DurationStats {
+            gamma_ln: ((1.0 + a) / (1.0 - a)).ln(),
+            buckets: BTreeMap::new(),
+            zeros: 0,
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        })
+    }
+
+    /// Record one duration in milliseconds. Negative durations are counted as zero and `NaN` is ignored.
+    /// # Examples
+    /// ```
+    /// const start = performance.now();
+    /// await fetch(url);
+    /// latency.record(performance.now() - start);
+    /// ```
+    pub fn record(&mut self, duration_ms: f64) {
+        if duration_ms.is_nan() {
+            return;
+        }
+        let d = duration_ms.max(0.0);
+        self.count += 1;
+        let delta = d - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (d - self.mean);
+        self.min = self.min.min(d);
+        self.max = self.max.max(d);
+        if d <= MIN_TRACKED_MS {
+            self.zeros += 1;
+        } else {
+            let index = (d.ln() / self.gamma_ln).ceil() as i32;
+            *self.buckets.entry(index).or_insert(0) += 1;
+        }
+    }
+
+    /// Add all durations recorded by `other`, which must use the same relative accuracy.
+    pub fn merge(&mut self, other: &DurationStats) -> Result<(), ValidationError> {
+        if (self.gamma_ln - other.gamma_ln).abs() > f64::EPSILON {
+            return Err(ValidationError::new("cannot merge stats with different accuracy"));
+        }
+        if other.count == 0 {
+            return Ok(());
+        }
+        let total = (self.count + other.count) as f64;
+        let delta = other.mean - self.mean;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * other.count as f64 / total;
+        self.mean += delta * other.count as f64 / total;
+        self.count += other.count;
+        self.zeros += other.zeros;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        for (&index, &n) in &other.buckets {
+            *self.buckets.entry(index).or_insert(0) += n;
+        }
+        Ok(())
+    }
+
+    /// Get the number of recorded durations.
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> f64 {
+        self.count as f64
+    }
+
+    /// Get the shortest recorded duration, or `undefined` if none was recorded.
+    #[wasm_bindgen(getter)]
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Get the longest recorded duration, or `undefined` if none was recorded.
+    #[wasm_bindgen(getter)]
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Get the mean of the recorded durations, or `undefined` if none was recorded.
+    #[wasm_bindgen(getter)]
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Get the sum of the recorded durations.
+    #[wasm_bindgen(getter)]
+    pub fn sum(&self) -> f64 {
+        self.mean * self.count as f64
+    }
+
+    /// Get the population standard deviation of the recorded durations, or `undefined` if none was recorded.
+    #[wasm_bindgen(getter)]
+    pub fn stddev(&self) -> Option<f64> {
+        (self.count > 0).then(|| (self.m2 / self.count as f64).sqrt())
+    }
+
+    /// Estimate the `p`th percentile (0 to 100) of the recorded durations, or `undefined` if none was recorded.
+    /// # Examples
+    /// ```
+    /// const p99 = latency.percentile(99);
+    /// ```
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        self.quantile(p / 100.0)
+    }
+
+    /// Estimate several percentiles at once, one for each of `ps` in the same order, with `NaN` where
+    /// `percentile` gives `undefined`.
+    /// # Examples
+    /// ```
+    /// const [p50, p90, p99] = latency.percentiles(new Float64Array([50, 90, 99]));
+    /// ```
+    pub fn percentiles(&self, ps: Vec<f64>) -> Vec<f64> {
+        ps.iter().map(|&p| self.percentile(p).unwrap_or(f64::NAN)).collect()
+    }
+
+    /// Forget all recorded durations.
+    pub fn reset(&mut self) {
+        self.buckets.clear();
+        self.zeros = 0;
+        self.count = 0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.min = f64::INFINITY;
+        self.max = f64::NEG_INFINITY;
+    }
+}
@@ -0,0 +1,101 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::UniDuration;
+
+/// Stopwatch that keeps the wall-clock times it was started and stopped rather than counting timer
+/// ticks, so it stays right however rarely the page's timers run: background tabs throttle them to
+/// once a minute and suspended pages stop them altogether. Every read works out the elapsed time from
+/// those timestamps afresh. Every method taking `now` uses the current time in epoch milliseconds if
+/// it is omitted.
+/// # Examples
+/// ```
+/// const sw = new Stopwatch();
+/// sw.start();
+/// setInterval(() => display.textContent = sw.elapsed().toClockString(), 1000);
+/// ```
+#[wasm_bindgen]
+pub struct Stopwatch {
+    /// When the current run started, while running.
+    started_at: Option<f64>,
+    /// Time counted by runs that have been stopped, in milliseconds.
+    banked_ms: f64,
+    /// Elapsed time as of the last `tick`.
+    ticked_ms: f64,
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Stopwatch::new()
+    }
+}
+
+fn now_or(now: Option<f64>) -> f64 {
+    now.unwrap_or_else(|| crate::now_ms() as f64)
+}
+
+impl Stopwatch {
+    fn elapsed_ms(&self, now: f64) -> f64 {
+        // A clock set back while running must not take time off.
+        self.banked_ms + self.started_at.map_or(0.0, |start| (now - start).max(0.0))
+    }
+}
+
+#[wasm_bindgen]
+impl Stopwatch {
+    /// Creates a new `Stopwatch` at zero, stopped.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Stopwatch {
+        Stopwatch { started_at: None, banked_ms: 0.0, ticked_ms: 0.0 }
+    }
+
+    /// Start or resume counting at `now`. Does nothing if already running.
+    pub fn start(&mut self, now: Option<f64>) {
+        if self.started_at.is_none() {
+            self.started_at = Some(now_or(now));
+        }
+    }
+
+    /// Stop counting at `now`, keeping the time counted so far. Does nothing if already stopped.
+    pub fn stop(&mut self, now: Option<f64>) {
+        if self.started_at.is_some() {
+            self.banked_ms = self.elapsed_ms(now_or(now));
+            self.started_at = None;
+        }
+    }
+
+    /// Set the time counted back to zero, leaving the stopwatch running or stopped. A running stopwatch
+    /// counts again from `now`.
+    pub fn reset(&mut self, now: Option<f64>) {
+        self.banked_ms = 0.0;
+        self.ticked_ms = 0.0;
+        if self.started_at.is_some() {
+            self.started_at = Some(now_or(now));
+        }
+    }
+
+    /// Check whether the stopwatch is counting.
+    #[wasm_bindgen(getter)]
+    pub fn running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    /// Get the time counted up to `now`.
+    pub fn elapsed(&self, now: Option<f64>) -> UniDuration {
+        UniDuration::new(self.elapsed_ms(now_or(now)))
+    }
+
+    /// Get the time counted since the previous `tick`, or since zero for the first, and move the tick
+    /// mark up to `now`. A handler called every second normally gets about a second; after its timer
+    /// was throttled or suspended it gets the whole gap, so anything it advances by these amounts,
+    /// such as an animation or a billing meter, keeps up with `elapsed`.
+    /// # Examples
+    /// ```
+    /// setInterval(() => meter.add(sw.tick().seconds), 1000);
+    /// ```
+    pub fn tick(&mut self, now: Option<f64>) -> UniDuration {
+        let elapsed = self.elapsed_ms(now_or(now));
+        let delta = elapsed - self.ticked_ms;
+        self.ticked_ms = elapsed;
+        UniDuration::new(delta)
+    }
+}
@@ -0,0 +1,152 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{MS_PER_DAY, MS_PER_MIN};
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Zenith angle of the sun's centre at sunrise and sunset, allowing for refraction and the solar disc.
+const SUNRISE_ZENITH: f64 = 90.833;
+/// Zenith angle of the sun's centre at the start and end of civil twilight.
+const CIVIL_ZENITH: f64 = 96.0;
+
+/// Julian day number of the Unix epoch.
+const JD_UNIX_EPOCH: f64 = 2_440_587.5;
+
+/// Solar position terms from the NOAA solar calculator for a moment given as Julian centuries since J2000.
+pub(crate) struct SolarPosition {
+    /// Apparent ecliptic longitude of the sun in degrees, from 0 to 360.
+    #[cfg_attr(not(feature = "calendar"), allow(dead_code))]
+    pub longitude: f64,
+    /// Declination of the sun in radians.
+    pub declination: f64,
+    /// Equation of time in minutes.
+    pub equation_of_time: f64,
+}
+
+impl SolarPosition {
+    pub fn at(t: f64) -> SolarPosition {
+        let l0 = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0).to_radians();
+        let m = (357.52911 + t * (35999.05029 - 0.0001537 * t)).to_radians();
+        let e = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+        let center = m.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+            + (2.0 * m).sin() * (0.019993 - 0.000101 * t)
+            + (3.0 * m).sin() * 0.000289;
+        let omega = (125.04 - 1934.136 * t).to_radians();
+        let apparent_long = (l0.to_degrees() + center - 0.00569 - 0.00478 * omega.sin()).to_radians();
+        let mean_obliquity = 23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+        let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+        let declination = (obliquity.sin() * apparent_long.sin()).asin();
+        let y = (obliquity / 2.0).tan().powi(2);
+        let equation_of_time = 4.0
+            * (y * (2.0 * l0).sin() - 2.0 * e * m.sin() + 4.0 * e * y * m.sin() * (2.0 * l0).cos()
+                - 0.5 * y * y * (4.0 * l0).sin()
+                - 1.25 * e * e * (2.0 * m).sin())
+            .to_degrees();
+        SolarPosition {
+            longitude: apparent_long.to_degrees().rem_euclid(360.0),
+            declination,
+            equation_of_time,
+        }
+    }
+}
+
+/// Julian centuries since J2000 for epoch milliseconds.
+pub(crate) fn julian_century(ms: f64) -> f64 {
+    (ms / MS_PER_DAY as f64 + JD_UNIX_EPOCH - 2_451_545.0) / 36525.0
+}
+
+/// Sunrise, sunset and twilight instants for one day at one place, in epoch milliseconds.
+#[wasm_bindgen]
+pub struct SunTimes {
+    dawn: Option<f64>,
+    sunrise: Option<f64>,
+    solar_noon: f64,
+    sunset: Option<f64>,
+    dusk: Option<f64>,
+}
+
+#[wasm_bindgen]
+impl SunTimes {
+    /// Get the start of civil twilight, or `undefined` if the sun stays above or below that depth all day.
+    #[wasm_bindgen(getter)]
+    pub fn dawn(&self) -> Option<f64> {
+        self.dawn
+    }
+
+    /// Get the sunrise, or `undefined` during polar day or night.
+    #[wasm_bindgen(getter)]
+    pub fn sunrise(&self) -> Option<f64> {
+        self.sunrise
+    }
+
+    /// Get the moment the sun is highest.
+    #[wasm_bindgen(getter, js_name = "solarNoon")]
+    pub fn solar_noon(&self) -> f64 {
+        self.solar_noon
+    }
+
+    /// Get the sunset, or `undefined` during polar day or night.
+    #[wasm_bindgen(getter)]
+    pub fn sunset(&self) -> Option<f64> {
+        self.sunset
+    }
+
+    /// Get the end of civil twilight, or `undefined` if the sun stays above or below that depth all day.
+    #[wasm_bindgen(getter)]
+    pub fn dusk(&self) -> Option<f64> {
+        self.dusk
+    }
+}
+
+/// Find when the sun crosses `zenith` on the date starting at `date_ms` (UTC midnight) with solar noon at
+/// `noon_ms`, refining the solar position at the event itself. `rising` picks the morning crossing.
+/// Returns `None` if the sun never crosses that zenith on the day.
+fn crossing(date_ms: f64, noon_ms: f64, lat: f64, lon: f64, zenith: f64, rising: bool) -> Option<f64> {
+    let mut event = noon_ms;
+    for _ in 0..3 {
+        let pos = SolarPosition::at(julian_century(event));
+        let lat = lat.to_radians();
+        let cos_ha = zenith.to_radians().cos() / (lat.cos() * pos.declination.cos())
+            - lat.tan() * pos.declination.tan();
+        if !(-1.0..=1.0).contains(&cos_ha) {
+            return None;
+        }
+        let ha_minutes = 4.0 * cos_ha.acos().to_degrees();
+        let noon_minutes = 720.0 - 4.0 * lon - pos.equation_of_time;
+        let minutes = if rising { noon_minutes - ha_minutes } else { noon_minutes + ha_minutes };
+        event = date_ms + minutes * MS_PER_MIN as f64;
+    }
+    Some(event.round())
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get sunrise, sunset, solar noon and civil twilight for the stored date at latitude `lat` and longitude `lon`
+    /// in degrees (north and east positive), using the NOAA solar calculator algorithm.
+    /// The date is taken in local solar time at that longitude, so the result is the day the stored time falls on there.
+    /// # Examples
+    /// ```
+    /// const sun = new Unitime().sunTimes(52.37, 4.90);
+    /// const sunrise = new Date(sun.sunrise);
+    /// ```
+    #[wasm_bindgen(js_name = "sunTimes")]
+    pub fn sun_times(&self, lat: f64, lon: f64) -> Result<SunTimes, ValidationError> {
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(ValidationError::new("latitude or longitude out of range"));
+        }
+        let day = MS_PER_DAY as f64;
+        let date = ((self.epoch_ms() as f64 + lon / 360.0 * day) / day).floor() * day;
+        let mut noon = date + day / 2.0 - lon / 360.0 * day;
+        for _ in 0..2 {
+            let eot = SolarPosition::at(julian_century(noon)).equation_of_time;
+            noon = date + (720.0 - 4.0 * lon - eot) * MS_PER_MIN as f64;
+        }
+        Ok(SunTimes {
+            dawn: crossing(date, noon, lat, lon, CIVIL_ZENITH, true),
+            sunrise: crossing(date, noon, lat, lon, SUNRISE_ZENITH, true),
+            solar_noon: noon.round(),
+            sunset: crossing(date, noon, lat, lon, SUNRISE_ZENITH, false),
+            dusk: crossing(date, noon, lat, lon, CIVIL_ZENITH, false),
+        })
+    }
+}
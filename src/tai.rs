@@ -0,0 +1,116 @@
+//! International Atomic Time (TAI) from the table of leap seconds inserted into UTC.
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_from_civil, MS_PER_DAY, MS_PER_SEC};
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::Unitime;
+
+/// The first UTC month of each value of TAI − UTC in seconds since UTC took whole leap seconds in 1972.
+/// Every step is a leap second inserted at the end of the month before. No leap second has been
+/// announced after the one at the end of 2016.
+const LEAP_SECONDS: [(i64, u32, i64); 28] = [
+    (1972, 1, 10),
+    (1972, 7, 11),
+    (1973, 1, 12),
+    (1974, 1, 13),
+    (1975, 1, 14),
+    (1976, 1, 15),
+    (1977, 1, 16),
+    (1978, 1, 17),
+    (1979, 1, 18),
+    (1980, 1, 19),
+    (1981, 7, 20),
+    (1982, 7, 21),
+    (1983, 7, 22),
+    (1985, 7, 23),
+    (1988, 1, 24),
+    (1990, 1, 25),
+    (1991, 1, 26),
+    (1992, 7, 27),
+    (1993, 7, 28),
+    (1994, 7, 29),
+    (1996, 1, 30),
+    (1997, 7, 31),
+    (1999, 1, 32),
+    (2006, 1, 33),
+    (2009, 1, 34),
+    (2012, 7, 35),
+    (2015, 7, 36),
+    (2017, 1, 37),
+];
+
+/// Each value of TAI − UTC in seconds with the UTC epoch milliseconds it takes effect at, latest first.
+fn steps() -> impl Iterator<Item = (i64, i64)> {
+    LEAP_SECONDS.iter().rev().map(|&(year, month, offset)| (days_from_civil(year, month, 1) * MS_PER_DAY, offset))
+}
+
+/// Get TAI − UTC in seconds at the UTC instant `utc_ms`. Before 1972 UTC was kept close to UT with
+/// fractional adjustments instead, and the initial 10 seconds are used.
+pub(crate) fn tai_minus_utc(utc_ms: i64) -> i64 {
+    steps().find(|&(start, _)| utc_ms >= start).map_or(10, |(_, offset)| offset)
+}
+
+/// Convert TAI milliseconds to UTC. TAI instants inside a leap second, which UTC writes as 23:59:60,
+/// fall onto the first second after it.
+pub(crate) fn tai_to_utc(tai_ms: i64) -> i64 {
+    for (start, offset) in steps() {
+        let utc = tai_ms.saturating_sub(offset * MS_PER_SEC);
+        if utc >= start {
+            return utc;
+        }
+        // The leap second just before `start` belongs to the previous offset but reads as `start`. The
+        // first entry only fixed the offset in place and has none.
+        if offset > LEAP_SECONDS[0].2 && utc.saturating_add(MS_PER_SEC) >= start {
+            return start;
+        }
+    }
+    tai_ms.saturating_sub(10 * MS_PER_SEC)
+}
+
+impl Unitime {
+    /// The stored time on the TAI scale, in nanoseconds since 1970-01-01 00:00:00 TAI.
+    fn tai_nanos(&self) -> i128 {
+        self.nanos + (tai_minus_utc(self.epoch_ms()) * MS_PER_SEC) as i128 * NANOS_PER_MILLI as i128
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the stored time on the TAI scale, in milliseconds since 1970-01-01 00:00:00 TAI. TAI runs
+    /// ahead of UTC by the leap seconds, 37 seconds since 2017.
+    /// # Examples
+    /// ```
+    /// const tai = new Unitime().toTAI();
+    /// ```
+    #[wasm_bindgen(js_name = "toTAI")]
+    pub fn to_tai(&self) -> f64 {
+        let ms = self.epoch_ms();
+        ms.saturating_add(tai_minus_utc(ms) * MS_PER_SEC) as f64
+    }
+
+    /// Creates a new `Unitime` from milliseconds since 1970-01-01 00:00:00 TAI, as returned by `toTAI`.
+    /// Times inside a leap second give the first second after it, since UTC epoch time cannot hold them.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromTAI(tai);
+    /// ```
+    #[wasm_bindgen(js_name = "fromTAI")]
+    pub fn from_tai(tai_ms: f64) -> Unitime {
+        Unitime::from_epoch_ms(tai_to_utc(tai_ms.floor() as i64))
+    }
+
+    /// Get the time from `other` to the stored time in SI seconds, counting the leap seconds in between,
+    /// which epoch time differences leave out.
+    /// # Examples
+    /// ```
+    /// const before = Unitime.fromSQLString("2016-12-31 23:59:59", true);
+    /// const after = Unitime.fromSQLString("2017-01-01 00:00:00", true);
+    /// after.diffTAI(before).seconds; // 2
+    /// ```
+    #[wasm_bindgen(js_name = "diffTAI")]
+    pub fn diff_tai(&self, other: &Unitime) -> UniDuration {
+        let nanos = self.tai_nanos() - other.tai_nanos();
+        UniDuration::from_nanos(nanos.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+}
@@ -0,0 +1,267 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::UniDuration;
+use crate::parse::Cursor;
+use crate::validation::{char_offset, ValidationError};
+
+/// Frame rate as an exact fraction, with the nominal whole-number rate used for counting frames in a timecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FrameRate {
+    num: i64,
+    den: i64,
+    nominal: i64,
+    drop: i64,
+}
+
+impl FrameRate {
+    /// Map a JS frame rate such as `29.97` or `25` to an exact rate. NTSC rates are `n * 1000 / 1001`.
+    fn from_fps(fps: f64, drop_frame: bool) -> Result<FrameRate, ValidationError> {
+        if !fps.is_finite() || fps <= 0.0 || fps > 1000.0 {
+            return Err(ValidationError::new("frame rate must be between 0 and 1000"));
+        }
+        let nominal = fps.round().max(1.0) as i64;
+        let ntsc = (fps - nominal as f64 * 1000.0 / 1001.0).abs() < 0.005;
+        let (num, den) = if ntsc {
+            (nominal * 1000, 1001)
+        } else if (fps - nominal as f64).abs() < 1e-9 {
+            (nominal, 1)
+        } else {
+            return Err(ValidationError::new("frame rate must be a whole number or an NTSC rate such as 29.97"));
+        };
+        let drop = match (drop_frame, ntsc, nominal) {
+            (false, _, _) => 0,
+            (true, true, 30) => 2,
+            (true, true, 60) => 4,
+            _ => return Err(ValidationError::new("drop-frame timecode is only defined for 29.97 and 59.94 fps")),
+        };
+        Ok(FrameRate { num, den, nominal, drop })
+    }
+
+    /// The largest frame count whose running time fits in a `UniDuration`.
+    fn max_frames(self) -> i64 {
+        (i64::MAX as i128 * self.num as i128 / (self.den as i128 * 1_000_000_000)) as i64
+    }
+
+    /// A timecode at frame count `frames`, or a range error if that is past [`FrameRate::max_frames`].
+    fn timecode(self, frames: i64) -> Result<Timecode, ValidationError> {
+        if !(0..=self.max_frames()).contains(&frames) {
+            return Err(ValidationError::range("frames", frames, 0, self.max_frames()));
+        }
+        Ok(Timecode { frames, rate: self })
+    }
+
+    /// Frame count of the given timecode fields, skipping the dropped frame numbers.
+    fn to_frames(self, h: i64, m: i64, s: i64, f: i64) -> i64 {
+        let minutes = 60 * h + m;
+        (3600 * h + 60 * m + s) * self.nominal + f - self.drop * (minutes - minutes / 10)
+    }
+
+    /// Timecode fields of a frame count, inserting the dropped frame numbers.
+    fn to_fields(self, frames: i64) -> (i64, i64, i64, i64) {
+        let mut frames = frames;
+        if self.drop > 0 {
+            let per_10_min = self.nominal * 600 - self.drop * 9;
+            let per_min = self.nominal * 60 - self.drop;
+            let tens = frames / per_10_min;
+            let rem = frames % per_10_min;
+            frames += self.drop * 9 * tens;
+            if rem > self.drop {
+                frames += self.drop * ((rem - self.drop) / per_min);
+            }
+        }
+        let f = frames % self.nominal;
+        let total_s = frames / self.nominal;
+        (total_s / 3600, total_s / 60 % 60, total_s % 60, f)
+    }
+}
+
+/// SMPTE timecode (`HH:MM:SS:FF`, or `HH:MM:SS;FF` for drop-frame) at a given frame rate.
+/// Timecodes are exact frame counts, so conversions between them never accumulate rounding errors.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timecode {
+    frames: i64,
+    rate: FrameRate,
+}
+
+#[wasm_bindgen]
+impl Timecode {
+    /// Creates a new `Timecode` from its fields at `fps` frames per second.
+    /// # Examples
+    /// ```
+    /// const tc = new Timecode(1, 0, 0, 0, 29.97, true);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        hours: u32,
+        minutes: u32,
+        seconds: u32,
+        frames: u32,
+        fps: f64,
+        drop_frame: Option<bool>,
+    ) -> Result<Timecode, ValidationError> {
+        let rate = FrameRate::from_fps(fps, drop_frame.unwrap_or(false))?;
+        let (h, m, s, f) = (hours as i64, minutes as i64, seconds as i64, frames as i64);
+        for (field, value, max) in [("minutes", m, 59), ("seconds", s, 59), ("frames", f, rate.nominal - 1)] {
+            if value > max {
+                return Err(ValidationError::range(field, value, 0, max));
+            }
+        }
+        if rate.drop > 0 && s == 0 && m % 10 != 0 && f < rate.drop {
+            return Err(ValidationError::new("frame number is dropped in drop-frame timecode"));
+        }
+        rate.timecode(rate.to_frames(h, m, s, f))
+    }
+
+    /// Creates a new `Timecode` from a frame count at `fps` frames per second.
+    /// # Examples
+    /// ```
+    /// const tc = Timecode.fromFrames(107892, 29.97, true); // 01:00:00;00
+    /// ```
+    #[wasm_bindgen(js_name = "fromFrames")]
+    pub fn from_frames(frames: f64, fps: f64, drop_frame: Option<bool>) -> Result<Timecode, ValidationError> {
+        if !frames.is_finite() || frames < 0.0 || frames.fract() != 0.0 {
+            return Err(ValidationError::new("frame count must be a non-negative integer"));
+        }
+        let rate = FrameRate::from_fps(fps, drop_frame.unwrap_or(false))?;
+        rate.timecode(frames as i64)
+    }
+
+    /// Creates a new `Timecode` for the frame showing at `duration` from the start, at `fps` frames per second.
+    /// # Examples
+    /// ```
+    /// const tc = Timecode.fromDuration(UniDuration.fromSeconds(video.currentTime), 25);
+    /// ```
+    #[wasm_bindgen(js_name = "fromDuration")]
+    pub fn from_duration(
+        duration: &UniDuration,
+        fps: f64,
+        drop_frame: Option<bool>,
+    ) -> Result<Timecode, ValidationError> {
+        if duration.nanos() < 0 {
+            return Err(ValidationError::range("nanoseconds", duration.nanos(), 0, i64::MAX));
+        }
+        let rate = FrameRate::from_fps(fps, drop_frame.unwrap_or(false))?;
+        let frames = duration.nanos() as i128 * rate.num as i128 / (rate.den as i128 * 1_000_000_000);
+        Ok(Timecode { frames: frames as i64, rate })
+    }
+
+    /// Parse a timecode string at `fps` frames per second. A `;` or `.` before the frames marks it as drop-frame.
+    /// # Examples
+    /// ```
+    /// const tc = Timecode.parse("00:10:00;00", 29.97);
+    /// ```
+    pub fn parse(s: &str, fps: f64) -> Result<Timecode, ValidationError> {
+        let lead = s.len() - s.trim_start().len();
+        let mut c = Cursor::new(s.trim());
+        let syntax = |c: &Cursor| {
+            ValidationError::syntax(format!("invalid timecode: {s}"), char_offset(s, lead + c.pos()))
+        };
+        let mut fields = [0u32; 4];
+        let mut drop_frame = false;
+        for (i, field) in fields.iter_mut().enumerate() {
+            if i == 3 && (c.eat(b';') || c.eat(b'.')) {
+                drop_frame = true;
+            } else if i > 0 && !c.eat(b':') {
+                return Err(syntax(&c));
+            }
+            *field = c.digits(2, 3).ok_or_else(|| syntax(&c))?.0;
+        }
+        if !c.is_end() {
+            return Err(syntax(&c));
+        }
+        Timecode::new(fields[0], fields[1], fields[2], fields[3], fps, Some(drop_frame))
+    }
+
+    /// Get the number of frames since `00:00:00:00`.
+    #[wasm_bindgen(getter, js_name = "totalFrames")]
+    pub fn total_frames(&self) -> f64 {
+        self.frames as f64
+    }
+
+    /// Get the exact frame rate in frames per second.
+    #[wasm_bindgen(getter)]
+    pub fn fps(&self) -> f64 {
+        self.rate.num as f64 / self.rate.den as f64
+    }
+
+    /// Whether the timecode uses drop-frame numbering.
+    #[wasm_bindgen(getter, js_name = "dropFrame")]
+    pub fn drop_frame(&self) -> bool {
+        self.rate.drop > 0
+    }
+
+    /// Get the hours field.
+    #[wasm_bindgen(getter)]
+    pub fn hours(&self) -> u32 {
+        self.rate.to_fields(self.frames).0 as u32
+    }
+
+    /// Get the minutes field.
+    #[wasm_bindgen(getter)]
+    pub fn minutes(&self) -> u32 {
+        self.rate.to_fields(self.frames).1 as u32
+    }
+
+    /// Get the seconds field.
+    #[wasm_bindgen(getter)]
+    pub fn seconds(&self) -> u32 {
+        self.rate.to_fields(self.frames).2 as u32
+    }
+
+    /// Get the frames field.
+    #[wasm_bindgen(getter)]
+    pub fn frames(&self) -> u32 {
+        self.rate.to_fields(self.frames).3 as u32
+    }
+
+    /// Get the running time from `00:00:00:00` to the start of this frame.
+    /// # Examples
+    /// ```
+    /// video.currentTime = tc.toDuration().seconds;
+    /// ```
+    #[wasm_bindgen(js_name = "toDuration")]
+    pub fn to_duration(&self) -> UniDuration {
+        let nanos = self.frames as i128 * self.rate.den as i128 * 1_000_000_000 / self.rate.num as i128;
+        UniDuration::from_nanos(nanos.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+
+    /// Get a timecode moved by `frames` frames, which may be negative. This is `checkedAddFrames`, so it
+    /// throws rather than clamping when the result would be before `00:00:00:00`.
+    #[wasm_bindgen(js_name = "addFrames")]
+    pub fn add_frames(&self, frames: i32) -> Result<Timecode, ValidationError> {
+        self.checked_add_frames(frames)
+    }
+
+    /// Get a timecode moved by `frames` frames, which may be negative, throwing if the result would be
+    /// before `00:00:00:00` or past the largest frame count whose running time fits in a `UniDuration`.
+    /// # Examples
+    /// ```
+    /// const previous = tc.checkedAddFrames(-1); // throws at 00:00:00:00
+    /// ```
+    #[wasm_bindgen(js_name = "checkedAddFrames")]
+    pub fn checked_add_frames(&self, frames: i32) -> Result<Timecode, ValidationError> {
+        self.rate.timecode(self.frames.saturating_add(frames as i64))
+    }
+
+    /// Get a timecode moved by `frames` frames, which may be negative, stopping at `00:00:00:00` and at
+    /// the largest frame count.
+    /// # Examples
+    /// ```
+    /// const rewound = tc.saturatingAddFrames(-10 * 25); // at most 10 s back, never before the start
+    /// ```
+    #[wasm_bindgen(js_name = "saturatingAddFrames")]
+    pub fn saturating_add_frames(&self, frames: i32) -> Timecode {
+        let frames = self.frames.saturating_add(frames as i64).clamp(0, self.rate.max_frames());
+        Timecode { frames, rate: self.rate }
+    }
+
+    /// Format as `HH:MM:SS:FF`, or `HH:MM:SS;FF` for drop-frame.
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        let (h, m, s, f) = self.rate.to_fields(self.frames);
+        let sep = if self.rate.drop > 0 { ';' } else { ':' };
+        let width = if self.rate.nominal > 100 { 3 } else { 2 };
+        format!("{h:02}:{m:02}:{s:02}{sep}{f:0width$}")
+    }
+}
@@ -0,0 +1,16 @@
+use wasm_bindgen::prelude::*;
+
+/// Longest single `setTimeout` that is armed. Browsers overflow delays past 2^31 − 1 ms and pause
+/// timers while a laptop sleeps, so long waits are split and the wall clock is checked again after each.
+const MAX_DELAY_MS: i64 = 60_000;
+
+/// Call `callback` after `delay_ms`, or after `MAX_DELAY_MS` if that is sooner, returning the timer
+/// handle if the host has `setTimeout`.
+pub(crate) fn set_timeout(callback: &Closure<dyn FnMut()>, delay_ms: i64) -> Option<JsValue> {
+    crate::host::set_timeout(callback.as_ref(), delay_ms.clamp(0, MAX_DELAY_MS) as f64)
+}
+
+/// Cancel a timer started by `set_timeout`.
+pub(crate) fn clear_timeout(handle: &JsValue) {
+    crate::host::clear_timeout(handle);
+}
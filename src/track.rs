@@ -0,0 +1,306 @@
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::civil::MS_PER_MIN;
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::parse::Cursor;
+use crate::sql::parse_date_time;
+use crate::validation::{char_offset, ValidationError};
+use crate::Unitime;
+
+/// Mean radius of the Earth in meters, as used for GPS distances.
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+/// Speed in meters per second below which `movingTime` counts the time between two points as stopped,
+/// 1.8 km/h, unless another is given.
+const STOPPED_BELOW_MPS: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    lat: f64,
+    lon: f64,
+    ms: i64,
+}
+
+impl Point {
+    /// Great-circle distance to `other` in meters.
+    fn distance(&self, other: &Point) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let half_dlat = (lat2 - lat1) / 2.0;
+        let half_dlon = (other.lon - self.lon).to_radians() / 2.0;
+        let a = half_dlat.sin().powi(2) + lat1.cos() * lat2.cos() * half_dlon.sin().powi(2);
+        2.0 * EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+    }
+}
+
+/// Read a GPX or GeoJSON point time, an ISO 8601 date and time in UTC unless it has an offset, as epoch
+/// milliseconds. `None` if it is not one.
+fn parse_time(s: &str) -> Option<Result<i64, ValidationError>> {
+    let mut c = Cursor::new(s.trim());
+    let dt = parse_date_time(&mut c)?;
+    let offset = c.utc_offset().unwrap_or(0);
+    if !c.is_end() {
+        return None;
+    }
+    Some(dt.validate().map(|()| dt.to_epoch_ms() - offset * MS_PER_MIN))
+}
+
+/// The value of the attribute `name` in the text of the tag `tag`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(at) = rest.find(name) {
+        let before = rest[..at].chars().next_back();
+        let after = rest[at + name.len()..].trim_start();
+        rest = &rest[at + name.len()..];
+        let Some(value) = after.strip_prefix('=').map(str::trim_start) else { continue };
+        if !before.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let quote = value.chars().next().filter(|&q| q == '"' || q == '\'')?;
+        return value[1..].split(quote).next();
+    }
+    None
+}
+
+/// Read the `<trkpt>` elements of the GPX document `xml` that have a `<time>`, by `<trkseg>`.
+fn parse_gpx(xml: &str) -> Result<Vec<Vec<Point>>, ValidationError> {
+    let syntax = |at: usize| ValidationError::syntax("invalid GPX track point", char_offset(xml, at));
+    let mut segments: Vec<Vec<Point>> = Vec::new();
+    // Where the open track point starts, and its position and time once read.
+    let mut point: Option<(usize, f64, f64, Option<i64>)> = None;
+    let mut pos = 0;
+    while let Some(lt) = xml[pos..].find('<') {
+        let start = pos + lt;
+        if xml[start..].starts_with("<!--") {
+            pos = xml[start..].find("-->").map_or(xml.len(), |end| start + end + 3);
+            continue;
+        }
+        let Some(gt) = xml[start..].find('>') else { break };
+        let tag = &xml[start + 1..start + gt];
+        pos = start + gt + 1;
+        let (end_tag, empty) = (tag.starts_with('/'), tag.ends_with('/'));
+        let name = tag.trim_start_matches('/').split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+        // Elements may carry a namespace prefix, as in `<gpx:trkpt>`.
+        let name = name.rsplit(':').next().unwrap_or(name);
+        match name {
+            "trkseg" if !end_tag => segments.push(Vec::new()),
+            "trkpt" if !end_tag => {
+                let coordinate = |key| attribute(tag, key).and_then(|v| v.trim().parse::<f64>().ok());
+                let (Some(lat), Some(lon)) = (coordinate("lat"), coordinate("lon")) else {
+                    return Err(syntax(start));
+                };
+                point = Some((start, lat, lon, None));
+            }
+            "time" if !end_tag && !empty => {
+                if let Some((_, _, _, time)) = &mut point {
+                    let end = xml[pos..].find("</").map_or(xml.len(), |end| pos + end);
+                    *time = Some(parse_time(&xml[pos..end]).ok_or_else(|| syntax(pos))??);
+                    pos = end;
+                }
+            }
+            _ => {}
+        }
+        if name == "trkpt" && (end_tag || empty) {
+            if let Some((_, lat, lon, Some(ms))) = point.take() {
+                let point = Point { lat, lon, ms };
+                match segments.last_mut() {
+                    Some(segment) => segment.push(point),
+                    None => segments.push(vec![point]),
+                }
+            }
+        }
+    }
+    if let Some((start, ..)) = point {
+        return Err(syntax(start));
+    }
+    Ok(segments)
+}
+
+/// Read the property `key` of `value`.
+fn get(value: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(value, &key.into()).ok().filter(|v| !v.is_undefined() && !v.is_null())
+}
+
+/// Read one line of GeoJSON `coordinates` and the `coordTimes` that go with it.
+fn parse_line(coordinates: &JsValue, times: &JsValue) -> Result<Vec<Point>, ValidationError> {
+    let invalid = |what: &str| ValidationError::new(format!("invalid GeoJSON track: {what}"));
+    let (coordinates, times) = (Array::from(coordinates), Array::from(times));
+    if coordinates.length() != times.length() {
+        return Err(invalid("coordTimes must have a time for each coordinate"));
+    }
+    coordinates
+        .iter()
+        .zip(times.iter())
+        .map(|(position, time)| {
+            let position = Array::from(&position);
+            let (lon, lat) = (position.get(0).as_f64(), position.get(1).as_f64());
+            let (Some(lon), Some(lat)) = (lon, lat) else { return Err(invalid("a position is not [lon, lat]")) };
+            let time = time.as_string().ok_or_else(|| invalid("a time is not a string"))?;
+            let ms = parse_time(&time).ok_or_else(|| invalid(&format!("{time} is not an ISO 8601 time")))??;
+            Ok(Point { lat, lon, ms })
+        })
+        .collect()
+}
+
+/// Times of a recorded GPS track, read from GPX or GeoJSON, for the time-domain figures fitness apps show:
+/// how long each segment took, moving and stopped time, and pace. Segments are the pieces a recording
+/// was paused between, whose gaps count toward no figure.
+/// # Examples
+/// ```
+/// const track = Track.fromGPX(await file.text());
+/// const pace = track.pace(); // per kilometre of moving time
+/// `${(track.distance / 1000).toFixed(1)} km in ${track.movingTime().toClockString()}`;
+/// ```
+#[wasm_bindgen]
+pub struct Track {
+    segments: Vec<Vec<Point>>,
+}
+
+impl Track {
+    /// Consecutive pairs of points within each segment.
+    fn legs(&self) -> impl Iterator<Item = (&Point, &Point)> {
+        self.segments.iter().flat_map(|segment| segment.iter().zip(segment.iter().skip(1)))
+    }
+
+    fn moving_ms(&self, below_mps: f64) -> i64 {
+        self.legs()
+            .map(|(from, to)| (to.ms - from.ms, from.distance(to)))
+            .filter(|&(ms, meters)| ms > 0 && meters * 1000.0 / ms as f64 >= below_mps)
+            .map(|(ms, _)| ms)
+            .sum()
+    }
+
+    fn check_speed(stopped_below_mps: Option<f64>) -> Result<f64, ValidationError> {
+        match stopped_below_mps.unwrap_or(STOPPED_BELOW_MPS) {
+            speed if speed.is_finite() && speed >= 0.0 => Ok(speed),
+            _ => Err(ValidationError::new("the stopped speed must be a non-negative number of meters per second")),
+        }
+    }
+}
+
+fn duration_ms(ms: i64) -> UniDuration {
+    UniDuration::from_nanos(ms.saturating_mul(NANOS_PER_MILLI))
+}
+
+#[wasm_bindgen]
+impl Track {
+    /// Creates a `Track` from the `<trkpt>` elements of a GPX document, by `<trkseg>`. Points without a
+    /// `<time>` are left out. GPX times are in UTC.
+    /// # Examples
+    /// ```
+    /// const track = Track.fromGPX(`<gpx><trk><trkseg>
+    ///     <trkpt lat="52.3702" lon="4.8952"><time>2024-05-01T07:00:00Z</time></trkpt>
+    ///     <trkpt lat="52.3710" lon="4.8970"><time>2024-05-01T07:00:30Z</time></trkpt>
+    /// </trkseg></trk></gpx>`);
+    /// ```
+    #[wasm_bindgen(js_name = "fromGPX")]
+    pub fn from_gpx(xml: &str) -> Result<Track, ValidationError> {
+        Ok(Track { segments: parse_gpx(xml)? })
+    }
+
+    /// Creates a `Track` from a GeoJSON `Feature` with a `LineString` or `MultiLineString` geometry and
+    /// a time for each position in `properties.coordTimes`, as GPX converters such as togeojson write.
+    #[wasm_bindgen(js_name = "fromGeoJSON")]
+    pub fn from_geo_json(feature: JsValue) -> Result<Track, ValidationError> {
+        let invalid = |what: &str| ValidationError::new(format!("invalid GeoJSON track: {what}"));
+        let geometry = get(&feature, "geometry").ok_or_else(|| invalid("no geometry"))?;
+        let coordinates = get(&geometry, "coordinates").ok_or_else(|| invalid("no coordinates"))?;
+        let times = get(&feature, "properties")
+            .and_then(|properties| get(&properties, "coordTimes"))
+            .ok_or_else(|| invalid("no properties.coordTimes"))?;
+        let segments = match get(&geometry, "type").and_then(|t| t.as_string()).as_deref() {
+            Some("LineString") => vec![parse_line(&coordinates, &times)?],
+            Some("MultiLineString") => {
+                let (lines, times) = (Array::from(&coordinates), Array::from(&times));
+                if lines.length() != times.length() {
+                    return Err(invalid("coordTimes must have a list of times for each line"));
+                }
+                lines.iter().zip(times.iter()).map(|(line, times)| parse_line(&line, &times)).collect::<Result<_, _>>()?
+            }
+            _ => return Err(invalid("the geometry is not a LineString or MultiLineString")),
+        };
+        Ok(Track { segments })
+    }
+
+    /// Get the number of segments.
+    #[wasm_bindgen(getter, js_name = "segmentCount")]
+    pub fn segment_count(&self) -> u32 {
+        self.segments.len() as u32
+    }
+
+    /// Get the number of timed points in all segments.
+    #[wasm_bindgen(getter, js_name = "pointCount")]
+    pub fn point_count(&self) -> u32 {
+        self.segments.iter().map(Vec::len).sum::<usize>() as u32
+    }
+
+    /// Get the time of the first point, or `undefined` if there are none.
+    #[wasm_bindgen(getter, js_name = "startTime")]
+    pub fn start_time(&self) -> Option<Unitime> {
+        self.segments.iter().flatten().next().map(|p| Unitime::from_epoch_ms(p.ms))
+    }
+
+    /// Get the time of the last point, or `undefined` if there are none.
+    #[wasm_bindgen(getter, js_name = "endTime")]
+    pub fn end_time(&self) -> Option<Unitime> {
+        self.segments.iter().flatten().next_back().map(|p| Unitime::from_epoch_ms(p.ms))
+    }
+
+    /// Get the distance along the track in meters, leaving out the gaps between segments.
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 {
+        self.legs().map(|(from, to)| from.distance(to)).sum()
+    }
+
+    /// Get the time from the first to the last point of each segment.
+    #[wasm_bindgen(js_name = "segmentDurations")]
+    pub fn segment_durations(&self) -> Vec<UniDuration> {
+        let span = |segment: &Vec<Point>| match (segment.first(), segment.last()) {
+            (Some(first), Some(last)) => last.ms - first.ms,
+            _ => 0,
+        };
+        self.segments.iter().map(|segment| duration_ms(span(segment))).collect()
+    }
+
+    /// Get the time the segments took together, leaving out the gaps between them.
+    #[wasm_bindgen(getter)]
+    pub fn duration(&self) -> UniDuration {
+        duration_ms(self.legs().map(|(from, to)| to.ms - from.ms).sum())
+    }
+
+    /// Get the time spent moving: the time between consecutive points covered at `stoppedBelowMps`
+    /// meters per second or faster, 0.5 by default.
+    #[wasm_bindgen(js_name = "movingTime")]
+    pub fn moving_time(&self, stopped_below_mps: Option<f64>) -> Result<UniDuration, ValidationError> {
+        Ok(duration_ms(self.moving_ms(Track::check_speed(stopped_below_mps)?)))
+    }
+
+    /// Get the time spent stopped, `duration` less `movingTime` with the same speed.
+    #[wasm_bindgen(js_name = "stoppedTime")]
+    pub fn stopped_time(&self, stopped_below_mps: Option<f64>) -> Result<UniDuration, ValidationError> {
+        let moving = self.moving_ms(Track::check_speed(stopped_below_mps)?);
+        Ok(duration_ms(self.duration().nanos() / NANOS_PER_MILLI - moving))
+    }
+
+    /// Get the moving time per `perMeters` of distance, a kilometre by default, such as 5:30 per km, or
+    /// `undefined` for a track that covers no distance.
+    /// # Examples
+    /// ```
+    /// track.pace(1609.344)?.toClockString(); // per mile
+    /// ```
+    pub fn pace(
+        &self,
+        per_meters: Option<f64>,
+        stopped_below_mps: Option<f64>,
+    ) -> Result<Option<UniDuration>, ValidationError> {
+        let per_meters = per_meters.unwrap_or(1000.0);
+        if !per_meters.is_finite() || per_meters <= 0.0 {
+            return Err(ValidationError::new("pace must be per a positive number of meters"));
+        }
+        let distance = self.distance();
+        if distance == 0.0 {
+            return Ok(None);
+        }
+        let moving = self.moving_ms(Track::check_speed(stopped_below_mps)?);
+        Ok(Some(UniDuration::new(moving as f64 * per_meters / distance)))
+    }
+}
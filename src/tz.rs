@@ -0,0 +1,450 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, DateTime, MS_PER_DAY, MS_PER_SEC};
+use crate::logger::{log, LogKind};
+use crate::tzdata;
+use crate::tzif::ZoneRules;
+use crate::validation::{check_year, ValidationError};
+use crate::Unitime;
+
+/// How `Unitime.fromLocal` resolves a wall-clock time that a zone skips, as when clocks go forward, or
+/// that it repeats, as when clocks go back. The names follow the `disambiguation` option of Temporal.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disambiguation {
+    /// The earlier of two repeated times, and for a skipped time the one after the gap.
+    Compatible = "compatible",
+    /// The earlier of two repeated times, and for a skipped time the one before the gap.
+    Earlier = "earlier",
+    /// The later of two repeated times, and for a skipped time the one after the gap.
+    Later = "later",
+    /// Throw for repeated and skipped times.
+    Reject = "reject",
+}
+
+/// Where the rules of IANA time zones come from, as chosen with `Unitime.setZoneSource`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneSource {
+    /// TZif data added or loaded for the zone, or else the host's `Intl`.
+    Auto = "auto",
+    /// Only TZif data. Other zones are errors, even where `Intl` knows them.
+    Data = "data",
+    /// Only the host's `Intl`, ignoring any TZif data.
+    Intl = "intl",
+}
+
+thread_local! {
+    static ZONE_SOURCE: Cell<ZoneSource> = const { Cell::new(ZoneSource::Auto) };
+}
+
+/// How `Unitime.zoneDisplayName` names a zone, as in the `timeZoneName` option of `Intl.DateTimeFormat`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneNameStyle {
+    /// "Central European Summer Time"
+    Long = "long",
+    /// "CEST", or "GMT+2" where the locale has no abbreviation
+    Short = "short",
+    /// "GMT+02:00"
+    LongOffset = "longOffset",
+    /// "GMT+2"
+    ShortOffset = "shortOffset",
+    /// "Central European Time", the same all year
+    LongGeneric = "longGeneric",
+    /// "Germany Time"
+    ShortGeneric = "shortGeneric",
+}
+
+impl ZoneNameStyle {
+    fn name(self) -> Result<&'static str, ValidationError> {
+        match self {
+            ZoneNameStyle::Long => Ok("long"),
+            ZoneNameStyle::Short => Ok("short"),
+            ZoneNameStyle::LongOffset => Ok("longOffset"),
+            ZoneNameStyle::ShortOffset => Ok("shortOffset"),
+            ZoneNameStyle::LongGeneric => Ok("longGeneric"),
+            ZoneNameStyle::ShortGeneric => Ok("shortGeneric"),
+            _ => Err(ValidationError::new("unknown zone name style")),
+        }
+    }
+}
+
+fn is_utc(zone: &str) -> bool {
+    ["UTC", "Etc/UTC", "GMT", "Etc/GMT"].iter().any(|z| z.eq_ignore_ascii_case(zone))
+}
+
+/// Create an `Intl.DateTimeFormat` for `locale`, or the host's locale if `None`, with `options`, and get
+/// its method `method`. `invalid` describes a rejected option.
+#[cfg(js)]
+fn intl_date_time_format(
+    locale: Option<&str>,
+    options: &js_sys::Object,
+    method: &str,
+    invalid: impl FnOnce() -> ValidationError,
+) -> Result<(JsValue, js_sys::Function), ValidationError> {
+    use js_sys::{Array, Function, Reflect};
+    use wasm_bindgen::JsCast;
+
+    let missing = || ValidationError::unavailable("time zones need Intl.DateTimeFormat in the JS host");
+    let intl = Reflect::get(&js_sys::global(), &"Intl".into()).map_err(|_| missing())?;
+    let constructor: Function =
+        Reflect::get(&intl, &"DateTimeFormat".into()).ok().and_then(|c| c.dyn_into().ok()).ok_or_else(missing)?;
+    let locales = locale.map_or(JsValue::UNDEFINED, JsValue::from);
+    let f = Reflect::construct(&constructor, &Array::of2(&locales, options)).map_err(|_| invalid())?;
+    let method = Reflect::get(&f, &method.into()).ok().and_then(|m| m.dyn_into().ok()).ok_or_else(missing)?;
+    Ok((f, method))
+}
+
+/// Create an `Intl.DateTimeFormat` for `locale`, or the host's locale if `None`, with string `options`,
+/// returning it along with its `formatToParts` method. `invalid` describes a rejected option.
+#[cfg(js)]
+fn date_time_format(
+    locale: Option<&str>,
+    options: &[(&str, &str)],
+    invalid: impl FnOnce() -> ValidationError,
+) -> Result<(JsValue, js_sys::Function), ValidationError> {
+    let object = js_sys::Object::new();
+    for &(key, value) in options {
+        let _ = js_sys::Reflect::set(&object, &key.into(), &value.into());
+    }
+    intl_date_time_format(locale, &object, "formatToParts", invalid)
+}
+
+/// Format instant `ms` with a formatter from `date_time_format`, as `(type, value)` pairs.
+#[cfg(js)]
+fn format_to_parts(
+    (f, format): &(JsValue, js_sys::Function),
+    ms: i64,
+) -> Result<Vec<(String, String)>, ValidationError> {
+    use js_sys::{Array, Reflect};
+    use wasm_bindgen::JsCast;
+
+    let date = js_sys::Date::new(&JsValue::from_f64(ms as f64));
+    let parts: Array = format.call1(f, &date).ok().and_then(|p| p.dyn_into().ok()).ok_or_else(|| {
+        ValidationError::new("time is outside the range of Intl.DateTimeFormat")
+    })?;
+    let field = |part: &JsValue, key: &str| Reflect::get(part, &key.into()).ok().and_then(|v| v.as_string());
+    let pair = |part: JsValue| (field(&part, "type").unwrap_or_default(), field(&part, "value").unwrap_or_default());
+    Ok(parts.iter().map(pair).collect())
+}
+
+/// Offset of `zone` from UTC in milliseconds at instant `ms`, read from the host's `Intl`.
+#[cfg(js)]
+fn intl_offset_ms(zone: &str, ms: i64) -> Result<i64, ValidationError> {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    thread_local! {
+        /// Formatters showing every field of a date, by zone.
+        static FORMATTERS: RefCell<HashMap<String, (JsValue, js_sys::Function)>> = RefCell::new(HashMap::new());
+    }
+    let cached = FORMATTERS.with(|cache| cache.borrow().get(zone).cloned());
+    let formatter = match cached {
+        Some(formatter) => formatter,
+        None => {
+            let options = [
+                ("timeZone", zone),
+                ("hourCycle", "h23"),
+                ("era", "short"),
+                ("year", "numeric"),
+                ("month", "numeric"),
+                ("day", "numeric"),
+                ("hour", "numeric"),
+                ("minute", "numeric"),
+                ("second", "numeric"),
+            ];
+            let invalid = || ValidationError::new(format!("unknown time zone: {zone}"));
+            let formatter = date_time_format(Some("en-US"), &options, invalid)?;
+            FORMATTERS.with(|cache| cache.borrow_mut().insert(zone.to_string(), formatter.clone()));
+            formatter
+        }
+    };
+    // Intl works in whole seconds.
+    let second = ms - ms.rem_euclid(1000);
+    let mut wall = DateTime { year: 0, month: 1, day: 1, hour: 0, minute: 0, second: 0, milli: 0 };
+    let mut bc = false;
+    for (kind, value) in format_to_parts(&formatter, second)? {
+        let number = value.parse::<u32>().unwrap_or(0);
+        match kind.as_str() {
+            "era" => bc = value.starts_with('B'),
+            "year" => wall.year = number as i64,
+            "month" => wall.month = number,
+            "day" => wall.day = number,
+            "hour" => wall.hour = number,
+            "minute" => wall.minute = number,
+            "second" => wall.second = number,
+            _ => {}
+        }
+    }
+    if bc {
+        wall.year = 1 - wall.year;
+    }
+    Ok(wall.to_epoch_ms() - second)
+}
+
+#[cfg(not(js))]
+fn intl_offset_ms(zone: &str, _ms: i64) -> Result<i64, ValidationError> {
+    Err(ValidationError::unavailable(format!("time zone {zone} needs Intl.DateTimeFormat in the JS host")))
+}
+
+/// Name of `zone`, or of the host's zone if `None`, at instant `ms` in `style`, read from the host's `Intl`.
+#[cfg(js)]
+fn intl_zone_name(ms: i64, zone: Option<&str>, locale: Option<&str>, style: &str) -> Result<String, ValidationError> {
+    let mut options = vec![("timeZoneName", style)];
+    options.extend(zone.map(|zone| ("timeZone", zone)));
+    let invalid = || ValidationError::new(format!("unknown time zone or locale: {}", zone.unwrap_or("local")));
+    let formatter = date_time_format(locale, &options, invalid)?;
+    format_to_parts(&formatter, ms)?
+        .into_iter()
+        .find_map(|(kind, value)| (kind == "timeZoneName").then_some(value))
+        .ok_or_else(|| ValidationError::new("the host did not name the time zone"))
+}
+
+/// Outside a JS host only UTC is known, and it is also the local zone.
+#[cfg(not(js))]
+fn intl_zone_name(_ms: i64, zone: Option<&str>, _locale: Option<&str>, style: &str) -> Result<String, ValidationError> {
+    match zone {
+        Some(zone) if !is_utc(zone) => {
+            Err(ValidationError::unavailable(format!("time zone {zone} needs Intl.DateTimeFormat in the JS host")))
+        }
+        _ => Ok(match style {
+            "long" => "Coordinated Universal Time",
+            "short" => "UTC",
+            _ => "GMT",
+        }
+        .to_string()),
+    }
+}
+
+/// Format instant `ms` in `zone` with `Intl.DateTimeFormat` and `options`, for `formatInZone`.
+#[cfg(js)]
+fn intl_format(
+    ms: i64,
+    zone: Option<&str>,
+    locale: Option<&str>,
+    options: Option<js_sys::Object>,
+) -> Result<String, ValidationError> {
+    use js_sys::{Object, Reflect};
+
+    let object = Object::assign(&Object::new(), &options.unwrap_or_default());
+    if let Some(zone) = zone {
+        let _ = Reflect::set(&object, &"timeZone".into(), &zone.into());
+    }
+    let invalid = || ValidationError::new("Intl.DateTimeFormat rejected the time zone, locale or options");
+    let (f, format) = intl_date_time_format(locale, &object, "format", invalid)?;
+    let date = js_sys::Date::new(&JsValue::from_f64(ms as f64));
+    format
+        .call1(&f, &date)
+        .ok()
+        .and_then(|s| s.as_string())
+        .ok_or_else(|| ValidationError::new("time is outside the range of Intl.DateTimeFormat"))
+}
+
+#[cfg(not(js))]
+fn intl_format(
+    _ms: i64,
+    _zone: Option<&str>,
+    _locale: Option<&str>,
+    _options: Option<js_sys::Object>,
+) -> Result<String, ValidationError> {
+    Err(ValidationError::unavailable("formatInZone needs Intl.DateTimeFormat in the JS host"))
+}
+
+/// Whether `name` is an offset such as "GMT+1" or "UTC-05:00" rather than an abbreviation.
+fn is_offset_name(name: &str) -> bool {
+    (name.starts_with("GMT") || name.starts_with("UTC")) && name.len() > 3
+}
+
+/// Write an offset from UTC in seconds the way `Intl` does in short names, such as "GMT+9" or "GMT-3:30".
+fn gmt_name(offset: i64) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 3600, offset.abs() / 60 % 60);
+    match (hours, minutes) {
+        (0, 0) => "GMT".to_string(),
+        (_, 0) => format!("GMT{sign}{hours}"),
+        _ => format!("GMT{sign}{hours}:{minutes:02}"),
+    }
+}
+
+/// TZif rules of `zone` if the zone source allows them and they are there, `None` to ask `Intl` instead.
+fn zone_rules(zone: &str) -> Result<Option<Rc<ZoneRules>>, ValidationError> {
+    match ZONE_SOURCE.with(Cell::get) {
+        ZoneSource::Intl => Ok(None),
+        ZoneSource::Data => tzdata::rules(zone)
+            .map(Some)
+            .ok_or_else(|| ValidationError::new(format!("no rules for time zone {zone}; add them with addZoneData"))),
+        _ => Ok(tzdata::rules(zone)),
+    }
+}
+
+/// Offset of the IANA zone `zone` from UTC in milliseconds at instant `ms`.
+pub(crate) fn offset_ms(zone: &str, ms: i64) -> Result<i64, ValidationError> {
+    if is_utc(zone) {
+        return Ok(0);
+    }
+    let (offset, source) = match zone_rules(zone)? {
+        Some(rules) => (rules.local_time(ms).offset * MS_PER_SEC, "TZif rules"),
+        None => (intl_offset_ms(zone, ms)?, "Intl"),
+    };
+    log(LogKind::Tz, || format!("{zone} is {offset} ms from UTC at {ms} ms, from {source}"));
+    Ok(offset)
+}
+
+/// The wall-clock reading at instant `ms` in the IANA zone `zone`, or in the host's zone if `None`. Fails
+/// for years `Unitime` cannot hold dates in, which leaves room for the offset and the days around `ms`.
+pub(crate) fn zoned_wall(ms: i64, zone: Option<&str>) -> Result<i64, ValidationError> {
+    check_year(civil::civil_from_days(ms.div_euclid(MS_PER_DAY)).0)?;
+    match zone {
+        Some(zone) => Ok(ms + offset_ms(zone, ms)?),
+        None => Ok(civil::utc_to_local_ms(ms)),
+    }
+}
+
+/// The instant at which the wall clock in `zone` reads `wall`, as epoch milliseconds without an offset.
+pub(crate) fn wall_to_zoned(wall: i64, zone: &str, disambiguation: Disambiguation) -> Result<i64, ValidationError> {
+    // Zones change offset at most once in any two days, so the offsets a day either side are the only
+    // ones that can apply.
+    let before = wall - offset_ms(zone, wall - MS_PER_DAY)?;
+    let after = wall - offset_ms(zone, wall + MS_PER_DAY)?;
+    let fits = |t: i64| offset_ms(zone, t).map(|offset| t + offset == wall);
+    let (earlier, later) = (before.min(after), before.max(after));
+    let matches = (fits(earlier)?, earlier != later && fits(later)?);
+    let skipped = match matches {
+        (true, false) => return Ok(earlier),
+        (false, true) => return Ok(later),
+        (true, true) => false,
+        (false, false) => true,
+    };
+    match disambiguation {
+        Disambiguation::Compatible if skipped => Ok(later),
+        Disambiguation::Compatible | Disambiguation::Earlier => Ok(earlier),
+        Disambiguation::Later => Ok(later),
+        _ if skipped => Err(ValidationError::new(format!("the local time does not exist in {zone}"))),
+        _ => Err(ValidationError::new(format!("the local time is ambiguous in {zone}"))),
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Creates a `Unitime` from a wall-clock date and time in the IANA time zone `zone`, such as
+    /// `"America/New_York"`. When the clocks go forward some local times never happen, and when they go
+    /// back some happen twice; `disambiguation` decides what those give, `"compatible"` by default.
+    /// Zone rules come from TZif data given to `addZoneData` or a zone loader, or else from the host's
+    /// `Intl.DateTimeFormat`, unless `setZoneSource` says otherwise.
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromLocal(2024, 3, 10, 2, 30, 0, "America/New_York"); // 03:30 EDT
+    /// Unitime.fromLocal(2024, 11, 3, 1, 30, 0, "America/New_York", "later"); // 01:30 EST
+    /// Unitime.fromLocal(2024, 3, 10, 2, 30, 0, "America/New_York", "reject"); // throws
+    /// ```
+    #[wasm_bindgen(js_name = "fromLocal")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_local(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        zone: &str,
+        disambiguation: Option<Disambiguation>,
+    ) -> Result<Unitime, ValidationError> {
+        let wall = DateTime { year: year as i64, month, day, hour, minute, second, milli: 0 };
+        wall.validate()?;
+        let disambiguation = disambiguation.unwrap_or(Disambiguation::Compatible);
+        Ok(Unitime::from_epoch_ms(wall_to_zoned(wall.to_epoch_ms(), zone, disambiguation)?))
+    }
+
+    /// Get the abbreviation of `zone`, or of the local zone if omitted, at the stored time, such as "EST"
+    /// or "EDT", "CET" or "CEST", "GMT" or "BST", and "AEST" or "IST". Zones without a common English
+    /// abbreviation give their offset, such as "GMT+9".
+    /// # Examples
+    /// ```
+    /// const t = Unitime.fromLocal(2024, 7, 1, 9, 0, 0, "Europe/Berlin");
+    /// t.zoneAbbreviation("Europe/Berlin"); // "CEST"
+    /// t.zoneAbbreviation("America/New_York"); // "EDT"
+    /// ```
+    #[wasm_bindgen(js_name = "zoneAbbreviation")]
+    pub fn zone_abbreviation(&self, zone: Option<String>) -> Result<String, ValidationError> {
+        let ms = self.epoch_ms();
+        let zone = zone.as_deref();
+        if let Some(rules) = zone.map(zone_rules).transpose()?.flatten() {
+            let local = rules.local_time(ms);
+            // The database writes zones without an abbreviation as offsets such as "+09".
+            if !local.abbr.starts_with(['+', '-']) {
+                return Ok(local.abbr.clone());
+            }
+            return Ok(gmt_name(local.offset));
+        }
+        // English locales only know the abbreviations of their own region, so try several.
+        let first = intl_zone_name(ms, zone, Some("en-US"), "short")?;
+        if !is_offset_name(&first) {
+            return Ok(first);
+        }
+        for locale in ["en-GB", "en-AU", "en-IN"] {
+            let name = intl_zone_name(ms, zone, Some(locale), "short")?;
+            if !is_offset_name(&name) {
+                return Ok(name);
+            }
+        }
+        Ok(first)
+    }
+
+    /// Get the name of `zone`, or of the local zone if omitted, at the stored time in the language of
+    /// `locale`, or the host's if omitted. `style` is `"long"` by default.
+    /// # Examples
+    /// ```
+    /// t.zoneDisplayName("en", "long", "Europe/Berlin"); // "Central European Summer Time"
+    /// t.zoneDisplayName("de", "long", "Europe/Berlin"); // "Mitteleuropäische Sommerzeit"
+    /// t.zoneDisplayName("en", "shortOffset", "Asia/Kolkata"); // "GMT+5:30"
+    /// ```
+    #[wasm_bindgen(js_name = "zoneDisplayName")]
+    pub fn zone_display_name(
+        &self,
+        locale: Option<String>,
+        style: Option<ZoneNameStyle>,
+        zone: Option<String>,
+    ) -> Result<String, ValidationError> {
+        let style = style.unwrap_or(ZoneNameStyle::Long).name()?;
+        intl_zone_name(self.epoch_ms(), zone.as_deref(), locale.as_deref(), style)
+    }
+
+    /// Choose where time zone methods get the rules of IANA zones: from TZif data if there is some for
+    /// the zone and else `Intl`, which is the default `"auto"`, from TZif data only, or from `Intl` only.
+    /// The `Intl` rules need no data in the bundle; TZif data gives the same answers in every host.
+    /// # Examples
+    /// ```
+    /// Unitime.setZoneSource("intl"); // ignore bundled zone data and trust the browser
+    /// ```
+    #[wasm_bindgen(js_name = "setZoneSource")]
+    pub fn set_zone_source(source: ZoneSource) -> Result<(), ValidationError> {
+        if !matches!(source, ZoneSource::Auto | ZoneSource::Data | ZoneSource::Intl) {
+            return Err(ValidationError::new("unknown zone source"));
+        }
+        ZONE_SOURCE.with(|s| s.set(source));
+        Ok(())
+    }
+
+    /// Format the stored time as the wall clock reads in `zone`, or in the local zone if omitted, with
+    /// the host's `Intl.DateTimeFormat`, so it takes no zone data or locale tables in the bundle.
+    /// `options` are those of `Intl.DateTimeFormat`, other than `timeZone`, and default to its date and
+    /// time in numbers; `locale` is the host's if omitted.
+    /// # Examples
+    /// ```
+    /// t.formatInZone("Asia/Tokyo", "ja", { dateStyle: "long", timeStyle: "short" }); // "2024年7月1日 16:00"
+    /// t.formatInZone("America/New_York", "en-US", { hour: "numeric", timeZoneName: "short" }); // "3 AM EDT"
+    /// ```
+    #[wasm_bindgen(js_name = "formatInZone")]
+    pub fn format_in_zone(
+        &self,
+        zone: Option<String>,
+        locale: Option<String>,
+        options: Option<js_sys::Object>,
+    ) -> Result<String, ValidationError> {
+        intl_format(self.epoch_ms(), zone.as_deref(), locale.as_deref(), options)
+    }
+}
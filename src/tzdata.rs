@@ -0,0 +1,213 @@
+//! Zone rules supplied by the page as TZif files, added directly or fetched on demand, and cached for
+//! the life of the module. Zones with rules here are worked out without `Intl`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use js_sys::{ArrayBuffer, Function, Promise, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::tzif::ZoneRules;
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// Where rules of zones that have not been added come from.
+#[derive(Clone)]
+enum Loader {
+    Callback(Function),
+    /// Base URL that zone names are appended to.
+    Url(String),
+}
+
+thread_local! {
+    static ZONES: RefCell<HashMap<String, Rc<ZoneRules>>> = RefCell::new(HashMap::new());
+    static LOADER: RefCell<Option<Loader>> = const { RefCell::new(None) };
+    /// Zones the loader has been asked for without `loadZone`, so that they are only asked for once.
+    static REQUESTED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    /// Loads still under way, so that a zone is not asked for again while it is.
+    static PENDING: RefCell<HashMap<String, Promise>> = RefCell::new(HashMap::new());
+    /// Bumped when the loader changes or the rules are cleared, so that loads started before are dropped.
+    static GENERATION: Cell<u32> = const { Cell::new(0) };
+}
+
+fn generation() -> u32 {
+    GENERATION.with(Cell::get)
+}
+
+fn cached(zone: &str) -> Option<Rc<ZoneRules>> {
+    ZONES.with(|zones| zones.borrow().get(zone).cloned())
+}
+
+fn add(zone: &str, data: &[u8]) -> Result<(), ValidationError> {
+    let rules = ZoneRules::parse(data)?;
+    ZONES.with(|zones| zones.borrow_mut().insert(zone.to_string(), Rc::new(rules)));
+    Ok(())
+}
+
+/// Add what a loader gave for `zone`, which should be a `Uint8Array` or `ArrayBuffer`.
+fn add_loaded(zone: &str, data: &JsValue) -> Result<(), ValidationError> {
+    if !(data.is_instance_of::<Uint8Array>() || data.is_instance_of::<ArrayBuffer>()) {
+        return Err(ValidationError::new(format!("the zone loader gave no TZif bytes for {zone}")));
+    }
+    add(zone, &Uint8Array::new(data).to_vec())
+}
+
+/// Call `promise.then(fulfilled, rejected)` with functions made by `Closure::once_into_js`.
+fn then(promise: &Promise, fulfilled: JsValue, rejected: JsValue) -> Result<Promise, JsValue> {
+    let then: Function = Reflect::get(promise, &"then".into())?.dyn_into()?;
+    then.call2(promise, &fulfilled, &rejected)?.dyn_into()
+}
+
+/// Fetch `url`, resolving to the body as an `ArrayBuffer`.
+fn fetch(url: &str) -> Result<JsValue, JsValue> {
+    let global = js_sys::global();
+    let fetch: Function = Reflect::get(&global, &"fetch".into())?.dyn_into()?;
+    let response = fetch.call1(&global, &url.into())?;
+    let url = url.to_string();
+    let body = Closure::once_into_js(move |response: JsValue| -> Result<JsValue, JsValue> {
+        if !Reflect::get(&response, &"ok".into())?.is_truthy() {
+            return Err(ValidationError::new(format!("could not fetch zone rules from {url}")).into());
+        }
+        let array_buffer: Function = Reflect::get(&response, &"arrayBuffer".into())?.dyn_into()?;
+        array_buffer.call0(&response)
+    });
+    Ok(then(&Promise::resolve(&response), body, JsValue::UNDEFINED)?.into())
+}
+
+/// Ask the loader for `zone`, resolving once its rules are cached. A loader that answers with bytes
+/// rather than a promise has its rules cached before this returns.
+fn load(zone: &str) -> Result<Promise, JsValue> {
+    if let Some(pending) = PENDING.with(|pending| pending.borrow().get(zone).cloned()) {
+        return Ok(pending);
+    }
+    // The loader is taken out first, since it may set another loader while it runs.
+    let loader = LOADER.with(|loader| loader.borrow().clone());
+    let started = generation();
+    let loaded = match loader {
+        Some(Loader::Callback(callback)) => callback.call1(&JsValue::NULL, &zone.into()),
+        Some(Loader::Url(base)) => fetch(&format!("{base}{zone}")),
+        None => Err(ValidationError::new(format!("no rules for time zone {zone} and no zone loader is set")).into()),
+    }?;
+    if !loaded.is_instance_of::<Promise>() {
+        if generation() == started {
+            add_loaded(zone, &loaded)?;
+        }
+        return Ok(Promise::resolve(&JsValue::UNDEFINED));
+    }
+    // Results of loads started before the loader changed are dropped.
+    let current = move || generation() == started;
+    let done = move |zone: &str| {
+        if current() {
+            PENDING.with(|pending| pending.borrow_mut().remove(zone));
+        }
+    };
+    let key = zone.to_string();
+    let (zone, failed_zone) = (zone.to_string(), zone.to_string());
+    let added = Closure::once_into_js(move |data: JsValue| -> Result<JsValue, JsValue> {
+        done(&zone);
+        if current() {
+            add_loaded(&zone, &data)?;
+        }
+        Ok(JsValue::UNDEFINED)
+    });
+    let failed = Closure::once_into_js(move |error: JsValue| -> Result<JsValue, JsValue> {
+        done(&failed_zone);
+        Err(error)
+    });
+    let promise = then(&Promise::resolve(&loaded), added, failed)?;
+    PENDING.with(|pending| pending.borrow_mut().insert(key, promise.clone()));
+    Ok(promise)
+}
+
+/// Let every zone be asked for again, as after the loader changes.
+fn forget_requests() {
+    GENERATION.with(|generation| generation.set(generation.get().wrapping_add(1)));
+    REQUESTED.with(|requested| requested.borrow_mut().clear());
+    PENDING.with(|pending| pending.borrow_mut().clear());
+}
+
+/// Rules of `zone` if it has been added or loaded. Otherwise the loader, if any, is asked for it so
+/// that later calls find it; a loader that answers at once serves this call too.
+pub(crate) fn rules(zone: &str) -> Option<Rc<ZoneRules>> {
+    if let Some(rules) = cached(zone) {
+        return Some(rules);
+    }
+    let has_loader = LOADER.with(|loader| loader.borrow().is_some());
+    if !has_loader || !REQUESTED.with(|requested| requested.borrow_mut().insert(zone.to_string())) {
+        return None;
+    }
+    if let Ok(promise) = load(zone) {
+        // Failures show up as `Intl` being used; `loadZone` reports them.
+        let _ = then(&promise, JsValue::UNDEFINED, Closure::once_into_js(|_: JsValue| {}));
+    }
+    cached(zone)
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Add the rules of the IANA zone `zone`, such as `"Europe/Berlin"`, from the bytes of its TZif file
+    /// as found in `/usr/share/zoneinfo`. Time zone methods use them instead of `Intl` from then on,
+    /// so hosts without time zone data still get every zone added.
+    /// # Examples
+    /// ```
+    /// const data = new Uint8Array(await (await fetch("/zoneinfo/Europe/Berlin")).arrayBuffer());
+    /// Unitime.addZoneData("Europe/Berlin", data);
+    /// ```
+    #[wasm_bindgen(js_name = "addZoneData")]
+    pub fn add_zone_data(zone: &str, data: &[u8]) -> Result<(), ValidationError> {
+        add(zone, data)
+    }
+
+    /// Set the function that supplies rules of zones that have not been added, or remove it if omitted.
+    /// It is called with a zone name the first time the zone is used, and returns its TZif bytes as a
+    /// `Uint8Array` or `ArrayBuffer`, or a promise of them. Until a promise resolves the zone is worked
+    /// out with `Intl`; await `loadZone` to wait for it.
+    /// # Examples
+    /// ```
+    /// Unitime.setZoneLoader(async zone => (await import(`./zoneinfo/${zone}.js`)).default);
+    /// ```
+    #[wasm_bindgen(js_name = "setZoneLoader")]
+    pub fn set_zone_loader(loader: Option<Function>) {
+        LOADER.with(|l| *l.borrow_mut() = loader.map(Loader::Callback));
+        forget_requests();
+    }
+
+    /// Fetch the rules of zones that have not been added from `url` followed by the zone name, or stop
+    /// fetching them if omitted. Like `setZoneLoader`, a zone is fetched the first time it is used.
+    /// # Examples
+    /// ```
+    /// Unitime.setZoneDataUrl("https://cdn.example.com/zoneinfo/");
+    /// await Unitime.loadZone("America/New_York"); // fetches .../zoneinfo/America/New_York
+    /// ```
+    #[wasm_bindgen(js_name = "setZoneDataUrl")]
+    pub fn set_zone_data_url(url: Option<String>) {
+        let url = url.map(|url| if url.ends_with('/') { url } else { url + "/" });
+        LOADER.with(|l| *l.borrow_mut() = url.map(Loader::Url));
+        forget_requests();
+    }
+
+    /// Get the rules of `zone` from the loader, resolving once they are ready, at once if they already
+    /// are. Rejects if there is no loader or it fails or gives invalid data.
+    #[wasm_bindgen(js_name = "loadZone")]
+    pub fn load_zone(zone: &str) -> Promise {
+        if cached(zone).is_some() {
+            return Promise::resolve(&JsValue::UNDEFINED);
+        }
+        load(zone).unwrap_or_else(|error| Promise::reject(&error))
+    }
+
+    /// Check whether rules of `zone` have been added or loaded.
+    #[wasm_bindgen(js_name = "hasZoneData")]
+    pub fn has_zone_data(zone: &str) -> bool {
+        cached(zone).is_some()
+    }
+
+    /// Remove every zone's added or loaded rules. The loader, if any, is asked for zones again.
+    #[wasm_bindgen(js_name = "clearZoneData")]
+    pub fn clear_zone_data() {
+        ZONES.with(|zones| zones.borrow_mut().clear());
+        forget_requests();
+    }
+}
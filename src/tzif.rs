@@ -0,0 +1,283 @@
+//! Reader for TZif files, the compiled zone rules of the IANA time zone database (RFC 8536).
+
+use crate::civil::{civil_from_days, days_from_civil, days_in_month, is_leap_year, weekday, MS_PER_DAY, MS_PER_SEC};
+use crate::parse::Cursor;
+use crate::validation::ValidationError;
+
+const SECS_PER_DAY: i64 = MS_PER_DAY / MS_PER_SEC;
+
+/// One kind of local time in a zone, such as its standard or its daylight saving time.
+#[derive(Clone, Debug)]
+pub(crate) struct LocalTime {
+    /// Offset from UTC in seconds, positive east of Greenwich.
+    pub offset: i64,
+    pub abbr: String,
+}
+
+/// Day of the year on which a POSIX TZ rule changes between standard and daylight time.
+#[derive(Clone, Copy, Debug)]
+enum RuleDay {
+    /// `Jn`: day 1 to 365, never counting February 29.
+    Julian(u32),
+    /// `n`: day 0 to 365, counting February 29 in leap years.
+    Ordinal(u32),
+    /// `Mm.w.d`: weekday `d`, from 0 for Sunday, of week `w` of month `m`, where week 5 is the last.
+    Month(u32, u32, u32),
+}
+
+impl RuleDay {
+    /// The day in `year`, in days since 1970-01-01.
+    fn day_in(self, year: i64) -> i64 {
+        match self {
+            RuleDay::Julian(n) => days_from_civil(year, 1, 1) + n as i64 - 1 + (n > 59 && is_leap_year(year)) as i64,
+            RuleDay::Ordinal(n) => days_from_civil(year, 1, 1) + n as i64,
+            RuleDay::Month(month, week, day) => {
+                let first = days_from_civil(year, month, 1);
+                let nth = first + (day as i64 - weekday(first) as i64).rem_euclid(7) + 7 * (week as i64 - 1);
+                if nth >= first + days_in_month(year, month) as i64 {
+                    nth - 7
+                } else {
+                    nth
+                }
+            }
+        }
+    }
+}
+
+/// A change of local time: the day, and the wall-clock time it happens at in seconds after midnight of
+/// the local time it ends.
+#[derive(Clone, Debug)]
+struct Change {
+    day: RuleDay,
+    time: i64,
+}
+
+/// Local time from the POSIX TZ string at the end of a TZif file, which covers every instant after the
+/// last transition listed.
+#[derive(Clone, Debug)]
+struct PosixRule {
+    std: LocalTime,
+    /// Daylight time, and when it starts and ends each year.
+    dst: Option<(LocalTime, Change, Change)>,
+}
+
+/// Read a zone abbreviation, letters such as `CET` or anything within angle brackets such as `<+0530>`.
+fn abbr(c: &mut Cursor) -> Option<String> {
+    let quoted = c.eat(b'<');
+    let mut name = String::new();
+    while let Some(b) = c.peek().filter(|&b| if quoted { b != b'>' } else { b.is_ascii_alphabetic() }) {
+        name.push(b as char);
+        c.eat(b);
+    }
+    if quoted && !c.eat(b'>') {
+        return None;
+    }
+    (name.len() >= 3).then_some(name)
+}
+
+/// Read `[+-]hh[:mm[:ss]]` as seconds. Hours go up to 167 so that rule times can fall on other days.
+fn hms(c: &mut Cursor) -> Option<i64> {
+    let sign = if c.eat(b'-') {
+        -1
+    } else {
+        c.eat(b'+');
+        1
+    };
+    let (hours, _) = c.digits(1, 3).filter(|&(h, _)| h <= 167)?;
+    let mut secs = hours as i64 * 3600;
+    for unit in [60, 1] {
+        if !c.eat(b':') {
+            break;
+        }
+        secs += c.digits(1, 2).filter(|&(v, _)| v < 60)?.0 as i64 * unit;
+    }
+    Some(sign * secs)
+}
+
+fn change(c: &mut Cursor) -> Option<Change> {
+    let number = |c: &mut Cursor, max_digits, range: std::ops::RangeInclusive<u32>| {
+        c.digits(1, max_digits).map(|(v, _)| v).filter(|v| range.contains(v))
+    };
+    let day = if c.eat(b'J') {
+        RuleDay::Julian(number(c, 3, 1..=365)?)
+    } else if c.eat(b'M') {
+        let month = number(c, 2, 1..=12)?;
+        c.eat(b'.').then_some(())?;
+        let week = number(c, 1, 1..=5)?;
+        c.eat(b'.').then_some(())?;
+        RuleDay::Month(month, week, number(c, 1, 0..=6)?)
+    } else {
+        RuleDay::Ordinal(number(c, 3, 0..=365)?)
+    };
+    let time = if c.eat(b'/') { hms(c)? } else { 2 * 3600 };
+    Some(Change { day, time })
+}
+
+impl PosixRule {
+    /// Read a TZ string such as `CET-1CEST,M3.5.0,M10.5.0/3`. Offsets in it count west of Greenwich.
+    fn parse(text: &str) -> Option<PosixRule> {
+        let mut c = Cursor::new(text);
+        let std_name = abbr(&mut c)?;
+        let std = LocalTime { offset: -hms(&mut c)?, abbr: std_name };
+        if c.is_end() {
+            return Some(PosixRule { std, dst: None });
+        }
+        let dst_name = abbr(&mut c)?;
+        let offset = match c.peek() {
+            None | Some(b',') => std.offset + 3600,
+            Some(_) => -hms(&mut c)?,
+        };
+        let dst = LocalTime { offset, abbr: dst_name };
+        let (start, end) = if c.eat(b',') {
+            let start = change(&mut c)?;
+            c.eat(b',').then_some(())?;
+            (start, change(&mut c)?)
+        } else {
+            // The rule POSIX leaves to the implementation; this is the US one, as glibc uses.
+            (Change { day: RuleDay::Month(3, 2, 0), time: 7200 }, Change { day: RuleDay::Month(11, 1, 0), time: 7200 })
+        };
+        c.is_end().then_some(PosixRule { std, dst: Some((dst, start, end)) })
+    }
+
+    fn local_time(&self, secs: i64) -> &LocalTime {
+        let Some((dst, start, end)) = &self.dst else {
+            return &self.std;
+        };
+        let (year, _, _) = civil_from_days((secs + self.std.offset).div_euclid(SECS_PER_DAY));
+        let starts = start.day.day_in(year) * SECS_PER_DAY + start.time - self.std.offset;
+        let ends = end.day.day_in(year) * SECS_PER_DAY + end.time - dst.offset;
+        // In the southern hemisphere daylight time spans the new year.
+        let in_dst = if starts < ends { starts <= secs && secs < ends } else { !(ends <= secs && secs < starts) };
+        if in_dst {
+            dst
+        } else {
+            &self.std
+        }
+    }
+}
+
+/// Rules of one time zone, read from a TZif file.
+#[derive(Clone, Debug)]
+pub(crate) struct ZoneRules {
+    /// Instants in seconds at which the local time changes, with the index in `types` of the new one.
+    transitions: Vec<(i64, usize)>,
+    types: Vec<LocalTime>,
+    rule: Option<PosixRule>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+fn cut_short() -> ValidationError {
+    ValidationError::new("TZif data is cut short")
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ValidationError> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or_else(cut_short)?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn int(&mut self, size: usize) -> Result<i64, ValidationError> {
+        let bytes = self.take(size)?;
+        // Negative 4-byte values start from all ones, which sign-extends them.
+        let start = if size < 8 && bytes.first().is_some_and(|&b| b & 0x80 != 0) { -1 } else { 0 };
+        Ok(bytes.iter().fold(start, |value, &b| value << 8 | b as i64))
+    }
+
+    /// Read a header, giving the version and the counts of
+    /// `[isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt]`.
+    fn header(&mut self) -> Result<(u8, [usize; 6]), ValidationError> {
+        if self.take(4)? != b"TZif" {
+            return Err(ValidationError::new("not TZif data"));
+        }
+        let version = self.take(1)?[0];
+        self.take(15)?;
+        let mut counts = [0; 6];
+        for count in &mut counts {
+            *count = self.int(4)? as u32 as usize;
+        }
+        Ok((version, counts))
+    }
+
+    /// Check that the data block described by `counts`, with times of `time_size` bytes, fits in the
+    /// bytes left, so that counts in a damaged header neither overflow nor allocate beyond the file.
+    fn block(&self, counts: [usize; 6], time_size: usize) -> Result<usize, ValidationError> {
+        let [isut, isstd, leap, time, types, chars] = counts;
+        let len = [(time, time_size + 1), (types, 6), (chars, 1), (leap, time_size + 4), (isstd, 1), (isut, 1)]
+            .iter()
+            .try_fold(0usize, |len, &(count, size)| len.checked_add(count.checked_mul(size)?));
+        len.filter(|&len| len <= self.bytes.len() - self.pos).ok_or_else(cut_short)
+    }
+}
+
+impl ZoneRules {
+    pub(crate) fn parse(bytes: &[u8]) -> Result<ZoneRules, ValidationError> {
+        let invalid = || ValidationError::new("invalid TZif data");
+        let mut r = Reader { bytes, pos: 0 };
+        let (version, mut counts) = r.header()?;
+        let mut time_size = 4;
+        if version >= b'2' {
+            // Skip the data with 32-bit times, kept for old readers, to the header of the 64-bit data.
+            r.take(r.block(counts, time_size)?)?;
+            counts = r.header()?.1;
+            time_size = 8;
+        }
+        r.block(counts, time_size)?;
+        let [isut, isstd, leap, time, types, chars] = counts;
+        if types == 0 {
+            return Err(invalid());
+        }
+        let times = (0..time).map(|_| r.int(time_size)).collect::<Result<Vec<_>, _>>()?;
+        let indices = r.take(time)?;
+        if indices.iter().any(|&i| i as usize >= types) {
+            return Err(invalid());
+        }
+        let mut infos = Vec::with_capacity(types);
+        for _ in 0..types {
+            let offset = r.int(4)?;
+            // The byte between is the daylight saving flag, which the rules do not need.
+            let index = r.take(2)?[1] as usize;
+            infos.push((offset, index));
+        }
+        let names = r.take(chars)?;
+        // Leap second records and the standard and UT indicators only matter to older ways of
+        // reading the file.
+        r.take(leap * (time_size + 4) + isstd + isut)?;
+        let mut local_times = Vec::with_capacity(types);
+        for (offset, index) in infos {
+            let name = names.get(index..).ok_or_else(invalid)?;
+            let name = &name[..name.iter().position(|&b| b == 0).unwrap_or(name.len())];
+            local_times.push(LocalTime { offset, abbr: String::from_utf8_lossy(name).into_owned() });
+        }
+        let mut rule = None;
+        if version >= b'2' {
+            // The footer is a TZ string between newlines, empty if the zone has no rule.
+            let footer = r.bytes[r.pos..].strip_prefix(b"\n").and_then(|f| f.split(|&b| b == b'\n').next());
+            let text = std::str::from_utf8(footer.ok_or_else(invalid)?).map_err(|_| invalid())?;
+            if !text.is_empty() {
+                let invalid_rule = || ValidationError::new(format!("invalid TZ rule: {text}"));
+                rule = Some(PosixRule::parse(text).ok_or_else(invalid_rule)?);
+            }
+        }
+        let transitions = times.into_iter().zip(indices.iter().map(|&i| i as usize)).collect();
+        Ok(ZoneRules { transitions, types: local_times, rule })
+    }
+
+    /// The local time in effect at instant `ms`.
+    pub(crate) fn local_time(&self, ms: i64) -> &LocalTime {
+        let secs = ms.div_euclid(MS_PER_SEC);
+        let next = self.transitions.partition_point(|&(t, _)| t <= secs);
+        match &self.rule {
+            Some(rule) if next == self.transitions.len() => rule.local_time(secs),
+            // Times before the first transition take the first type.
+            _ if next == 0 => &self.types[0],
+            _ => &self.types[self.transitions[next - 1].1],
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use wasm_bindgen::prelude::*;
+
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::intervals::IntervalSet;
+
+/// Up and down history of a service, for status pages. Record each change of state as it is observed;
+/// availability over any window then counts only the time whose state is known, from the first record
+/// on. Durations render for people with `UniDuration.formatDuration`.
+/// # Examples
+/// ```
+/// const api = new UptimeTracker();
+/// api.record(true, Date.parse("2024-06-01T00:00Z"));
+/// api.record(false, Date.parse("2024-06-03T14:00Z"));
+/// api.record(true, Date.parse("2024-06-03T14:45Z"));
+/// api.availability(Date.parse("2024-06-01T00:00Z")).toFixed(3); // "99.xxx"
+/// api.downtime(Date.parse("2024-06-01T00:00Z")).formatDuration(); // "45 min"
+/// ```
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct UptimeTracker {
+    /// Changes of state in epoch milliseconds, sorted by time, with whether the service came up.
+    changes: Vec<(i64, bool)>,
+}
+
+impl UptimeTracker {
+    /// Milliseconds of `[start, end)` spent up and down.
+    fn split(&self, start: i64, end: i64) -> (i64, i64) {
+        let mut totals = (0, 0);
+        for (i, &(from, up)) in self.changes.iter().enumerate() {
+            let to = self.changes.get(i + 1).map_or(end, |&(t, _)| t);
+            let ms = (to.min(end) - from.max(start)).max(0);
+            if up {
+                totals.0 += ms;
+            } else {
+                totals.1 += ms;
+            }
+        }
+        totals
+    }
+
+    fn window(start: f64, end: Option<f64>) -> (i64, i64) {
+        (start.floor() as i64, end.map_or_else(crate::now_ms, |ms| ms.floor() as i64))
+    }
+}
+
+#[wasm_bindgen]
+impl UptimeTracker {
+    /// Creates a new `UptimeTracker` with no history.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> UptimeTracker {
+        UptimeTracker::default()
+    }
+
+    /// Record that the service was up, or down if `up` is `false`, from `at` in epoch milliseconds
+    /// (the current time if omitted). Records may arrive out of order; one that repeats the state
+    /// before it changes nothing.
+    pub fn record(&mut self, up: bool, at: Option<f64>) {
+        let at = at.map_or_else(crate::now_ms, |ms| ms.floor() as i64);
+        let i = self.changes.partition_point(|&(t, _)| t <= at);
+        if i > 0 && self.changes[i - 1].1 == up {
+            return;
+        }
+        self.changes.insert(i, (at, up));
+        // A following record of the same state is now redundant.
+        if self.changes.get(i + 1).is_some_and(|&(_, next)| next == up) {
+            self.changes.remove(i + 1);
+        }
+    }
+
+    /// Get whether the service is up according to the latest record, or `undefined` with no records.
+    #[wasm_bindgen(getter, js_name = "isUp")]
+    pub fn is_up(&self) -> Option<bool> {
+        self.changes.last().map(|&(_, up)| up)
+    }
+
+    /// Get the number of recorded changes of state.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Get the percentage of known time from `start` to `end` in epoch milliseconds (the current time if
+    /// omitted) that the service was up, or `undefined` if no state is known in that window.
+    pub fn availability(&self, start: f64, end: Option<f64>) -> Option<f64> {
+        let (start, end) = UptimeTracker::window(start, end);
+        let (up, down) = self.split(start, end);
+        (up + down > 0).then(|| up as f64 * 100.0 / (up + down) as f64)
+    }
+
+    /// Get the time the service was up from `start` to `end` in epoch milliseconds (the current time if omitted).
+    pub fn uptime(&self, start: f64, end: Option<f64>) -> UniDuration {
+        let (start, end) = UptimeTracker::window(start, end);
+        UniDuration::from_nanos(self.split(start, end).0.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Get the time the service was down from `start` to `end` in epoch milliseconds (the current time if omitted).
+    pub fn downtime(&self, start: f64, end: Option<f64>) -> UniDuration {
+        let (start, end) = UptimeTracker::window(start, end);
+        UniDuration::from_nanos(self.split(start, end).1.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Get the outages from `start` to `end` in epoch milliseconds (the current time if omitted), cut to
+    /// that window, e.g. to draw a status bar.
+    pub fn outages(&self, start: f64, end: Option<f64>) -> IntervalSet {
+        let (start, end) = UptimeTracker::window(start, end);
+        let mut outages = IntervalSet::new();
+        for (i, &(from, up)) in self.changes.iter().enumerate() {
+            if !up {
+                let to = self.changes.get(i + 1).map_or(end, |&(t, _)| t);
+                outages.insert(from.max(start), to.min(end));
+            }
+        }
+        outages
+    }
+
+    /// Remove all history.
+    pub fn clear(&mut self) {
+        self.changes.clear();
+    }
+}
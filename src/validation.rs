@@ -0,0 +1,197 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_in_month, DateTime, MAX_YEAR};
+use crate::js_date::MAX_DATE_MS;
+use crate::logger::{log, LogKind};
+
+/// What kind of error a `ValidationError` is. The codes stay the same from release to release, unlike
+/// messages, so code can branch on them.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// A value that is wrong in some other way, such as an unknown option.
+    Invalid = "invalid",
+    /// Input that does not follow the expected syntax; `offset` says where.
+    Syntax = "syntax",
+    /// A field out of range; `field`, `value`, `min` and `max` say which and how.
+    Range = "range",
+    /// Something the JS host lacks, such as `Intl.DateTimeFormat` or a secure random source.
+    Unavailable = "unavailable",
+    /// A bug in Unitime. Please report it.
+    Internal = "internal",
+}
+
+/// What was wrong with a value given to a parser or constructor, thrown instead of a plain `Error` so
+/// forms can point at the problem. Every error has a stable `code`. Syntax errors carry the character
+/// `offset` where the input stopped making sense; range errors carry the `field`, its `value`, and the
+/// allowed `min` and `max`.
+/// # Examples
+/// ```
+/// try {
+///     Unitime.fromSQLString("2024-02-30 10:00", true);
+/// } catch (e) {
+///     if (e instanceof ValidationError && e.code === "range") {
+///         console.log(e.field, e.value, e.min, e.max); // "day" 30 1 29
+///     }
+/// }
+/// ```
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    code: ErrorCode,
+    message: String,
+    field: Option<&'static str>,
+    value: Option<f64>,
+    range: Option<(f64, f64)>,
+    offset: Option<usize>,
+}
+
+impl ValidationError {
+    /// An error without any detail beyond its message.
+    pub(crate) fn new(message: impl Into<String>) -> ValidationError {
+        ValidationError {
+            code: ErrorCode::Invalid,
+            message: message.into(),
+            field: None,
+            value: None,
+            range: None,
+            offset: None,
+        }
+    }
+
+    /// Input that does not follow the expected syntax, from character `offset` on.
+    pub(crate) fn syntax(message: impl Into<String>, offset: usize) -> ValidationError {
+        let message = message.into();
+        log(LogKind::Parse, || format!("{message} (at character {offset})"));
+        ValidationError { code: ErrorCode::Syntax, offset: Some(offset), ..ValidationError::new(message) }
+    }
+
+    /// Something the JS host does not provide.
+    pub(crate) fn unavailable(message: impl Into<String>) -> ValidationError {
+        ValidationError { code: ErrorCode::Unavailable, ..ValidationError::new(message) }
+    }
+
+    /// Something that cannot happen unless Unitime has a bug.
+    pub(crate) fn internal(message: impl Into<String>) -> ValidationError {
+        ValidationError { code: ErrorCode::Internal, ..ValidationError::new(message) }
+    }
+
+    /// A `field` whose `value` lies outside `min..=max`.
+    pub(crate) fn range(field: &'static str, value: i64, min: i64, max: i64) -> ValidationError {
+        ValidationError {
+            code: ErrorCode::Range,
+            field: Some(field),
+            value: Some(value as f64),
+            range: Some((min as f64, max as f64)),
+            ..ValidationError::new(format!("{field} {value} out of range {min} to {max}"))
+        }
+    }
+}
+
+/// Count the characters in the first `byte` bytes of `text`, for offsets that make sense to JavaScript.
+pub(crate) fn char_offset(text: &str, byte: usize) -> usize {
+    text.get(..byte).map_or(byte, |prefix| prefix.chars().count())
+}
+
+/// Check that `year` is within the years whose dates `Unitime` can hold.
+pub(crate) fn check_year(year: i64) -> Result<(), ValidationError> {
+    if !(-MAX_YEAR..=MAX_YEAR).contains(&year) {
+        return Err(ValidationError::range("year", year, -MAX_YEAR, MAX_YEAR));
+    }
+    Ok(())
+}
+
+/// Check epoch milliseconds `ms` given as `field` against the range of a JavaScript `Date`, returning
+/// them in whole milliseconds.
+#[cfg_attr(not(feature = "timers"), allow(dead_code))]
+pub(crate) fn check_date_ms(field: &'static str, ms: f64) -> Result<i64, ValidationError> {
+    if ms.is_nan() {
+        return Err(ValidationError::new(format!("{field} is NaN")));
+    }
+    if !(-(MAX_DATE_MS as f64)..=MAX_DATE_MS as f64).contains(&ms) {
+        return Err(ValidationError::range(field, ms as i64, -MAX_DATE_MS, MAX_DATE_MS));
+    }
+    Ok(ms.floor() as i64)
+}
+
+/// Check a calendar date, reporting the first field out of range.
+pub(crate) fn check_date(year: i64, month: u32, day: u32) -> Result<(), ValidationError> {
+    check_year(year)?;
+    if !(1..=12).contains(&month) {
+        return Err(ValidationError::range("month", month as i64, 1, 12));
+    }
+    let max_day = days_in_month(year, month);
+    if !(1..=max_day).contains(&day) {
+        return Err(ValidationError::range("day", day as i64, 1, max_day as i64));
+    }
+    Ok(())
+}
+
+impl DateTime {
+    /// Check every field like `is_valid`, reporting the first one out of range.
+    #[cfg_attr(not(any(feature = "parse", feature = "tz")), allow(dead_code))]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_date(self.year, self.month, self.day)?;
+        let limits = [
+            ("hour", self.hour, 23),
+            ("minute", self.minute, 59),
+            ("second", self.second, 59),
+            ("millisecond", self.milli, 999),
+        ];
+        match limits.iter().find(|(_, value, max)| value > max) {
+            Some(&(field, value, max)) => Err(ValidationError::range(field, value as i64, 0, max as i64)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl ValidationError {
+    /// Get the kind of error, which unlike the message stays the same from release to release.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Get the description of the error.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    /// Get the name of the field that was out of range, such as `"month"`, or `undefined` for other errors.
+    #[wasm_bindgen(getter)]
+    pub fn field(&self) -> Option<String> {
+        self.field.map(str::to_string)
+    }
+
+    /// Get the value of the field that was out of range.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Get the smallest value the field allows.
+    #[wasm_bindgen(getter)]
+    pub fn min(&self) -> Option<f64> {
+        self.range.map(|(min, _)| min)
+    }
+
+    /// Get the largest value the field allows.
+    #[wasm_bindgen(getter)]
+    pub fn max(&self) -> Option<f64> {
+        self.range.map(|(_, max)| max)
+    }
+
+    /// Get the character offset into the input where parsing failed, or `undefined` for range errors.
+    #[wasm_bindgen(getter)]
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Get the message, prefixed like a JavaScript error's.
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_js(&self) -> String {
+        format!("ValidationError: {}", self.message)
+    }
+}
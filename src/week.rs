@@ -0,0 +1,106 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{civil_from_days, days_from_civil, weekday, MS_PER_DAY};
+use crate::month::midnight;
+use crate::validation::ValidationError;
+use crate::Unitime;
+
+/// How weeks are numbered within a year: the day they start on, and which week counts as the first.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeekRule {
+    /// ISO 8601: weeks start on Monday, and week 1 is the one holding the year's first Thursday.
+    Iso = "iso",
+    /// United States: weeks start on Sunday, and week 1 is the one holding January 1.
+    Us = "us",
+    /// Much of the Middle East: weeks start on Saturday, and week 1 is the one holding January 1.
+    MiddleEast = "middle-east",
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for WeekRule {
+    fn default() -> Self {
+        WeekRule::Iso
+    }
+}
+
+impl WeekRule {
+    /// The weekday weeks start on, from 0 for Sunday, and the fewest days of a year its first week holds.
+    fn params(self) -> Result<(i64, i64), ValidationError> {
+        match self {
+            WeekRule::Iso => Ok((1, 4)),
+            WeekRule::Us => Ok((0, 1)),
+            WeekRule::MiddleEast => Ok((6, 1)),
+            _ => Err(ValidationError::new("unknown week rule")),
+        }
+    }
+
+    /// The first day of the week holding day `days`, both in days since 1970-01-01.
+    pub(crate) fn week_start(self, days: i64) -> Result<i64, ValidationError> {
+        Ok(days - (weekday(days) as i64 - self.params()?.0).rem_euclid(7))
+    }
+
+    /// The first day of week 1 of `year`.
+    fn first_week(self, year: i64) -> Result<i64, ValidationError> {
+        let jan1 = days_from_civil(year, 1, 1);
+        let start = self.week_start(jan1)?;
+        Ok(if start + 7 - jan1 < self.params()?.1 { start + 7 } else { start })
+    }
+
+    /// The week-numbering year and the week of day `days`. Days at either end of a year can belong to
+    /// a week of the year before or after.
+    pub(crate) fn week_of(self, days: i64) -> Result<(i64, u32), ValidationError> {
+        let (year, _, _) = civil_from_days(days);
+        let year = if days < self.first_week(year)? {
+            year - 1
+        } else if days >= self.first_week(year + 1)? {
+            year + 1
+        } else {
+            year
+        };
+        Ok((year, ((self.week_start(days)? - self.first_week(year)?) / 7 + 1) as u32))
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get the week of the year of the stored time under `rule`, ISO 8601 by default, in local time
+    /// unless `utc` is `true`. Around the new year the week may belong to the year before or after,
+    /// which `getWeekYear` gives.
+    /// # Examples
+    /// ```
+    /// const t = new Unitime().fromEpochMil(Date.UTC(2024, 11, 30));
+    /// t.getWeek("iso", true); // 1, of 2025
+    /// t.getWeek("us", true); // 1, of 2025
+    /// ```
+    #[wasm_bindgen(js_name = "getWeek")]
+    pub fn get_week(&self, rule: Option<WeekRule>, utc: Option<bool>) -> Result<u32, ValidationError> {
+        Ok(rule.unwrap_or_default().week_of(self.wall_days(utc))?.1)
+    }
+
+    /// Get the year that the week `getWeek` gives belongs to under `rule`, ISO 8601 by default, in local
+    /// time unless `utc` is `true`.
+    #[wasm_bindgen(js_name = "getWeekYear")]
+    pub fn get_week_year(&self, rule: Option<WeekRule>, utc: Option<bool>) -> Result<i32, ValidationError> {
+        Ok(rule.unwrap_or_default().week_of(self.wall_days(utc))?.0 as i32)
+    }
+
+    /// Get midnight starting the week of the stored time under `rule`: the Monday for ISO 8601, the
+    /// default, the Sunday for `"us"` and the Saturday for `"middle-east"`. In local time unless `utc`
+    /// is `true`.
+    /// # Examples
+    /// ```
+    /// const monday = new Unitime().startOfWeek();
+    /// ```
+    #[wasm_bindgen(js_name = "startOfWeek")]
+    pub fn start_of_week(&self, rule: Option<WeekRule>, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        Ok(midnight(rule.unwrap_or_default().week_start(self.wall_days(utc))?, utc))
+    }
+}
+
+impl Unitime {
+    /// The day of the stored time's wall-clock reading, in days since 1970-01-01.
+    fn wall_days(&self, utc: Option<bool>) -> i64 {
+        self.wall_ms(utc.unwrap_or(false)).div_euclid(MS_PER_DAY)
+    }
+}
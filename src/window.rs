@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::ValidationError;
+
+/// Trailing time window over event timestamps, for live counters such as requests per second.
+/// Events older than the window are evicted as new events arrive or the window is queried.
+/// Every method taking `now` uses the current time if it is omitted.
+#[wasm_bindgen]
+pub struct SlidingWindow {
+    duration_ms: f64,
+    events: VecDeque<f64>,
+}
+
+impl SlidingWindow {
+    fn evict(&mut self, now: Option<f64>) {
+        let cutoff = now.unwrap_or_else(|| crate::now_ms() as f64) - self.duration_ms;
+        while self.events.front().is_some_and(|&t| t <= cutoff) {
+            self.events.pop_front();
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl SlidingWindow {
+    /// Creates a new `SlidingWindow` covering the trailing `duration_ms` milliseconds.
+    /// # Examples
+    /// ```
+    /// const w = new SlidingWindow(10000);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(duration_ms: f64) -> Result<SlidingWindow, ValidationError> {
+        if duration_ms.is_nan() || duration_ms <= 0.0 {
+            return Err(ValidationError::new("window duration must be positive"));
+        }
+        Ok(SlidingWindow { duration_ms, events: VecDeque::new() })
+    }
+
+    /// Get the length of the window in milliseconds.
+    #[wasm_bindgen(getter, js_name = "durationMs")]
+    pub fn duration_ms(&self) -> f64 {
+        self.duration_ms
+    }
+
+    /// Record an event at time `t` in epoch milliseconds, or now if omitted.
+    /// Events must be pushed in chronological order; an event earlier than the latest one is ignored.
+    /// # Examples
+    /// ```
+    /// const w = new SlidingWindow(1000);
+    /// socket.onmessage = () => w.push();
+    /// ```
+    pub fn push(&mut self, t: Option<f64>) {
+        let t = t.unwrap_or_else(|| crate::now_ms() as f64);
+        if t.is_nan() || self.events.back().is_some_and(|&last| t < last) {
+            return;
+        }
+        self.events.push_back(t);
+        self.evict(Some(t));
+    }
+
+    /// Get the number of events within the window ending at `now`.
+    /// # Examples
+    /// ```
+    /// const n = w.count();
+    /// ```
+    pub fn count(&mut self, now: Option<f64>) -> usize {
+        self.evict(now);
+        self.events.len()
+    }
+
+    /// Get the average number of events per second within the window ending at `now`.
+    /// # Examples
+    /// ```
+    /// const rps = w.ratePerSecond();
+    /// ```
+    #[wasm_bindgen(js_name = "ratePerSecond")]
+    pub fn rate_per_second(&mut self, now: Option<f64>) -> f64 {
+        self.count(now) as f64 * 1000.0 / self.duration_ms
+    }
+
+    /// Get the time in milliseconds between each pair of consecutive events within the window ending at `now`.
+    /// # Examples
+    /// ```
+    /// const gaps = w.gaps();
+    /// ```
+    pub fn gaps(&mut self, now: Option<f64>) -> Vec<f64> {
+        self.evict(now);
+        let (a, b) = self.events.as_slices();
+        let times: Vec<f64> = a.iter().chain(b).copied().collect();
+        times.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Get the longest quiet period within the window ending at `now`, including the time before the first
+    /// and after the last event. This is the whole window if there were no events.
+    /// # Examples
+    /// ```
+    /// if (w.maxGap() > 5000) showIdle();
+    /// ```
+    #[wasm_bindgen(js_name = "maxGap")]
+    pub fn max_gap(&mut self, now: Option<f64>) -> f64 {
+        let now = now.unwrap_or_else(|| crate::now_ms() as f64);
+        self.evict(Some(now));
+        let mut prev = now - self.duration_ms;
+        let mut max = 0.0f64;
+        for &t in self.events.iter().chain(std::iter::once(&now)) {
+            max = max.max(t - prev);
+            prev = t;
+        }
+        max
+    }
+
+    /// Get the timestamps of the events within the window ending at `now`.
+    pub fn events(&mut self, now: Option<f64>) -> Vec<f64> {
+        self.evict(now);
+        self.events.iter().copied().collect()
+    }
+
+    /// Remove all recorded events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
@@ -0,0 +1,124 @@
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{days_in_month, wall_to_instant, DateTime};
+use crate::validation::{check_year, ValidationError};
+use crate::{Unitime, NANOS_PER_MILLI};
+
+/// Check that `value` of `field` is from `min` to `max`.
+fn check(field: &'static str, value: u32, min: u32, max: u32) -> Result<(), ValidationError> {
+    if !(min..=max).contains(&value) {
+        return Err(ValidationError::range(field, value as i64, min as i64, max as i64));
+    }
+    Ok(())
+}
+
+impl Unitime {
+    /// A new `Unitime` with the wall-clock fields of the stored time, in UTC if `utc` is `true` and else
+    /// in local time, changed by `change`. A day past the end of the month moves back to its last day,
+    /// and the part of the stored time below a millisecond is kept.
+    fn with_fields(
+        &self,
+        utc: Option<bool>,
+        change: impl FnOnce(&mut DateTime) -> Result<(), ValidationError>,
+    ) -> Result<Unitime, ValidationError> {
+        let utc = utc.unwrap_or(false);
+        let mut dt = DateTime::from_epoch_ms(self.wall_ms(utc));
+        change(&mut dt)?;
+        check_year(dt.year)?;
+        dt.day = dt.day.min(days_in_month(dt.year, dt.month));
+        let ms = wall_to_instant(dt.to_epoch_ms(), utc);
+        Ok(Unitime::from_nanos(ms as i128 * NANOS_PER_MILLI + self.nanos.rem_euclid(NANOS_PER_MILLI)))
+    }
+}
+
+#[wasm_bindgen]
+impl Unitime {
+    /// Get a new `Unitime` at the same wall-clock date and time in `year`, in local time unless `utc` is
+    /// `true`. February 29 becomes February 28 in years without one.
+    /// # Examples
+    /// ```
+    /// const sameDay2030 = new Unitime().withYear(2030);
+    /// ```
+    #[wasm_bindgen(js_name = "withYear")]
+    pub fn with_year(&self, year: i32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        self.with_fields(utc, |dt| {
+            dt.year = year as i64;
+            Ok(())
+        })
+    }
+
+    /// Get a new `Unitime` at the same wall-clock day and time in `month`, from 1 for January, of the
+    /// same year, in local time unless `utc` is `true`. Days past the end of the month move back to its
+    /// last day, so January 31 with month 2 is February 28 or 29.
+    /// # Examples
+    /// ```
+    /// new Unitime().fromEpochMil(Date.UTC(2024, 0, 31)).withMonth(2, true); // 2024-02-29
+    /// ```
+    #[wasm_bindgen(js_name = "withMonth")]
+    pub fn with_month(&self, month: u32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        check("month", month, 1, 12)?;
+        self.with_fields(utc, |dt| {
+            dt.month = month;
+            Ok(())
+        })
+    }
+
+    /// Get a new `Unitime` on `day` of the same month at the same wall-clock time, in local time unless
+    /// `utc` is `true`. The day must be in the month.
+    #[wasm_bindgen(js_name = "withDay")]
+    pub fn with_day(&self, day: u32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        self.with_fields(utc, |dt| {
+            check("day", day, 1, days_in_month(dt.year, dt.month))?;
+            dt.day = day;
+            Ok(())
+        })
+    }
+
+    /// Get a new `Unitime` at `hour`, from 0 to 23, of the same wall-clock day, keeping the minutes and
+    /// seconds, in local time unless `utc` is `true`.
+    /// # Examples
+    /// ```
+    /// const nineSharp = new Unitime().withHour(9).withMinute(0).withSecond(0).withMillisecond(0);
+    /// ```
+    #[wasm_bindgen(js_name = "withHour")]
+    pub fn with_hour(&self, hour: u32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        check("hour", hour, 0, 23)?;
+        self.with_fields(utc, |dt| {
+            dt.hour = hour;
+            Ok(())
+        })
+    }
+
+    /// Get a new `Unitime` at `minute`, from 0 to 59, of the same wall-clock hour, in local time unless
+    /// `utc` is `true`.
+    #[wasm_bindgen(js_name = "withMinute")]
+    pub fn with_minute(&self, minute: u32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        check("minute", minute, 0, 59)?;
+        self.with_fields(utc, |dt| {
+            dt.minute = minute;
+            Ok(())
+        })
+    }
+
+    /// Get a new `Unitime` at `second`, from 0 to 59, of the same wall-clock minute, in local time unless
+    /// `utc` is `true`.
+    #[wasm_bindgen(js_name = "withSecond")]
+    pub fn with_second(&self, second: u32, utc: Option<bool>) -> Result<Unitime, ValidationError> {
+        check("second", second, 0, 59)?;
+        self.with_fields(utc, |dt| {
+            dt.second = second;
+            Ok(())
+        })
+    }
+
+    /// Get a new `Unitime` at `millisecond`, from 0 to 999, of the same second. The part below a
+    /// millisecond is kept.
+    #[wasm_bindgen(js_name = "withMillisecond")]
+    pub fn with_millisecond(&self, millisecond: u32) -> Result<Unitime, ValidationError> {
+        check("millisecond", millisecond, 0, 999)?;
+        self.with_fields(Some(true), |dt| {
+            dt.milli = millisecond;
+            Ok(())
+        })
+    }
+}
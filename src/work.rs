@@ -0,0 +1,206 @@
+use std::collections::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+
+use crate::civil::{self, days_from_civil, weekday, MS_PER_DAY, MS_PER_HOUR, MS_PER_MIN};
+use crate::duration::{UniDuration, NANOS_PER_MILLI};
+use crate::parse::Cursor;
+use crate::validation::{char_offset, check_date, ValidationError};
+use crate::Unitime;
+
+/// Days in 10,000 Gregorian years, the furthest `addWorkingHours` looks for working time.
+const MAX_SPAN_DAYS: i64 = 3_652_425;
+
+/// Read a time of day in `HH:MM` form, up to `24:00`, as milliseconds after midnight.
+fn time_of_day(s: &str) -> Result<i64, ValidationError> {
+    let mut c = Cursor::new(s.trim());
+    let lead = s.len() - s.trim_start().len();
+    let syntax = |c: &Cursor| {
+        ValidationError::syntax(format!("invalid time of day: {s}"), char_offset(s, lead + c.pos()))
+    };
+    let hour = c.digits(1, 2).ok_or_else(|| syntax(&c))?.0;
+    if !c.eat(b':') {
+        return Err(syntax(&c));
+    }
+    let minute = c.fixed(2).ok_or_else(|| syntax(&c))?;
+    if !c.is_end() {
+        return Err(syntax(&c));
+    }
+    if minute > 59 {
+        return Err(ValidationError::range("minute", minute as i64, 0, 59));
+    }
+    let ms = hour as i64 * MS_PER_HOUR + minute as i64 * MS_PER_MIN;
+    if ms > MS_PER_DAY {
+        return Err(ValidationError::range("hour", hour as i64, 0, 24));
+    }
+    Ok(ms)
+}
+
+/// Opening hours for each day of the week and a list of holidays, for computing in business time.
+/// Hours are wall-clock times in local time unless the schedule was created for UTC.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct WorkSchedule {
+    /// Opening and closing time in milliseconds after midnight for each weekday from Sunday, or `None` when closed.
+    hours: [Option<(i64, i64)>; 7],
+    /// Holidays as days after 1970-01-01.
+    holidays: BTreeSet<i64>,
+    utc: bool,
+}
+
+impl WorkSchedule {
+    /// The working hours of wall-clock day `day` as a range of instants, if it is a working day whose
+    /// hours `Unitime` can hold.
+    fn window(&self, day: i64) -> Option<(i64, i64)> {
+        if self.holidays.contains(&day) {
+            return None;
+        }
+        let (open, close) = self.hours[weekday(day)]?;
+        let start = day.checked_mul(MS_PER_DAY)?;
+        let (open, close) = (start.checked_add(open)?, start.checked_add(close)?);
+        Some((civil::wall_to_instant(open, self.utc), civil::wall_to_instant(close, self.utc)))
+    }
+
+    /// Mark day `day` after 1970-01-01 as a holiday.
+    pub(crate) fn insert_holiday(&mut self, day: i64) {
+        self.holidays.insert(day);
+    }
+
+    fn day_of(&self, ms: i64) -> i64 {
+        civil::instant_to_wall(ms, self.utc).div_euclid(MS_PER_DAY)
+    }
+
+    /// Working milliseconds on the wall clock of day `day` if it were not a holiday.
+    fn wall_hours(&self, day: i64) -> i64 {
+        self.hours[weekday(day)].map_or(0, |(open, close)| close - open)
+    }
+
+    /// Working milliseconds from instant `a` to a later instant `b`. Only the days in the partial weeks
+    /// at each end are looked at one by one; the whole weeks between them count the opening hours of a
+    /// week less those of the holidays in them, on the wall clock.
+    fn working_ms(&self, a: i64, b: i64) -> i64 {
+        let in_span = |day: i64| {
+            self.window(day).map_or(0, |(open, close)| (close.min(b).saturating_sub(open.max(a))).max(0))
+        };
+        let (first, last) = (self.day_of(a), self.day_of(b));
+        // Whole weeks start the day after the first and end the day before the last, which both
+        // need clipping to the span.
+        let weeks = (last - first - 1).max(0) / 7;
+        let (middle, tail) = (first + 1, first + 1 + weeks * 7);
+        let week_ms: i64 = (0..7).map(|day| self.wall_hours(day)).sum();
+        let holiday_ms: i64 = self.holidays.range(middle..tail).map(|&day| self.wall_hours(day)).sum();
+        let ends = (first..middle.min(last + 1)).chain(tail.max(middle)..=last).map(in_span);
+        ends.fold(weeks.saturating_mul(week_ms) - holiday_ms, i64::saturating_add)
+    }
+}
+
+#[wasm_bindgen]
+impl WorkSchedule {
+    /// Creates a new `WorkSchedule` open 09:00 to 17:00 Monday to Friday, in local time unless `utc` is `true`.
+    /// # Examples
+    /// ```
+    /// const support = new WorkSchedule();
+    /// support.setHours(5, "09:00", "13:00"); // short Fridays
+    /// support.addHoliday(2024, 12, 25);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(utc: Option<bool>) -> WorkSchedule {
+        let day = Some((9 * MS_PER_HOUR, 17 * MS_PER_HOUR));
+        WorkSchedule {
+            hours: [None, day, day, day, day, day, None],
+            holidays: BTreeSet::new(),
+            utc: utc.unwrap_or(false),
+        }
+    }
+
+    /// Set the opening hours of `weekday`, from 0 for Sunday to 6 for Saturday, as `HH:MM` times. `close`
+    /// may be `24:00` and must be after `open`.
+    #[wasm_bindgen(js_name = "setHours")]
+    pub fn set_hours(&mut self, weekday: u32, open: &str, close: &str) -> Result<(), ValidationError> {
+        if weekday > 6 {
+            return Err(ValidationError::range("weekday", weekday as i64, 0, 6));
+        }
+        let (open, close) = (time_of_day(open)?, time_of_day(close)?);
+        if close <= open {
+            return Err(ValidationError::new("closing time must be after opening time"));
+        }
+        self.hours[weekday as usize] = Some((open, close));
+        Ok(())
+    }
+
+    /// Mark `weekday`, from 0 for Sunday to 6 for Saturday, as closed all day.
+    #[wasm_bindgen(js_name = "closeDay")]
+    pub fn close_day(&mut self, weekday: u32) -> Result<(), ValidationError> {
+        if weekday > 6 {
+            return Err(ValidationError::range("weekday", weekday as i64, 0, 6));
+        }
+        self.hours[weekday as usize] = None;
+        Ok(())
+    }
+
+    /// Mark a date as a holiday with no working hours.
+    #[wasm_bindgen(js_name = "addHoliday")]
+    pub fn add_holiday(&mut self, year: i32, month: u32, day: u32) -> Result<(), ValidationError> {
+        check_date(year as i64, month, day)?;
+        self.insert_holiday(days_from_civil(year as i64, month, day));
+        Ok(())
+    }
+
+    /// Check whether `t` falls within working hours.
+    #[wasm_bindgen(js_name = "isWorkingTime")]
+    pub fn is_working_time(&self, t: &Unitime) -> bool {
+        let ms = t.epoch_ms();
+        self.window(self.day_of(ms)).is_some_and(|(open, close)| (open..close).contains(&ms))
+    }
+
+    /// Get the working time from `a` to `b`, which is negative if `b` is before `a`. Weeks other than
+    /// the first and last are counted on the wall clock, so a clock change during their opening
+    /// hours does not lengthen or shorten them.
+    /// # Examples
+    /// ```
+    /// const sla = support.workingDurationBetween(ticket.openedAt, new Unitime());
+    /// if (sla.seconds > 8 * 3600) escalate(ticket);
+    /// ```
+    #[wasm_bindgen(js_name = "workingDurationBetween")]
+    pub fn working_duration_between(&self, a: &Unitime, b: &Unitime) -> UniDuration {
+        let (a, b) = (a.epoch_ms(), b.epoch_ms());
+        let ms = if a <= b { self.working_ms(a, b) } else { -self.working_ms(b, a) };
+        UniDuration::from_nanos(ms.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    /// Get the time `hours` working hours after `t`, skipping closed days, holidays and time outside
+    /// opening hours. A result that lands exactly on closing time stays there rather than moving to the
+    /// next opening, while adding 0 to a time outside working hours moves it to the next opening. Fails
+    /// if that is more than 10,000 years after `t`.
+    /// # Examples
+    /// ```
+    /// const due = support.addWorkingHours(ticket.openedAt, 16);
+    /// ```
+    #[wasm_bindgen(js_name = "addWorkingHours")]
+    pub fn add_working_hours(&self, t: &Unitime, hours: f64) -> Result<Unitime, ValidationError> {
+        if !(hours >= 0.0 && hours.is_finite()) {
+            return Err(ValidationError::new("working hours to add must be a non-negative number"));
+        }
+        if self.hours.iter().all(Option::is_none) {
+            return Err(ValidationError::new("the schedule has no working hours"));
+        }
+        let mut left = (hours * MS_PER_HOUR as f64).round() as i64;
+        let mut now = t.epoch_ms();
+        let mut day = self.day_of(now);
+        let last = day + MAX_SPAN_DAYS;
+        while day <= last {
+            if let Some((open, close)) = self.window(day) {
+                let start = now.max(open);
+                if start < close {
+                    if left <= close - start {
+                        return Ok(Unitime::from_epoch_ms(start.saturating_add(left)));
+                    }
+                    left -= close - start;
+                }
+                now = now.max(close);
+            }
+            day += 1;
+        }
+        Err(ValidationError::new("working hours to add reach more than 10,000 years after the start"))
+    }
+}